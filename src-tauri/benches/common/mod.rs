@@ -0,0 +1,29 @@
+//! Timing helpers shared by the benchmarks
+
+use std::time::Instant;
+
+/// Runs a baseline and a candidate workload once each and prints how much faster the candidate is
+///
+/// # Parameters
+/// * `label` - Description of the workload
+/// * `baseline` - Name and workload of the slower approach
+/// * `candidate` - Name and workload of the approach being measured against it
+pub fn compare_timings(label: &str, baseline: (&str, impl FnOnce()), candidate: (&str, impl FnOnce())) {
+    let start = Instant::now();
+    (baseline.1)();
+    let baseline_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    (candidate.1)();
+    let candidate_elapsed = start.elapsed();
+
+    println!(
+        "{}: {} {:?}, {} {:?} ({:.1}x faster)",
+        label,
+        baseline.0,
+        baseline_elapsed,
+        candidate.0,
+        candidate_elapsed,
+        baseline_elapsed.as_secs_f64() / candidate_elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,37 @@
+//! Note manager benchmarks
+//!
+//! Run with `cargo bench --bench notes`.
+
+mod common;
+
+use common::compare_timings;
+use notter_app_lib::bench::{NoteManager, NoteType};
+use tempfile::TempDir;
+
+/// Compares `check_note_exists` with `get_note(...).is_ok()`
+fn bench_check_note_exists() {
+    const CHECKS: usize = 10_000;
+
+    let temp_dir = TempDir::new().unwrap();
+    let manager = NoteManager::new(temp_dir.path().to_path_buf());
+    let content = "Lorem ipsum dolor sit amet. ".repeat(200);
+    let note = manager.create_note("Bench", &content, NoteType::Markdown, None).unwrap();
+
+    compare_timings(
+        &format!("{} checks", CHECKS),
+        ("get_note", || {
+            for _ in 0..CHECKS {
+                assert!(manager.get_note(&note.id).is_ok());
+            }
+        }),
+        ("check_note_exists", || {
+            for _ in 0..CHECKS {
+                assert!(manager.check_note_exists(&note.id));
+            }
+        }),
+    );
+}
+
+fn main() {
+    bench_check_note_exists();
+}
@@ -0,0 +1,107 @@
+//! Search index benchmarks
+//!
+//! Run with `cargo bench --bench search`.
+
+mod common;
+
+use chrono::Utc;
+use common::compare_timings;
+use notter_app_lib::bench::{AppConfig, Note, NoteManager, NoteType, SearchService};
+use tempfile::TempDir;
+
+/// Creates a Markdown note whose title is its ID
+fn note(id: &str, content: &str, tags: &[&str]) -> Note {
+    Note {
+        id: id.to_string(),
+        title: id.to_string(),
+        content: content.to_string(),
+        created: Utc::now(),
+        modified: Utc::now(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        file_type: NoteType::Markdown,
+        path: format!("{}.md", id),
+    }
+}
+
+/// Compares `batch_index_notes` with calling `index_note` for each note
+fn bench_batch_index_notes() {
+    const NOTES: usize = 500;
+
+    let notes: Vec<Note> = (0..NOTES)
+        .map(|i| {
+            let content = format!("# Note {}\n\n{}", i, "Lorem ipsum. ".repeat(50));
+            note(&format!("note-{}", i), &content, &[format!("tag{}", i % 50).as_str()])
+        })
+        .collect();
+
+    let single_dir = TempDir::new().unwrap();
+    let single_service = SearchService::new(single_dir.path(), &AppConfig::default()).unwrap();
+    let batch_dir = TempDir::new().unwrap();
+    let batch_service = SearchService::new(batch_dir.path(), &AppConfig::default()).unwrap();
+
+    compare_timings(
+        &format!("{} notes", NOTES),
+        ("one commit per note", || {
+            for note in &notes {
+                single_service.index_note(note).unwrap();
+            }
+        }),
+        ("single commit", || batch_service.batch_index_notes(&notes).unwrap()),
+    );
+
+    assert_eq!(batch_service.document_count().unwrap(), NOTES);
+}
+
+/// Compares `search_by_tags` with filtering the note list like `filter_notes_by_tags`
+fn bench_search_by_tags() {
+    const NOTES: usize = 5_000;
+    const QUERIES: usize = 20;
+
+    let app_dir = TempDir::new().unwrap();
+    let notes_dir = TempDir::new().unwrap();
+    let manager = NoteManager::new(notes_dir.path().to_path_buf());
+    for i in 0..NOTES {
+        let body = "Lorem ipsum. ".repeat(50);
+        let content = format!("# Note {}\n\n{} #tag{} #group{}", i, body, i % 50, i % 7);
+        manager.create_note(&format!("Note {}", i), &content, NoteType::Markdown, None).unwrap();
+    }
+
+    let notes: Vec<Note> = manager
+        .list_notes(None)
+        .unwrap()
+        .iter()
+        .map(|summary| manager.get_note(&summary.id).unwrap())
+        .collect();
+    let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+    service.rebuild_index(&notes, |_| {}).unwrap();
+
+    let tags = ["tag3".to_string(), "group2".to_string()];
+    let mut listed = 0;
+    let mut indexed = 0;
+
+    compare_timings(
+        &format!("{} notes, {} queries", NOTES, QUERIES),
+        ("list filter", || {
+            for _ in 0..QUERIES {
+                listed = manager
+                    .list_notes(None)
+                    .unwrap()
+                    .into_iter()
+                    .filter(|note| tags.iter().all(|tag| note.tags.contains(tag)))
+                    .count();
+            }
+        }),
+        ("index", || {
+            for _ in 0..QUERIES {
+                indexed = service.search_by_tags(&["tag3", "group2"], true, NOTES).unwrap().len();
+            }
+        }),
+    );
+
+    assert_eq!(listed, indexed);
+}
+
+fn main() {
+    bench_batch_index_notes();
+    bench_search_by_tags();
+}
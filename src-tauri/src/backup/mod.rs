@@ -0,0 +1,290 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Format of the timestamp used as the file name of a backup (UTC)
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S-%3f";
+
+/// Format of backup file names without milliseconds, written by earlier versions
+const LEGACY_BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Extension of backup archives
+const BACKUP_EXTENSION: &str = "zip";
+
+/// Information about a backup archive
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    /// Path of the backup archive
+    pub path: PathBuf,
+    /// When the backup was created
+    pub timestamp: DateTime<Utc>,
+    /// Size of the archive in bytes
+    pub size_bytes: u64,
+}
+
+/// Creates, lists and restores ZIP backups of the notes directory
+pub struct BackupService;
+
+impl BackupService {
+    /// Creates a ZIP archive of the notes directory
+    ///
+    /// The archive is named after the current time in milliseconds; a later
+    /// millisecond is used if a backup with that name exists. If the backup
+    /// directory is inside the notes directory, it is left out of the archive.
+    ///
+    /// # Parameters
+    /// * `notes_dir` - Path to the notes directory
+    /// * `backup_dir` - Directory to write the archive to
+    ///
+    /// # Returns
+    /// Path of the created archive
+    pub fn create_backup(notes_dir: &Path, backup_dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(backup_dir).context("Failed to create backup directory")?;
+
+        let mut timestamp = Utc::now();
+        let (name, backup_path) = loop {
+            let name = timestamp.format(BACKUP_TIMESTAMP_FORMAT).to_string();
+            let backup_path = backup_dir.join(format!("{}.{}", name, BACKUP_EXTENSION));
+            if !backup_path.exists() {
+                break (name, backup_path);
+            }
+            timestamp += chrono::Duration::milliseconds(1);
+        };
+        // Write to a temporary file so an interrupted backup is never listed
+        let partial_path = backup_dir.join(format!("{}.{}.partial", name, BACKUP_EXTENSION));
+
+        let file = fs::File::create(&partial_path).context("Failed to create backup file")?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let excluded_dir = backup_dir.canonicalize().ok();
+        let entries = WalkDir::new(notes_dir)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|entry| {
+                excluded_dir.as_deref().map_or(true, |excluded| {
+                    entry.path().canonicalize().map_or(true, |path| path != excluded)
+                })
+            });
+
+        for entry in entries {
+            let entry = entry.context("Failed to read notes directory")?;
+            let relative_path = entry
+                .path()
+                .strip_prefix(notes_dir)
+                .context("Path is not in notes directory")?;
+
+            // ZIP archives always use `/` as the separator
+            let name = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if entry.file_type().is_dir() {
+                zip.add_directory(name, options)
+                    .context("Failed to add folder to backup")?;
+            } else if entry.file_type().is_file() {
+                zip.start_file(name, options)
+                    .context("Failed to add file to backup")?;
+                let mut source = fs::File::open(entry.path()).context("Failed to open file")?;
+                io::copy(&mut source, &mut zip).context("Failed to write file to backup")?;
+            }
+        }
+
+        zip.finish().context("Failed to finish backup archive")?;
+        fs::rename(&partial_path, &backup_path).context("Failed to save backup archive")?;
+
+        Ok(backup_path)
+    }
+
+    /// Lists the backups in a backup directory
+    ///
+    /// # Parameters
+    /// * `backup_dir` - Directory containing the backups
+    ///
+    /// # Returns
+    /// Backups, newest first
+    pub fn list_backups(backup_dir: &Path) -> Result<Vec<BackupInfo>> {
+        if !backup_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+
+        for entry in fs::read_dir(backup_dir).context("Failed to read backup directory")? {
+            let path = entry.context("Failed to read backup entry")?.path();
+
+            if path.extension().map_or(true, |ext| ext != BACKUP_EXTENSION) {
+                continue;
+            }
+
+            let Some(timestamp) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| {
+                    NaiveDateTime::parse_from_str(stem, BACKUP_TIMESTAMP_FORMAT)
+                        .or_else(|_| NaiveDateTime::parse_from_str(stem, LEGACY_BACKUP_TIMESTAMP_FORMAT))
+                        .ok()
+                })
+                .map(|timestamp| timestamp.and_utc())
+            else {
+                continue;
+            };
+
+            let size_bytes = path
+                .metadata()
+                .context("Failed to read backup metadata")?
+                .len();
+
+            backups.push(BackupInfo {
+                path,
+                timestamp,
+                size_bytes,
+            });
+        }
+
+        backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+        Ok(backups)
+    }
+
+    /// Extracts a backup over the notes directory
+    ///
+    /// Files in the backup replace the current files with the same path;
+    /// files that are not in the backup are left untouched.
+    ///
+    /// # Parameters
+    /// * `backup_path` - Path of the backup archive
+    /// * `notes_dir` - Path to the notes directory
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn restore_from_backup(backup_path: &Path, notes_dir: &Path) -> Result<()> {
+        let file = fs::File::open(backup_path).context("Failed to open backup")?;
+        let mut archive = ZipArchive::new(file).context("Failed to read backup archive")?;
+
+        // Entries with paths escaping the target directory are rejected by `extract`
+        archive
+            .extract(notes_dir)
+            .context("Failed to extract backup")?;
+
+        Ok(())
+    }
+
+    /// Deletes the oldest backups beyond a maximum count
+    ///
+    /// # Parameters
+    /// * `backup_dir` - Directory containing the backups
+    /// * `max_backups` - Number of backups to keep (0 keeps all backups)
+    ///
+    /// # Returns
+    /// Number of deleted backups
+    pub fn prune_backups(backup_dir: &Path, max_backups: u32) -> Result<usize> {
+        if max_backups == 0 {
+            return Ok(0);
+        }
+
+        let backups = Self::list_backups(backup_dir)?;
+        let mut removed = 0;
+
+        for backup in backups.iter().skip(max_backups as usize) {
+            fs::remove_file(&backup.path).context("Failed to delete old backup")?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// Checks whether a scheduled backup is due
+    ///
+    /// # Parameters
+    /// * `backup_dir` - Directory containing the backups
+    /// * `interval_hours` - Minimum number of hours between backups
+    ///
+    /// # Returns
+    /// True if there is no backup newer than the interval
+    pub fn is_backup_due(backup_dir: &Path, interval_hours: u32) -> Result<bool> {
+        let latest = Self::list_backups(backup_dir)?.into_iter().next();
+
+        Ok(latest.map_or(true, |backup| {
+            Utc::now() - backup.timestamp >= chrono::Duration::hours(interval_hours as i64)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let notes_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+        fs::create_dir_all(notes_dir.path().join("work").join("empty")).unwrap();
+        fs::write(notes_dir.path().join("a.md"), "# A").unwrap();
+        fs::write(notes_dir.path().join("work").join("b.txt"), "b").unwrap();
+
+        let backup_path = BackupService::create_backup(notes_dir.path(), backup_dir.path()).unwrap();
+
+        let backups = BackupService::list_backups(backup_dir.path()).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].path, backup_path);
+
+        fs::write(notes_dir.path().join("a.md"), "# Changed").unwrap();
+        fs::remove_dir_all(notes_dir.path().join("work")).unwrap();
+
+        BackupService::restore_from_backup(&backup_path, notes_dir.path()).unwrap();
+
+        assert_eq!(fs::read_to_string(notes_dir.path().join("a.md")).unwrap(), "# A");
+        assert_eq!(fs::read_to_string(notes_dir.path().join("work").join("b.txt")).unwrap(), "b");
+        assert!(notes_dir.path().join("work").join("empty").is_dir());
+    }
+
+    #[test]
+    fn test_backup_skips_nested_backup_dir() {
+        let notes_dir = TempDir::new().unwrap();
+        let backup_dir = notes_dir.path().join("backups");
+        fs::write(notes_dir.path().join("a.md"), "# A").unwrap();
+
+        let first_path = BackupService::create_backup(notes_dir.path(), &backup_dir).unwrap();
+        let backup_path = BackupService::create_backup(notes_dir.path(), &backup_dir).unwrap();
+
+        // Backups taken within the same second get distinct names
+        assert_ne!(first_path, backup_path);
+        assert_eq!(BackupService::list_backups(&backup_dir).unwrap().len(), 2);
+
+        let archive = ZipArchive::new(fs::File::open(backup_path).unwrap()).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert_eq!(names, vec!["a.md"]);
+    }
+
+    #[test]
+    fn test_prune_and_schedule() {
+        let backup_dir = TempDir::new().unwrap();
+        assert!(BackupService::is_backup_due(backup_dir.path(), 24).unwrap());
+
+        for name in ["2024-01-01_00-00-00", "2024-01-02_00-00-00", "2024-01-03_00-00-00-500"] {
+            fs::write(backup_dir.path().join(format!("{}.zip", name)), "").unwrap();
+        }
+        fs::write(backup_dir.path().join("notes.txt"), "").unwrap();
+
+        assert_eq!(BackupService::prune_backups(backup_dir.path(), 2).unwrap(), 1);
+
+        let backups = BackupService::list_backups(backup_dir.path()).unwrap();
+        let names: Vec<String> = backups
+            .iter()
+            .map(|backup| backup.path.file_stem().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["2024-01-03_00-00-00-500", "2024-01-02_00-00-00"]);
+
+        assert!(BackupService::is_backup_due(backup_dir.path(), 24).unwrap());
+    }
+}
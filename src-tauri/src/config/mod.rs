@@ -1,8 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
 use anyhow::{Context, Result};
-use crate::notes::NoteType;
+use crate::notes::{self, IdScheme, NoteTemplate, NoteType, SortOption};
 
 /// Mode for automatic search index updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,9 +24,113 @@ impl Default for AutoUpdateMode {
     }
 }
 
+/// Current version of the configuration format
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Smallest search index writer heap size Tantivy accepts, in megabytes
+pub const MIN_SEARCH_WRITER_MEMORY_MB: u32 = 15;
+
+/// Largest search index writer heap size, in megabytes
+pub const MAX_SEARCH_WRITER_MEMORY_MB: u32 = 1024;
+
+/// Language of notes assumed by the search index
+pub const DEFAULT_SEARCH_LANGUAGE: &str = "en";
+
+/// Maximum number of remembered recently viewed notes
+pub const MAX_RECENTLY_VIEWED: usize = 50;
+
+/// Placeholders that can be used in note naming patterns
+const NAMING_PLACEHOLDERS: &[&str] = &["title", "number", "date", "datetime", "extension"];
+
+/// Placeholders of which a naming pattern needs at least one to tell notes apart
+/// 
+/// `{date}` is not enough on its own, since all notes of a day share it.
+const DISTINCT_NAMING_PLACEHOLDERS: &[&str] = &["title", "number", "datetime"];
+
+/// Error returned when a note naming pattern is invalid
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[error("{message}")]
+pub struct PatternValidationError {
+    /// Human-readable error message
+    pub message: String,
+    /// Unknown placeholders in the pattern, including their braces (e.g. "{titl}")
+    pub invalid_placeholders: Vec<String>,
+}
+
+impl From<anyhow::Error> for PatternValidationError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<PatternValidationError>() {
+            Ok(error) => error,
+            Err(error) => Self {
+                message: error.to_string(),
+                invalid_placeholders: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Checks that a note naming pattern only uses known placeholders
+/// 
+/// # Parameters
+/// * `pattern` - Pattern for naming new notes
+/// 
+/// # Returns
+/// The error with all unknown placeholders if the pattern is invalid
+fn validate_naming_pattern(pattern: &str) -> std::result::Result<(), PatternValidationError> {
+    let mut placeholders = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        placeholders.push(&rest[start..=start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    
+    let name = |placeholder: &str| placeholder[1..placeholder.len() - 1].to_string();
+    
+    let invalid_placeholders: Vec<String> = placeholders
+        .iter()
+        .filter(|placeholder| !NAMING_PLACEHOLDERS.contains(&name(placeholder).as_str()))
+        .map(|placeholder| placeholder.to_string())
+        .collect();
+    
+    if !invalid_placeholders.is_empty() {
+        return Err(PatternValidationError {
+            message: format!("Unknown placeholders in pattern: {}", invalid_placeholders.join(", ")),
+            invalid_placeholders,
+        });
+    }
+    
+    if !placeholders
+        .iter()
+        .any(|placeholder| DISTINCT_NAMING_PLACEHOLDERS.contains(&name(placeholder).as_str()))
+    {
+        return Err(PatternValidationError {
+            message: "Pattern must contain a {title}, {number} or {datetime} placeholder".to_string(),
+            invalid_placeholders: Vec::new(),
+        });
+    }
+    
+    Ok(())
+}
+
+/// Editor state of a note, restored when the note is opened again
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoteUiState {
+    /// Cursor position as a character offset
+    pub cursor_position: Option<usize>,
+    /// Vertical scroll offset in pixels
+    pub scroll_offset: Option<f32>,
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    /// Version of the configuration format; files without it are version 0
+    #[serde(default)]
+    pub schema_version: u32,
+    
     /// Path to the directory containing notes
     pub notes_dir: Option<PathBuf>,
     
@@ -55,6 +162,119 @@ pub struct AppConfig {
     /// Whether to enable subnotes display
     #[serde(default)]
     pub enable_subnotes: bool,
+
+    /// Number of days trashed notes are kept before `empty_trash` removes them
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+
+    /// Maximum number of history snapshots kept per note
+    #[serde(default = "default_max_history_versions")]
+    pub max_history_versions: u32,
+
+    /// Whether to watch the notes directory for changes made outside the app
+    #[serde(default)]
+    pub watch_external_changes: bool,
+
+    /// IDs of pinned notes, in the order they were pinned
+    #[serde(default)]
+    pub pinned_notes: Vec<String>,
+
+    /// User-defined note templates (name -> content)
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+
+    /// Folder (relative to the notes directory) where daily notes are created
+    #[serde(default = "default_daily_notes_folder")]
+    pub daily_notes_folder: Option<String>,
+
+    /// Name of the template used for new daily notes (built-in "daily" if unset)
+    #[serde(default)]
+    pub daily_note_template: Option<String>,
+
+    /// Sort order used when listing notes without an explicit sort option
+    #[serde(default)]
+    pub default_sort: SortOption,
+
+    /// Last editor state per note ID
+    #[serde(default)]
+    pub ui_states: HashMap<String, NoteUiState>,
+
+    /// IDs of recently viewed notes with the time they were viewed, newest first
+    #[serde(default)]
+    pub recently_viewed: Vec<(String, DateTime<Utc>)>,
+
+    /// Names of folders hidden from listing and indexing, in addition to the
+    /// always excluded application folders
+    #[serde(default)]
+    pub excluded_folders: Vec<String>,
+
+    /// How IDs are assigned to new notes
+    #[serde(default)]
+    pub id_scheme: IdScheme,
+
+    /// Whether Emacs Org-mode (`.org`) files are listed as notes
+    #[serde(default)]
+    pub include_org_files: bool,
+
+    /// Additional file extensions (without the dot) listed as plain text notes
+    #[serde(default)]
+    pub custom_extensions: Vec<String>,
+
+    /// Whether symbolic links inside the notes directory are followed
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+
+    /// Maximum folder depth below the notes directory that is scanned for notes
+    #[serde(default = "default_max_walk_depth")]
+    pub max_walk_depth: u32,
+
+    /// Whether moving notes into a missing folder creates the folder
+    #[serde(default = "default_auto_create_folders")]
+    pub auto_create_folders: bool,
+
+    /// Lowercase words left out of word frequencies, in addition to common English words
+    #[serde(default)]
+    pub custom_stop_words: Vec<String>,
+
+    /// Heap size of the search index writer in megabytes
+    #[serde(default = "default_search_writer_memory_mb")]
+    pub search_writer_memory_mb: u32,
+
+    /// Whether to merge search index segments after a rebuild
+    #[serde(default = "default_optimize_on_rebuild")]
+    pub optimize_on_rebuild: bool,
+
+    /// Maximum number of hits fetched for a paginated search; deeper pages come back empty
+    #[serde(default = "default_max_paginated_results")]
+    pub max_paginated_results: usize,
+
+    /// ISO 639-1 code of the language used to tokenize and stem search terms
+    #[serde(default = "default_search_language")]
+    pub search_language: String,
+
+    /// Maximum number of characters in note previews
+    #[serde(default = "default_preview_length")]
+    pub preview_length: usize,
+
+    /// Number of days without changes after which a note is due for review
+    #[serde(default = "default_stale_notes_threshold_days")]
+    pub stale_notes_threshold_days: u32,
+
+    /// Whether to create backups of the notes directory automatically
+    #[serde(default)]
+    pub backup_enabled: bool,
+
+    /// Minimum number of hours between automatic backups
+    #[serde(default = "default_backup_interval_hours")]
+    pub backup_interval_hours: u32,
+
+    /// Directory for backups (a folder in the app data directory if unset)
+    #[serde(default)]
+    pub backup_dir: Option<PathBuf>,
+
+    /// Number of backups to keep (0 keeps all backups)
+    #[serde(default = "default_max_backups")]
+    pub max_backups: u32,
 }
 
 /// Default update interval (30 minutes)
@@ -62,6 +282,76 @@ fn default_update_interval() -> u32 {
     30
 }
 
+/// Default trash retention window (30 days)
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+/// Default daily notes folder ("Daily")
+fn default_daily_notes_folder() -> Option<String> {
+    Some("Daily".to_string())
+}
+
+/// Default number of history snapshots per note (50)
+fn default_max_history_versions() -> u32 {
+    50
+}
+
+/// Follow symbolic links in the notes directory by default
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+/// Default maximum folder depth scanned for notes
+fn default_max_walk_depth() -> u32 {
+    notes::DEFAULT_MAX_WALK_DEPTH
+}
+
+/// Create missing folders when moving notes by default
+fn default_auto_create_folders() -> bool {
+    true
+}
+
+/// Default search index writer heap size (50 MB)
+fn default_search_writer_memory_mb() -> u32 {
+    50
+}
+
+/// Merge search index segments after rebuilds by default
+fn default_optimize_on_rebuild() -> bool {
+    true
+}
+
+/// Default cap on how deep paginated search can reach (1000 hits)
+fn default_max_paginated_results() -> usize {
+    1000
+}
+
+/// Default search language (English)
+fn default_search_language() -> String {
+    DEFAULT_SEARCH_LANGUAGE.to_string()
+}
+
+/// Default note preview length (200 characters)
+fn default_preview_length() -> usize {
+    200
+}
+
+/// Default stale note threshold (90 days)
+fn default_stale_notes_threshold_days() -> u32 {
+    90
+}
+
+/// Default interval between automatic backups (24 hours)
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+/// Default number of backups to keep (10)
+fn default_max_backups() -> u32 {
+    10
+}
+
 impl Default for AppConfig {
     /// Creates a default configuration
     /// 
@@ -69,6 +359,7 @@ impl Default for AppConfig {
     /// A new AppConfig with default values
     fn default() -> Self {
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             notes_dir: None,
             note_naming_pattern: Some("{number}-{title}.{extension}".to_string()),
             default_note_type: Some(NoteType::Markdown),
@@ -77,6 +368,34 @@ impl Default for AppConfig {
             auto_update_interval: 30,
             subnote_pattern: Some("{parent}{letter}".to_string()),
             enable_subnotes: true,
+            trash_retention_days: default_trash_retention_days(),
+            max_history_versions: default_max_history_versions(),
+            watch_external_changes: false,
+            pinned_notes: Vec::new(),
+            templates: HashMap::new(),
+            daily_notes_folder: default_daily_notes_folder(),
+            daily_note_template: None,
+            default_sort: SortOption::default(),
+            ui_states: HashMap::new(),
+            recently_viewed: Vec::new(),
+            excluded_folders: Vec::new(),
+            id_scheme: IdScheme::default(),
+            include_org_files: false,
+            custom_extensions: Vec::new(),
+            follow_symlinks: default_follow_symlinks(),
+            max_walk_depth: default_max_walk_depth(),
+            auto_create_folders: default_auto_create_folders(),
+            custom_stop_words: Vec::new(),
+            search_writer_memory_mb: default_search_writer_memory_mb(),
+            optimize_on_rebuild: default_optimize_on_rebuild(),
+            max_paginated_results: default_max_paginated_results(),
+            search_language: default_search_language(),
+            preview_length: default_preview_length(),
+            stale_notes_threshold_days: default_stale_notes_threshold_days(),
+            backup_enabled: false,
+            backup_interval_hours: default_backup_interval_hours(),
+            backup_dir: None,
+            max_backups: default_max_backups(),
         }
     }
 }
@@ -113,10 +432,40 @@ impl ConfigManager {
             AppConfig::default()
         };
         
-        Ok(Self {
+        let mut manager = Self {
             config_path,
             config,
-        })
+        };
+        
+        // Upgrade configs written by older versions; configs from newer versions are left untouched
+        let version = manager.config.schema_version;
+        if version < CURRENT_SCHEMA_VERSION {
+            manager.config = Self::migrate(manager.config, version, CURRENT_SCHEMA_VERSION)?;
+            manager.save_config()?;
+        }
+        
+        Ok(manager)
+    }
+    
+    /// Migrates a configuration between schema versions
+    /// 
+    /// # Parameters
+    /// * `config` - Configuration to migrate
+    /// * `from` - Schema version of the configuration
+    /// * `to` - Schema version to migrate to
+    /// 
+    /// # Returns
+    /// The migrated configuration
+    pub fn migrate(mut config: AppConfig, from: u32, to: u32) -> Result<AppConfig> {
+        for version in from..to {
+            config = match version {
+                0 => v0_to_v1(config),
+                _ => anyhow::bail!("No migration from config version {}", version),
+            };
+        }
+        
+        config.schema_version = to;
+        Ok(config)
     }
     
     /// Gets the current configuration
@@ -127,14 +476,103 @@ impl ConfigManager {
         self.config.clone()
     }
     
+    /// Resets all settings to their defaults
+    /// 
+    /// The notes directory is kept so the app stays initialized.
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn reset_to_defaults(&mut self) -> Result<()> {
+        let notes_dir = self.config.notes_dir.take();
+        
+        // Update config
+        self.config = AppConfig {
+            notes_dir,
+            ..AppConfig::default()
+        };
+        self.save_config()
+    }
+    
+    /// Writes the current configuration to a JSON file
+    /// 
+    /// # Parameters
+    /// * `path` - Path of the file to write
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn export_config(&self, path: &Path) -> Result<()> {
+        let config_str = serde_json::to_string_pretty(&self.config)
+            .context("Failed to serialize config")?;
+        
+        fs::write(path, config_str)
+            .context("Failed to write config export")?;
+        
+        Ok(())
+    }
+    
+    /// Replaces the configuration with one read from a JSON file
+    /// 
+    /// Configurations from older versions are migrated. The imported notes
+    /// directory must exist on this machine; without one, the current notes
+    /// directory is kept. Settings are validated like in their setters.
+    /// 
+    /// # Parameters
+    /// * `path` - Path of the file to read
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn import_config(&mut self, path: &Path) -> Result<()> {
+        let config_str = fs::read_to_string(path)
+            .context("Failed to read config file")?;
+        
+        let mut config: AppConfig = serde_json::from_str(&config_str)
+            .context("Failed to parse config file")?;
+        
+        // Validate config
+        if config.schema_version > CURRENT_SCHEMA_VERSION {
+            anyhow::bail!(
+                "Config was written by a newer version of the app (version {})",
+                config.schema_version
+            );
+        }
+        
+        if config.schema_version < CURRENT_SCHEMA_VERSION {
+            let from = config.schema_version;
+            config = Self::migrate(config, from, CURRENT_SCHEMA_VERSION)?;
+        }
+        
+        match &config.notes_dir {
+            Some(notes_dir) => {
+                let notes_dir = expand_path(notes_dir);
+                
+                if !notes_dir.is_dir() {
+                    anyhow::bail!("Notes directory does not exist: {}", notes_dir.display());
+                }
+                
+                config.notes_dir = Some(notes_dir);
+            }
+            // An imported config must not de-initialize the app
+            None => config.notes_dir = self.config.notes_dir.clone(),
+        }
+        
+        validate_config(&mut config)?;
+        
+        // Update config
+        self.config = config;
+        self.save_config()
+    }
+    
     /// Sets the notes directory
     /// 
     /// # Parameters
-    /// * `path` - Path to the notes directory
+    /// * `path` - Path to the notes directory (a leading `~` is expanded)
     /// 
     /// # Returns
     /// Result indicating success or failure
     pub fn set_notes_dir(&mut self, path: PathBuf) -> Result<()> {
+        // Store the expanded path so the config never contains `~`
+        let path = expand_path(&path);
+        
         // Validate directory
         if !path.is_dir() {
             anyhow::bail!("Path is not a directory");
@@ -154,16 +592,18 @@ impl ConfigManager {
     
     /// Sets the note naming pattern
     /// 
+    /// The pattern may only use the `{title}`, `{number}`, `{date}`,
+    /// `{datetime}` and `{extension}` placeholders, and must contain
+    /// `{title}`, `{number}` or `{datetime}` to tell notes apart.
+    /// 
     /// # Parameters
     /// * `pattern` - Pattern for naming new notes
     /// 
     /// # Returns
-    /// Result indicating success or failure
+    /// Result indicating success or failure; invalid patterns fail with a `PatternValidationError`
     pub fn set_note_naming_pattern(&mut self, pattern: String) -> Result<()> {
         // Validate pattern
-        if !pattern.contains("{title}") {
-            anyhow::bail!("Pattern must contain {{title}} placeholder");
-        }
+        validate_naming_pattern(&pattern)?;
         
         // Update config
         self.config.note_naming_pattern = Some(pattern);
@@ -222,17 +662,1066 @@ impl ConfigManager {
         self.save_config()
     }
     
-    /// Saves the current configuration to disk
+    /// Sets how long trashed notes are kept
+    /// 
+    /// # Parameters
+    /// * `days` - Retention window in days
     /// 
     /// # Returns
     /// Result indicating success or failure
-    fn save_config(&self) -> Result<()> {
-        let config_str = serde_json::to_string_pretty(&self.config)
-            .context("Failed to serialize config")?;
+    pub fn set_trash_retention_days(&mut self, days: u32) -> Result<()> {
+        // Update config
+        self.config.trash_retention_days = days;
+        self.save_config()
+    }
+    
+    /// Sets after how many days without changes a note is considered stale
+    /// 
+    /// # Parameters
+    /// * `days` - Stale note threshold in days
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_stale_notes_threshold(&mut self, days: u32) -> Result<()> {
+        // Update config
+        self.config.stale_notes_threshold_days = days;
+        self.save_config()
+    }
+    
+    /// Sets the automatic backup options
+    /// 
+    /// # Parameters
+    /// * `enabled` - Whether to create backups automatically
+    /// * `interval_hours` - Minimum number of hours between automatic backups
+    /// * `backup_dir` - Directory for backups (a folder in the app data directory if None)
+    /// * `max_backups` - Number of backups to keep (0 keeps all backups)
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_backup_settings(
+        &mut self,
+        enabled: bool,
+        interval_hours: u32,
+        backup_dir: Option<PathBuf>,
+        max_backups: u32,
+    ) -> Result<()> {
+        validate_backup_interval(interval_hours)?;
+        
+        // Update config
+        self.config.backup_enabled = enabled;
+        self.config.backup_interval_hours = interval_hours;
+        self.config.backup_dir = backup_dir.map(|dir| expand_path(&dir));
+        self.config.max_backups = max_backups;
+        self.save_config()
+    }
+    
+    /// Sets the default sort order for listing notes
+    /// 
+    /// # Parameters
+    /// * `option` - Sort option used when none is specified
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_default_sort(&mut self, option: SortOption) -> Result<()> {
+        // Update config
+        self.config.default_sort = option;
+        self.save_config()
+    }
+    
+    /// Sets how IDs are assigned to new notes
+    /// 
+    /// # Parameters
+    /// * `scheme` - ID scheme to use
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_id_scheme(&mut self, scheme: IdScheme) -> Result<()> {
+        // Update config
+        self.config.id_scheme = scheme;
+        self.save_config()
+    }
+    
+    /// Sets the maximum number of history snapshots kept per note
+    /// 
+    /// # Parameters
+    /// * `max_versions` - Maximum number of snapshots (at least 1)
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_max_history_versions(&mut self, max_versions: u32) -> Result<()> {
+        validate_max_history_versions(max_versions)?;
         
-        fs::write(&self.config_path, config_str)
-            .context("Failed to write config file")?;
+        // Update config
+        self.config.max_history_versions = max_versions;
+        self.save_config()
+    }
+    
+    /// Sets the heap size of the search index writer
+    /// 
+    /// # Parameters
+    /// * `mb` - Heap size in megabytes, at least `MIN_SEARCH_WRITER_MEMORY_MB`;
+    ///   larger values than `MAX_SEARCH_WRITER_MEMORY_MB` are clamped to it
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_search_writer_memory(&mut self, mb: u32) -> Result<()> {
+        validate_search_writer_memory(mb)?;
         
-        Ok(())
+        // Update config
+        self.config.search_writer_memory_mb = mb.min(MAX_SEARCH_WRITER_MEMORY_MB);
+        self.save_config()
+    }
+    
+    /// Sets the maximum number of hits fetched for a paginated search
+    /// 
+    /// # Parameters
+    /// * `max_results` - Maximum of `page * page_size` before results are cut off, at least 1
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_max_paginated_results(&mut self, max_results: usize) -> Result<()> {
+        validate_max_paginated_results(max_results)?;
+        
+        // Update config
+        self.config.max_paginated_results = max_results;
+        self.save_config()
+    }
+    
+    /// Sets the language used to tokenize and stem search terms
+    /// 
+    /// # Parameters
+    /// * `language` - ISO 639-1 language code, e.g. `de`
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_search_language(&mut self, language: &str) -> Result<()> {
+        let language = normalize_search_language(language)?;
+        
+        // Update config
+        self.config.search_language = language;
+        self.save_config()
+    }
+    
+    /// Sets whether to watch the notes directory for external changes
+    /// 
+    /// # Parameters
+    /// * `enabled` - Whether to watch for external changes
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_watch_external_changes(&mut self, enabled: bool) -> Result<()> {
+        // Update config
+        self.config.watch_external_changes = enabled;
+        self.save_config()
+    }
+    
+    /// Pins a note
+    /// 
+    /// Pinning an already pinned note has no effect.
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to pin
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn pin_note(&mut self, id: &str) -> Result<()> {
+        if self.config.pinned_notes.iter().any(|pinned| pinned == id) {
+            return Ok(());
+        }
+        
+        // Update config
+        self.config.pinned_notes.push(id.to_string());
+        self.save_config()
+    }
+    
+    /// Unpins a note
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to unpin
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn unpin_note(&mut self, id: &str) -> Result<()> {
+        // Update config
+        self.config.pinned_notes.retain(|pinned| pinned != id);
+        self.save_config()
+    }
+    
+    /// Sets the folders to hide from listing and indexing
+    /// 
+    /// Application folders such as `.trash` are always excluded and are not stored.
+    /// 
+    /// # Parameters
+    /// * `folders` - Names of folders to exclude
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_excluded_folders(&mut self, folders: Vec<String>) -> Result<()> {
+        // Update config
+        self.config.excluded_folders = normalize_excluded_folders(folders);
+        self.save_config()
+    }
+    
+    /// Sets additional file extensions to list as plain text notes
+    /// 
+    /// Extensions are stored in lowercase without duplicates.
+    /// 
+    /// # Parameters
+    /// * `extensions` - Extensions without the leading dot, e.g. `rst`
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_custom_extensions(&mut self, extensions: Vec<String>) -> Result<()> {
+        let custom_extensions = normalize_custom_extensions(extensions)?;
+        
+        // Update config
+        self.config.custom_extensions = custom_extensions;
+        self.save_config()
+    }
+    
+    /// Sets whether symbolic links in the notes directory are followed
+    /// 
+    /// # Parameters
+    /// * `enabled` - Whether to follow symbolic links
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_follow_symlinks(&mut self, enabled: bool) -> Result<()> {
+        // Update config
+        self.config.follow_symlinks = enabled;
+        self.save_config()
+    }
+    
+    /// Sets the maximum folder depth scanned for notes
+    /// 
+    /// # Parameters
+    /// * `depth` - Maximum depth below the notes directory (at least 1)
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_max_walk_depth(&mut self, depth: u32) -> Result<()> {
+        validate_max_walk_depth(depth)?;
+        
+        // Update config
+        self.config.max_walk_depth = depth;
+        self.save_config()
+    }
+    
+    /// Sets whether moving notes into a missing folder creates the folder
+    /// 
+    /// # Parameters
+    /// * `enabled` - Whether to create missing folders
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_auto_create_folders(&mut self, enabled: bool) -> Result<()> {
+        // Update config
+        self.config.auto_create_folders = enabled;
+        self.save_config()
+    }
+    
+    /// Sets the words left out of word frequencies
+    /// 
+    /// Words are stored in lowercase without duplicates or surrounding whitespace.
+    /// 
+    /// # Parameters
+    /// * `words` - Words to ignore in addition to common English words
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_custom_stop_words(&mut self, words: Vec<String>) -> Result<()> {
+        // Update config
+        self.config.custom_stop_words = normalize_custom_stop_words(words);
+        self.save_config()
+    }
+    
+    /// Sets the editor state of a note
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// * `state` - Editor state to remember
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_note_ui_state(&mut self, id: &str, state: NoteUiState) -> Result<()> {
+        // Update config
+        self.config.ui_states.insert(id.to_string(), state);
+        self.save_config()
+    }
+    
+    /// Gets the editor state of a note
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// The remembered editor state, if any
+    pub fn get_note_ui_state(&self, id: &str) -> Option<NoteUiState> {
+        self.config.ui_states.get(id).cloned()
+    }
+    
+    /// Records that a note was viewed
+    /// 
+    /// The note moves to the front of the recently viewed notes; only the
+    /// newest `MAX_RECENTLY_VIEWED` notes are kept.
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the viewed note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn record_note_viewed(&mut self, id: &str) -> Result<()> {
+        // Update config
+        self.config.recently_viewed.retain(|(viewed, _)| viewed != id);
+        self.config.recently_viewed.insert(0, (id.to_string(), Utc::now()));
+        self.config.recently_viewed.truncate(MAX_RECENTLY_VIEWED);
+        self.save_config()
+    }
+    
+    /// Forgets the editor state of a note, e.g. after it was deleted
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn remove_note_ui_state(&mut self, id: &str) -> Result<()> {
+        if self.config.ui_states.remove(id).is_none() {
+            return Ok(());
+        }
+        
+        self.save_config()
+    }
+    
+    /// Removes the pin, editor state and recently viewed entry of a note that no longer exists
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn remove_note_references(&mut self, id: &str) -> Result<()> {
+        // Update config
+        self.config.pinned_notes.retain(|pinned| pinned != id);
+        self.config.ui_states.remove(id);
+        self.config.recently_viewed.retain(|(viewed, _)| viewed != id);
+        self.save_config()
+    }
+    
+    /// Moves pins, editor states and recently viewed entries to new note IDs
+    /// 
+    /// # Parameters
+    /// * `new_ids` - New IDs keyed by the old IDs
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn replace_note_ids(&mut self, new_ids: &HashMap<String, String>) -> Result<()> {
+        if new_ids.is_empty() {
+            return Ok(());
+        }
+        
+        let new_id = |id: &String| new_ids.get(id).unwrap_or(id).clone();
+        
+        // Update config
+        self.config.pinned_notes = self.config.pinned_notes.iter().map(new_id).collect();
+        self.config.ui_states = std::mem::take(&mut self.config.ui_states)
+            .into_iter()
+            .map(|(id, state)| (new_id(&id), state))
+            .collect();
+        for (id, _) in &mut self.config.recently_viewed {
+            *id = new_id(id);
+        }
+        self.save_config()
+    }
+    
+    /// Adds or replaces a user-defined template
+    /// 
+    /// A user template with the same name as a built-in template takes precedence.
+    /// 
+    /// # Parameters
+    /// * `name` - Name of the template
+    /// * `content` - Template content
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn add_template(&mut self, name: &str, content: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("Template name must not be empty");
+        }
+        
+        // Update config
+        self.config.templates.insert(name.to_string(), content.to_string());
+        self.save_config()
+    }
+    
+    /// Deletes a user-defined template
+    /// 
+    /// # Parameters
+    /// * `name` - Name of the template
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn delete_template(&mut self, name: &str) -> Result<()> {
+        if self.config.templates.remove(name).is_none() {
+            if notes::builtin_template(name).is_some() {
+                anyhow::bail!("Built-in template '{}' cannot be deleted", name);
+            }
+            anyhow::bail!("Template '{}' not found", name);
+        }
+        
+        self.save_config()
+    }
+    
+    /// Gets the content of a template
+    /// 
+    /// # Parameters
+    /// * `name` - Name of the template
+    /// 
+    /// # Returns
+    /// The template content, looking up user templates before built-in ones
+    pub fn get_template(&self, name: &str) -> Option<String> {
+        self.config
+            .templates
+            .get(name)
+            .cloned()
+            .or_else(|| notes::builtin_template(name).map(String::from))
+    }
+    
+    /// Lists all available templates
+    /// 
+    /// # Returns
+    /// Built-in and user-defined templates sorted by name
+    pub fn list_templates(&self) -> Vec<NoteTemplate> {
+        let mut templates: Vec<NoteTemplate> = notes::builtin_templates()
+            .into_iter()
+            .filter(|template| !self.config.templates.contains_key(&template.name))
+            .collect();
+        
+        templates.extend(self.config.templates.iter().map(|(name, content)| NoteTemplate {
+            name: name.clone(),
+            content: content.clone(),
+            builtin: false,
+        }));
+        
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+    
+    /// Sets the folder where daily notes are created
+    /// 
+    /// # Parameters
+    /// * `folder` - Folder relative to the notes directory, or None for the notes directory itself
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_daily_notes_folder(&mut self, folder: Option<String>) -> Result<()> {
+        // Update config
+        self.config.daily_notes_folder = folder;
+        self.save_config()
+    }
+    
+    /// Sets the template used for new daily notes
+    /// 
+    /// # Parameters
+    /// * `template` - Name of the template, or None for the built-in "daily" template
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn set_daily_note_template(&mut self, template: Option<String>) -> Result<()> {
+        if let Some(name) = &template {
+            if self.get_template(name).is_none() {
+                anyhow::bail!("Template '{}' not found", name);
+            }
+        }
+        
+        // Update config
+        self.config.daily_note_template = template;
+        self.save_config()
+    }
+    
+    /// Saves the current configuration to disk
+    /// 
+    /// The config is written to a temporary file that then replaces the config
+    /// file, so a crash never leaves a truncated config behind.
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    fn save_config(&self) -> Result<()> {
+        let config_str = serde_json::to_string_pretty(&self.config)
+            .context("Failed to serialize config")?;
+        
+        let temp_path = self.config_path.with_extension("tmp");
+        write_synced(&temp_path, &config_str)
+            .context("Failed to write temporary config file")?;
+        
+        if let Err(e) = fs::rename(&temp_path, &self.config_path) {
+            // Renaming over an existing file can fail on Windows
+            log::warn!("Failed to replace config file, retrying with a temporary file: {}", e);
+            let _ = fs::remove_file(&temp_path);
+            self.save_config_with_temp_file(&config_str)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Saves serialized configuration through a `NamedTempFile` next to the config file
+    /// 
+    /// # Parameters
+    /// * `config_str` - The serialized configuration
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    fn save_config_with_temp_file(&self, config_str: &str) -> Result<()> {
+        let config_dir = self.config_path
+            .parent()
+            .context("Config file has no parent directory")?;
+        
+        let mut file = tempfile::NamedTempFile::new_in(config_dir)
+            .context("Failed to create temporary config file")?;
+        file.write_all(config_str.as_bytes())
+            .context("Failed to write temporary config file")?;
+        file.as_file().sync_all()
+            .context("Failed to write temporary config file")?;
+        
+        file.persist(&self.config_path)
+            .context("Failed to replace config file")?;
+        
+        Ok(())
+    }
+}
+
+/// Validates a complete configuration, e.g. an imported one, like the setters do
+///
+/// List settings are normalized in place.
+///
+/// # Parameters
+/// * `config` - Configuration to validate
+///
+/// # Returns
+/// The first invalid setting as an error
+fn validate_config(config: &mut AppConfig) -> Result<()> {
+    if let Some(pattern) = &config.note_naming_pattern {
+        validate_naming_pattern(pattern)?;
+    }
+    validate_backup_interval(config.backup_interval_hours)?;
+    validate_max_history_versions(config.max_history_versions)?;
+    validate_search_writer_memory(config.search_writer_memory_mb)?;
+    validate_max_paginated_results(config.max_paginated_results)?;
+    validate_max_walk_depth(config.max_walk_depth)?;
+    
+    config.search_language = normalize_search_language(&config.search_language)?;
+    config.excluded_folders = normalize_excluded_folders(std::mem::take(&mut config.excluded_folders));
+    config.custom_extensions = normalize_custom_extensions(std::mem::take(&mut config.custom_extensions))?;
+    config.custom_stop_words = normalize_custom_stop_words(std::mem::take(&mut config.custom_stop_words));
+    
+    Ok(())
+}
+
+/// Checks that automatic backups are at least an hour apart
+fn validate_backup_interval(interval_hours: u32) -> Result<()> {
+    if interval_hours == 0 {
+        anyhow::bail!("Backup interval must be at least 1 hour");
+    }
+    
+    Ok(())
+}
+
+/// Checks that at least one history snapshot is kept per note
+fn validate_max_history_versions(max_versions: u32) -> Result<()> {
+    if max_versions == 0 {
+        anyhow::bail!("Maximum number of history versions must be at least 1");
+    }
+    
+    Ok(())
+}
+
+/// Checks that the search writer heap is large enough for Tantivy
+fn validate_search_writer_memory(mb: u32) -> Result<()> {
+    if mb < MIN_SEARCH_WRITER_MEMORY_MB {
+        anyhow::bail!("Search writer memory must be at least {} MB", MIN_SEARCH_WRITER_MEMORY_MB);
+    }
+    
+    Ok(())
+}
+
+/// Checks that paginated search returns at least one hit
+fn validate_max_paginated_results(max_results: usize) -> Result<()> {
+    if max_results == 0 {
+        anyhow::bail!("Maximum number of paginated results must be at least 1");
+    }
+    
+    Ok(())
+}
+
+/// Checks that at least the top level of the notes directory is scanned
+fn validate_max_walk_depth(depth: u32) -> Result<()> {
+    if depth == 0 {
+        anyhow::bail!("Maximum folder depth must be at least 1");
+    }
+    
+    Ok(())
+}
+
+/// Lowercases and checks an ISO 639-1 language code
+fn normalize_search_language(language: &str) -> Result<String> {
+    let language = language.trim().to_lowercase();
+    
+    if language.is_empty() || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        anyhow::bail!("Invalid language code: {:?}", language);
+    }
+    
+    Ok(language)
+}
+
+/// Trims excluded folder names, dropping empty, reserved and duplicate ones
+fn normalize_excluded_folders(folders: Vec<String>) -> Vec<String> {
+    let mut excluded_folders: Vec<String> = Vec::new();
+    
+    for folder in folders {
+        let folder = folder.trim().to_string();
+        if folder.is_empty()
+            || notes::RESERVED_DIRS.contains(&folder.as_str())
+            || excluded_folders.contains(&folder)
+        {
+            continue;
+        }
+        excluded_folders.push(folder);
+    }
+    
+    excluded_folders
+}
+
+/// Lowercases custom extensions without duplicates, rejecting invalid ones
+fn normalize_custom_extensions(extensions: Vec<String>) -> Result<Vec<String>> {
+    let mut custom_extensions: Vec<String> = Vec::new();
+    
+    for extension in extensions {
+        let extension = extension.trim().to_lowercase();
+        if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+            anyhow::bail!("Invalid file extension: {:?}", extension);
+        }
+        if !custom_extensions.contains(&extension) {
+            custom_extensions.push(extension);
+        }
+    }
+    
+    Ok(custom_extensions)
+}
+
+/// Lowercases and trims stop words, dropping empty and duplicate ones
+fn normalize_custom_stop_words(words: Vec<String>) -> Vec<String> {
+    let mut custom_stop_words: Vec<String> = Vec::new();
+    
+    for word in words {
+        let word = word.trim().to_lowercase();
+        if !word.is_empty() && !custom_stop_words.contains(&word) {
+            custom_stop_words.push(word);
+        }
+    }
+    
+    custom_stop_words
+}
+
+/// Writes a file and flushes it to disk
+///
+/// # Parameters
+/// * `path` - Path of the file
+/// * `contents` - Contents to write
+///
+/// # Returns
+/// Result indicating success or failure
+fn write_synced(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+/// Migrates a version 0 configuration to version 1
+///
+/// Version 0 files may lack settings that only get a value when changed in the
+/// UI, so missing ones are filled in with their defaults.
+fn v0_to_v1(mut config: AppConfig) -> AppConfig {
+    let defaults = AppConfig::default();
+
+    if config.note_naming_pattern.is_none() {
+        config.note_naming_pattern = defaults.note_naming_pattern;
+    }
+    if config.default_note_type.is_none() {
+        config.default_note_type = defaults.default_note_type;
+    }
+    if config.subnote_pattern.is_none() {
+        config.subnote_pattern = defaults.subnote_pattern;
+    }
+
+    config
+}
+
+/// Expands a leading `~` in a path to the user's home directory
+///
+/// # Parameters
+/// * `path` - Path that may start with `~`
+///
+/// # Returns
+/// The expanded path, or the path unchanged if it has no leading `~`
+pub fn expand_path(path: &Path) -> PathBuf {
+    expand_path_with_home(path, dirs::home_dir().as_deref())
+}
+
+/// Expands a leading `~` in a path to the given home directory
+fn expand_path_with_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    let mut components = path.components();
+
+    match (components.next(), home) {
+        (Some(Component::Normal(first)), Some(home)) if first == "~" => {
+            let rest = components.as_path();
+            if rest.as_os_str().is_empty() {
+                home.to_path_buf()
+            } else {
+                home.join(rest)
+            }
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_sort_missing_from_config() {
+        let config: AppConfig = serde_json::from_str(r#"{"notes_dir": null}"#).unwrap();
+        assert_eq!(config.default_sort, SortOption::ModifiedNewest);
+    }
+
+    #[test]
+    fn test_default_sort_round_trip() {
+        let config = AppConfig {
+            default_sort: SortOption::TitleAsc,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.default_sort, SortOption::TitleAsc);
+    }
+
+    #[test]
+    fn test_expand_path_with_home() {
+        let home = tempfile::TempDir::new().unwrap();
+        let home_path = home.path();
+        fs::create_dir(home_path.join("Notes")).unwrap();
+
+        let expanded = expand_path_with_home(Path::new("~/Notes"), Some(home_path));
+        assert_eq!(expanded, home_path.join("Notes"));
+        assert!(expanded.is_dir());
+
+        assert_eq!(expand_path_with_home(Path::new("~"), Some(home_path)), home_path);
+    }
+
+    #[test]
+    fn test_expand_path_without_tilde() {
+        let home = tempfile::TempDir::new().unwrap();
+
+        assert_eq!(
+            expand_path_with_home(Path::new("/var/notes"), Some(home.path())),
+            PathBuf::from("/var/notes")
+        );
+        assert_eq!(
+            expand_path_with_home(Path::new("notes/~"), Some(home.path())),
+            PathBuf::from("notes/~")
+        );
+        // Other users' home directories are not supported
+        assert_eq!(
+            expand_path_with_home(Path::new("~alice/notes"), Some(home.path())),
+            PathBuf::from("~alice/notes")
+        );
+        // Without a known home directory the path is left unchanged
+        assert_eq!(expand_path_with_home(Path::new("~/Notes"), None), PathBuf::from("~/Notes"));
+    }
+
+    #[test]
+    fn test_migrate_v0_config() {
+        let config: AppConfig = serde_json::from_str(r#"{"notes_dir": "/home/user/Notes", "auto_update_interval": 10}"#).unwrap();
+        assert_eq!(config.schema_version, 0);
+
+        let migrated = ConfigManager::migrate(config, 0, CURRENT_SCHEMA_VERSION).unwrap();
+
+        assert_eq!(migrated.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(migrated.notes_dir, Some(PathBuf::from("/home/user/Notes")));
+        assert_eq!(migrated.auto_update_interval, 10);
+        assert_eq!(migrated.note_naming_pattern.as_deref(), Some("{number}-{title}.{extension}"));
+        assert_eq!(migrated.default_note_type, Some(NoteType::Markdown));
+        assert_eq!(migrated.subnote_pattern.as_deref(), Some("{parent}{letter}"));
+        assert_eq!(migrated.trash_retention_days, 30);
+    }
+
+    #[test]
+    fn test_set_custom_extensions() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+
+        manager
+            .set_custom_extensions(vec!["rst".into(), " ADOC ".into(), "rst".into()])
+            .unwrap();
+        assert_eq!(manager.get_config().custom_extensions, vec!["rst", "adoc"]);
+
+        for invalid in [".wiki", "notes/md", "", "tar.gz"] {
+            assert!(manager.set_custom_extensions(vec![invalid.into()]).is_err());
+        }
+        assert_eq!(manager.get_config().custom_extensions, vec!["rst", "adoc"]);
+    }
+
+    #[test]
+    fn test_set_custom_stop_words() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        assert!(manager.get_config().custom_stop_words.is_empty());
+
+        manager
+            .set_custom_stop_words(vec!["Also".into(), " also ".into(), "".into(), "etc".into()])
+            .unwrap();
+        assert_eq!(manager.get_config().custom_stop_words, vec!["also", "etc"]);
+    }
+
+    #[test]
+    fn test_set_note_naming_pattern() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+
+        for valid in [
+            "{number}-{title}.{extension}",
+            "{date}-{title}.{extension}",
+            "{datetime}.{extension}",
+            "{number}.{extension}",
+        ] {
+            manager.set_note_naming_pattern(valid.to_string()).unwrap();
+            assert_eq!(manager.get_config().note_naming_pattern.as_deref(), Some(valid));
+        }
+
+        assert!(manager.set_note_naming_pattern("note.{extension}".to_string()).is_err());
+
+        let error = manager
+            .set_note_naming_pattern("{date}.{extension}".to_string())
+            .unwrap_err()
+            .downcast::<PatternValidationError>()
+            .unwrap();
+        assert!(error.invalid_placeholders.is_empty());
+        assert_eq!(manager.get_config().note_naming_pattern.as_deref(), Some("{number}.{extension}"));
+
+        let error = manager
+            .set_note_naming_pattern("{titl}-{number}-{ext}".to_string())
+            .unwrap_err()
+            .downcast::<PatternValidationError>()
+            .unwrap();
+        assert_eq!(error.invalid_placeholders, vec!["{titl}", "{ext}"]);
+    }
+
+    #[test]
+    fn test_reset_to_defaults_keeps_notes_dir() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let notes_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        manager.set_notes_dir(notes_dir.path().to_path_buf()).unwrap();
+        manager.set_trash_retention_days(7).unwrap();
+
+        manager.reset_to_defaults().unwrap();
+
+        let config = manager.get_config();
+        assert_eq!(config.notes_dir.as_deref(), Some(notes_dir.path()));
+        assert_eq!(config.trash_retention_days, AppConfig::default().trash_retention_days);
+    }
+
+    #[test]
+    fn test_export_and_import_config() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let notes_dir = tempfile::TempDir::new().unwrap();
+        let export_path = config_dir.path().join("export.json");
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        manager.set_notes_dir(notes_dir.path().to_path_buf()).unwrap();
+        manager.set_trash_retention_days(7).unwrap();
+
+        manager.export_config(&export_path).unwrap();
+        manager.reset_to_defaults().unwrap();
+        manager.import_config(&export_path).unwrap();
+
+        assert_eq!(manager.get_config().trash_retention_days, 7);
+
+        let missing_dir = AppConfig {
+            notes_dir: Some(notes_dir.path().join("missing")),
+            ..Default::default()
+        };
+        fs::write(&export_path, serde_json::to_string(&missing_dir).unwrap()).unwrap();
+
+        assert!(manager.import_config(&export_path).is_err());
+        assert_eq!(manager.get_config().trash_retention_days, 7);
+    }
+
+    #[test]
+    fn test_set_max_paginated_results() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        assert_eq!(manager.get_config().max_paginated_results, 1000);
+
+        manager.set_max_paginated_results(50).unwrap();
+        assert!(manager.set_max_paginated_results(0).is_err());
+
+        let reloaded = ConfigManager::new(config_dir.path()).unwrap();
+        assert_eq!(reloaded.get_config().max_paginated_results, 50);
+    }
+
+    #[test]
+    fn test_set_search_writer_memory_clamps_to_maximum() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+
+        manager.set_search_writer_memory(100).unwrap();
+        assert_eq!(manager.get_config().search_writer_memory_mb, 100);
+
+        manager.set_search_writer_memory(u32::MAX).unwrap();
+        assert_eq!(manager.get_config().search_writer_memory_mb, MAX_SEARCH_WRITER_MEMORY_MB);
+        assert!(manager.set_search_writer_memory(MIN_SEARCH_WRITER_MEMORY_MB - 1).is_err());
+
+        let reloaded = ConfigManager::new(config_dir.path()).unwrap();
+        assert_eq!(reloaded.get_config().search_writer_memory_mb, MAX_SEARCH_WRITER_MEMORY_MB);
+    }
+
+    #[test]
+    fn test_replace_note_ids() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        let state = NoteUiState { cursor_position: Some(3), scroll_offset: None };
+        manager.pin_note("old").unwrap();
+        manager.pin_note("kept").unwrap();
+        manager.set_note_ui_state("old", state.clone()).unwrap();
+        manager.record_note_viewed("old").unwrap();
+
+        let new_ids = HashMap::from([("old".to_string(), "new".to_string())]);
+        manager.replace_note_ids(&new_ids).unwrap();
+
+        let reloaded = ConfigManager::new(config_dir.path()).unwrap();
+        let config = reloaded.get_config();
+        assert_eq!(config.pinned_notes, vec!["new".to_string(), "kept".to_string()]);
+        assert_eq!(reloaded.get_note_ui_state("new"), Some(state));
+        assert_eq!(reloaded.get_note_ui_state("old"), None);
+        assert_eq!(config.recently_viewed[0].0, "new");
+    }
+
+    #[test]
+    fn test_remove_note_references() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        let state = NoteUiState { cursor_position: Some(3), scroll_offset: None };
+        manager.pin_note("gone").unwrap();
+        manager.pin_note("kept").unwrap();
+        manager.set_note_ui_state("gone", state.clone()).unwrap();
+        manager.set_note_ui_state("kept", state.clone()).unwrap();
+        manager.record_note_viewed("kept").unwrap();
+        manager.record_note_viewed("gone").unwrap();
+
+        manager.remove_note_references("gone").unwrap();
+
+        let reloaded = ConfigManager::new(config_dir.path()).unwrap();
+        let config = reloaded.get_config();
+        assert_eq!(config.pinned_notes, vec!["kept".to_string()]);
+        assert_eq!(reloaded.get_note_ui_state("gone"), None);
+        assert_eq!(reloaded.get_note_ui_state("kept"), Some(state));
+        assert_eq!(
+            config.recently_viewed.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(),
+            vec!["kept"]
+        );
+    }
+
+    #[test]
+    fn test_import_config_validates_settings() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let notes_dir = tempfile::TempDir::new().unwrap();
+        let import_path = config_dir.path().join("import.json");
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        manager.set_notes_dir(notes_dir.path().to_path_buf()).unwrap();
+
+        let invalid_configs = [
+            AppConfig { note_naming_pattern: Some("{titl}.md".into()), ..Default::default() },
+            AppConfig { max_history_versions: 0, ..Default::default() },
+            AppConfig { search_writer_memory_mb: 1, ..Default::default() },
+            AppConfig { max_paginated_results: 0, ..Default::default() },
+            AppConfig { custom_extensions: vec!["tar.gz".into()], ..Default::default() },
+        ];
+        for config in invalid_configs {
+            fs::write(&import_path, serde_json::to_string(&config).unwrap()).unwrap();
+            assert!(manager.import_config(&import_path).is_err());
+        }
+
+        // A config without notes directory keeps the current one
+        let config = AppConfig {
+            notes_dir: None,
+            custom_stop_words: vec![" Also ".into(), "also".into()],
+            ..Default::default()
+        };
+        fs::write(&import_path, serde_json::to_string(&config).unwrap()).unwrap();
+        manager.import_config(&import_path).unwrap();
+
+        assert_eq!(manager.get_config().notes_dir, Some(notes_dir.path().to_path_buf()));
+        assert_eq!(manager.get_config().custom_stop_words, vec!["also"]);
+    }
+
+    #[test]
+    fn test_save_config_is_never_half_written() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let config_path = config_dir.path().join("config.json");
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+        manager.save_config().unwrap();
+
+        let writer = std::thread::spawn(move || {
+            for i in 0..50 {
+                // A large config makes a partial write likely to be observed
+                manager.config.daily_note_template = Some(format!("{}", i).repeat(50_000));
+                manager.save_config().unwrap();
+            }
+        });
+
+        while !writer.is_finished() {
+            let saved = fs::read_to_string(&config_path).unwrap();
+            assert!(serde_json::from_str::<AppConfig>(&saved).is_ok());
+        }
+        writer.join().unwrap();
+
+        assert!(!config_dir.path().join("config.tmp").exists());
+    }
+
+    #[test]
+    fn test_record_note_viewed() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(config_dir.path()).unwrap();
+
+        for i in 0..MAX_RECENTLY_VIEWED + 5 {
+            manager.record_note_viewed(&format!("note{}", i)).unwrap();
+        }
+        manager.record_note_viewed("note10").unwrap();
+
+        let viewed: Vec<String> = manager
+            .get_config()
+            .recently_viewed
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(viewed.len(), MAX_RECENTLY_VIEWED);
+        assert_eq!(viewed[0], "note10");
+        assert_eq!(viewed[1], format!("note{}", MAX_RECENTLY_VIEWED + 4));
+        assert_eq!(viewed.iter().filter(|id| *id == "note10").count(), 1);
+    }
+
+    #[test]
+    fn test_config_manager_upgrades_config_file() {
+        let config_dir = tempfile::TempDir::new().unwrap();
+        fs::write(config_dir.path().join("config.json"), r#"{"notes_dir": null}"#).unwrap();
+
+        let manager = ConfigManager::new(config_dir.path()).unwrap();
+        assert_eq!(manager.get_config().schema_version, CURRENT_SCHEMA_VERSION);
+
+        let saved = fs::read_to_string(config_dir.path().join("config.json")).unwrap();
+        let saved: AppConfig = serde_json::from_str(&saved).unwrap();
+        assert_eq!(saved.schema_version, CURRENT_SCHEMA_VERSION);
     }
 }
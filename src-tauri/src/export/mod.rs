@@ -0,0 +1,390 @@
+use anyhow::{Context, Result};
+use pulldown_cmark::{html, Options, Parser};
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::notes::{
+    frontmatter_tags, inline_tags, remove_inline_tags, split_frontmatter, Note, NoteType,
+    RESERVED_DIRS,
+};
+
+/// Summary of an export to another application's format
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportReport {
+    /// Number of notes copied unchanged
+    pub copied: usize,
+    /// Number of notes whose content or file type was converted
+    pub converted: usize,
+    /// Errors for notes that could not be exported
+    pub errors: Vec<String>,
+}
+
+/// Converts a note to a standalone HTML document
+///
+/// Markdown notes are rendered to HTML; plain text and Org notes are wrapped
+/// in `<pre>` without any markdown processing.
+///
+/// # Parameters
+/// * `note` - The note to convert
+///
+/// # Returns
+/// The HTML document
+pub fn note_to_html(note: &Note) -> String {
+    let body = match note.file_type {
+        NoteType::Markdown => {
+            let mut options = Options::empty();
+            options.insert(Options::ENABLE_TABLES);
+            options.insert(Options::ENABLE_STRIKETHROUGH);
+            options.insert(Options::ENABLE_TASKLISTS);
+
+            let parser = Parser::new_ext(&note.content, options);
+            let mut body = String::new();
+            html::push_html(&mut body, parser);
+            body
+        }
+        NoteType::PlainText | NoteType::Org => format!("<pre>{}</pre>\n", escape_html(&note.content)),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(&note.title),
+        body
+    )
+}
+
+/// Writes a note as an HTML file
+///
+/// # Parameters
+/// * `note` - The note to export
+/// * `output_path` - Path of the HTML file to write
+///
+/// # Returns
+/// Result indicating success or failure
+pub fn export_note_to_html(note: &Note, output_path: &Path) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
+
+    fs::write(output_path, note_to_html(note)).context("Failed to write HTML file")?;
+
+    Ok(())
+}
+
+/// Writes each note as an HTML file inside a directory
+///
+/// Files mirror the notes' paths relative to the notes directory, with an
+/// `.html` extension, so notes with the same name in different folders do not collide.
+///
+/// # Parameters
+/// * `notes` - The notes to export
+/// * `output_dir` - Directory to write the HTML files to
+///
+/// # Returns
+/// Paths of the written files
+pub fn export_notes_to_html(notes: &[Note], output_dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(output_dir).context("Failed to create output directory")?;
+
+    let mut written = Vec::with_capacity(notes.len());
+
+    for note in notes {
+        let output_path = output_dir.join(Path::new(&note.path).with_extension("html"));
+        export_note_to_html(note, &output_path)?;
+        written.push(output_path);
+    }
+
+    Ok(written)
+}
+
+/// Exports the notes directory as an Obsidian vault
+///
+/// Markdown and plain text notes are written as `.md` files with the same
+/// relative paths. A plain text note next to a Markdown note of the same name
+/// is written as `name (txt).md` instead, so neither overwrites the other. Inline `#tags` are moved to a `tags:` list in the
+/// frontmatter and wikilinks are reduced to the note title. Other files,
+/// reserved folders and the output directory itself are skipped. The notes
+/// directory is never modified.
+///
+/// # Parameters
+/// * `notes_dir` - Path to the notes directory
+/// * `output_dir` - Directory to write the Obsidian vault to
+///
+/// # Returns
+/// The number of copied and converted notes and per-note errors
+pub fn export_to_obsidian(notes_dir: &Path, output_dir: &Path) -> Result<ExportReport> {
+    fs::create_dir_all(output_dir).context("Failed to create output directory")?;
+
+    let notes_dir_canonical = notes_dir.canonicalize().context("Failed to resolve notes directory")?;
+    let output_dir_canonical = output_dir.canonicalize().context("Failed to resolve output directory")?;
+
+    if notes_dir_canonical == output_dir_canonical {
+        anyhow::bail!("Output directory must differ from the notes directory");
+    }
+
+    let entries = WalkDir::new(notes_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let is_reserved = entry.depth() == 1
+                && entry.file_name().to_str().map_or(false, |name| RESERVED_DIRS.contains(&name));
+            let is_output = entry
+                .path()
+                .canonicalize()
+                .map_or(false, |path| path == output_dir_canonical);
+            !is_reserved && !is_output
+        });
+
+    let mut report = ExportReport::default();
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.errors.push(e.to_string());
+                continue;
+            }
+        };
+
+        let is_plain_text = match entry.path().extension().and_then(|ext| ext.to_str()) {
+            Some("md") => false,
+            Some("txt") => true,
+            _ => continue,
+        };
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative_path = entry
+            .path()
+            .strip_prefix(notes_dir)
+            .context("Path is not in notes directory")?;
+
+        let target = match obsidian_target_path(notes_dir, relative_path, is_plain_text) {
+            Ok(target) => output_dir.join(target),
+            Err(e) => {
+                report.errors.push(format!("{}: {}", relative_path.display(), e));
+                continue;
+            }
+        };
+
+        match export_obsidian_note(entry.path(), &target) {
+            Ok(changed) if changed || is_plain_text => report.converted += 1,
+            Ok(_) => report.copied += 1,
+            Err(e) => report
+                .errors
+                .push(format!("{}: {}", relative_path.display(), e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Gets the path of the Markdown file a note is exported to
+///
+/// # Parameters
+/// * `notes_dir` - Path to the notes directory
+/// * `relative_path` - Path of the note relative to the notes directory
+/// * `is_plain_text` - Whether the note is a plain text note
+///
+/// # Returns
+/// The target path relative to the output directory, or an error if it is
+/// taken by another note
+fn obsidian_target_path(notes_dir: &Path, relative_path: &Path, is_plain_text: bool) -> Result<PathBuf> {
+    let target = relative_path.with_extension("md");
+
+    if !is_plain_text || !notes_dir.join(&target).exists() {
+        return Ok(target);
+    }
+
+    let stem = relative_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .context("Invalid note file name")?;
+    let target = relative_path.with_file_name(format!("{} (txt).md", stem));
+
+    if notes_dir.join(&target).exists() {
+        anyhow::bail!("{} is taken by another note", target.display());
+    }
+
+    Ok(target)
+}
+
+/// Writes a single note in Obsidian format
+///
+/// # Parameters
+/// * `source` - Path of the note
+/// * `target` - Path of the Markdown file to write
+///
+/// # Returns
+/// Whether the content had to be converted
+fn export_obsidian_note(source: &Path, target: &Path) -> Result<bool> {
+    let content = fs::read_to_string(source).context("Failed to read note file")?;
+    let converted = to_obsidian_markdown(&content);
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
+
+    fs::write(target, &converted).context("Failed to write note file")?;
+
+    Ok(converted != content)
+}
+
+/// Converts note content to Obsidian-flavored Markdown
+///
+/// # Parameters
+/// * `content` - Content of the note
+///
+/// # Returns
+/// The content with tags in the frontmatter and title-only wikilinks
+fn to_obsidian_markdown(content: &str) -> String {
+    let (frontmatter, body) = split_frontmatter(content);
+    let frontmatter_block = &content[..content.len() - body.len()];
+
+    let mut tags = frontmatter.map(frontmatter_tags).unwrap_or_default();
+    let body_tags = inline_tags(body);
+    let body = remove_inline_tags(body, &body_tags);
+
+    for tag in body_tags {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let body = strip_wikilink_paths(&body);
+
+    if tags.is_empty() {
+        return format!("{}{}", frontmatter_block, body);
+    }
+
+    let mut new_frontmatter = frontmatter.map(without_tags_key).unwrap_or_default();
+    new_frontmatter.push_str("tags:\n");
+    for tag in &tags {
+        new_frontmatter.push_str(&format!("  - {}\n", tag));
+    }
+
+    format!("---\n{}---\n{}", new_frontmatter, body)
+}
+
+/// Removes the `tags:` key and its list items from frontmatter
+fn without_tags_key(frontmatter: &str) -> String {
+    let mut kept = String::new();
+    let mut in_tags = false;
+
+    for line in frontmatter.split_inclusive('\n') {
+        if line.starts_with("tags:") {
+            in_tags = true;
+            continue;
+        }
+
+        if in_tags && line.trim_start().starts_with('-') {
+            continue;
+        }
+
+        in_tags = false;
+        kept.push_str(line);
+    }
+
+    if !kept.is_empty() && !kept.ends_with('\n') {
+        kept.push('\n');
+    }
+
+    kept
+}
+
+/// Reduces `[[folder/Title]]` wikilinks to `[[Title]]`, keeping aliases
+fn strip_wikilink_paths(content: &str) -> String {
+    let regex = Regex::new(r"\[\[([^\[\]|]+)(\|[^\[\]]*)?\]\]").expect("Invalid wikilink regex");
+
+    regex
+        .replace_all(content, |captures: &regex::Captures| {
+            let target = &captures[1];
+            let title = target.rsplit('/').next().unwrap_or(target);
+            let title = title.strip_suffix(".md").unwrap_or(title);
+            let alias = captures.get(2).map_or("", |alias| alias.as_str());
+            format!("[[{}{}]]", title, alias)
+        })
+        .into_owned()
+}
+
+/// Escapes text for inclusion in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            other => escaped.push(other),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_to_obsidian_markdown() {
+        assert_eq!(
+            to_obsidian_markdown("# Title\n\nSee [[work/Plan|the plan]] #todo\n#idea\n"),
+            "---\ntags:\n  - todo\n  - idea\n---\n# Title\n\nSee [[Plan|the plan]]\n"
+        );
+        assert_eq!(
+            to_obsidian_markdown("---\naliases: [x]\ntags:\n  - a\n---\nBody #b"),
+            "---\naliases: [x]\ntags:\n  - a\n  - b\n---\nBody"
+        );
+        assert_eq!(to_obsidian_markdown("No tags"), "No tags");
+    }
+
+    #[test]
+    fn test_export_to_obsidian() {
+        let notes_dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        fs::create_dir_all(notes_dir.path().join("work")).unwrap();
+        fs::create_dir_all(notes_dir.path().join(".trash")).unwrap();
+        fs::write(notes_dir.path().join("a.md"), "# A").unwrap();
+        fs::write(notes_dir.path().join("work").join("b.txt"), "b #tag").unwrap();
+        fs::write(notes_dir.path().join(".trash").join("c.md"), "# C").unwrap();
+
+        let report = export_to_obsidian(notes_dir.path(), output_dir.path()).unwrap();
+
+        assert_eq!((report.copied, report.converted), (1, 1));
+        assert!(report.errors.is_empty());
+        assert_eq!(fs::read_to_string(output_dir.path().join("a.md")).unwrap(), "# A");
+        assert_eq!(
+            fs::read_to_string(output_dir.path().join("work").join("b.md")).unwrap(),
+            "---\ntags:\n  - tag\n---\nb"
+        );
+        assert!(!output_dir.path().join(".trash").exists());
+        assert_eq!(fs::read_to_string(notes_dir.path().join("work").join("b.txt")).unwrap(), "b #tag");
+    }
+
+    #[test]
+    fn test_export_to_obsidian_keeps_notes_with_same_name() {
+        let notes_dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        fs::write(notes_dir.path().join("x.md"), "markdown").unwrap();
+        fs::write(notes_dir.path().join("x.txt"), "plain").unwrap();
+        fs::write(notes_dir.path().join("y.md"), "markdown").unwrap();
+        fs::write(notes_dir.path().join("y.txt"), "plain").unwrap();
+        fs::write(notes_dir.path().join("y (txt).md"), "taken").unwrap();
+
+        let report = export_to_obsidian(notes_dir.path(), output_dir.path()).unwrap();
+
+        assert_eq!(fs::read_to_string(output_dir.path().join("x.md")).unwrap(), "markdown");
+        assert_eq!(fs::read_to_string(output_dir.path().join("x (txt).md")).unwrap(), "plain");
+        assert_eq!(fs::read_to_string(output_dir.path().join("y (txt).md")).unwrap(), "taken");
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with("y.txt"));
+    }
+}
@@ -0,0 +1,268 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::notes::{ImportReport, NoteManager, APP_DIR, RESERVED_DIRS};
+
+/// Name of the folder holding the configuration of an Obsidian vault
+const OBSIDIAN_CONFIG_DIR: &str = ".obsidian";
+
+/// File in the application data folder recording the notes written by Obsidian imports
+const IMPORT_MANIFEST_FILE: &str = "obsidian_import.json";
+
+/// SHA-256 hashes of the imported notes as written, keyed by relative path
+type ImportManifest = HashMap<String, String>;
+
+/// What happened to a single note during an import
+#[derive(Debug, PartialEq, Eq)]
+enum ImportOutcome {
+    /// The note was copied
+    Imported,
+    /// The target already has the same content
+    Unchanged,
+    /// The target was edited since the last import or was not imported at all
+    Conflict,
+}
+
+/// Imports the Markdown notes of an Obsidian vault
+///
+/// Files are copied with their subdirectory structure. Frontmatter `tags:`
+/// lists are kept as they are, since tags are read from frontmatter as well
+/// as from inline `#tags`. The `.obsidian` folder and reserved folders are
+/// skipped, as are notes that already exist with the same content.
+///
+/// An existing note is only overwritten if an earlier import wrote it, it
+/// was not edited since, and the source is newer. Any other existing note is
+/// reported as a conflict and left unchanged.
+///
+/// # Parameters
+/// * `source_dir` - Path to the Obsidian vault
+/// * `target_note_manager` - Note manager of the vault to import into
+///
+/// # Returns
+/// A report of imported, skipped, conflicting and failed files
+pub fn import_from_obsidian(source_dir: &Path, target_note_manager: &NoteManager) -> Result<ImportReport> {
+    if !source_dir.is_dir() {
+        anyhow::bail!("Source path is not a directory");
+    }
+
+    let mut report = ImportReport::default();
+    let manifest_path = manifest_path(target_note_manager);
+    let mut manifest = load_manifest(&manifest_path);
+
+    let entries = WalkDir::new(source_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            !entry.file_type().is_dir()
+                || entry.file_name().to_str().map_or(true, |name| {
+                    name != OBSIDIAN_CONFIG_DIR && !RESERVED_DIRS.contains(&name)
+                })
+        });
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.errors.push(e.to_string());
+                continue;
+            }
+        };
+
+        if !entry.file_type().is_file() || entry.path().extension().map_or(true, |ext| ext != "md") {
+            continue;
+        }
+
+        let relative_path = match entry.path().strip_prefix(source_dir) {
+            Ok(relative_path) => relative_path.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+
+        match import_note(entry.path(), &relative_path, target_note_manager, &mut manifest) {
+            Ok(ImportOutcome::Imported) => report.imported += 1,
+            Ok(ImportOutcome::Unchanged) => report.skipped += 1,
+            Ok(ImportOutcome::Conflict) => report.conflicts.push(relative_path),
+            Err(e) => report.errors.push(format!("{}: {}", relative_path, e)),
+        }
+    }
+
+    if report.imported > 0 {
+        target_note_manager.invalidate_cache();
+    }
+
+    save_manifest(&manifest_path, &manifest)?;
+
+    Ok(report)
+}
+
+/// Copies a single note into the target vault
+///
+/// The copy gets the modification time of the source, so a later import can
+/// tell whether the source changed since.
+///
+/// # Parameters
+/// * `source` - Path of the note in the Obsidian vault
+/// * `relative_path` - Path of the note relative to the vault
+/// * `target_note_manager` - Note manager of the vault to import into
+/// * `manifest` - Hashes of previously imported notes, updated with this note
+///
+/// # Returns
+/// Whether the note was imported, unchanged or conflicts with an existing note
+fn import_note(
+    source: &Path,
+    relative_path: &str,
+    target_note_manager: &NoteManager,
+    manifest: &mut ImportManifest,
+) -> Result<ImportOutcome> {
+    // Validate the target so that nothing is written outside the notes directory
+    let target = target_note_manager.resolve_relative_path(relative_path)?;
+
+    let modified = source
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .context("Failed to read file metadata")?;
+    let content = fs::read(source).context("Failed to read note")?;
+    let hash = content_hash(&content);
+
+    match fs::read(&target) {
+        Ok(existing) => {
+            let existing_hash = content_hash(&existing);
+            if existing_hash == hash {
+                manifest.insert(relative_path.to_string(), hash);
+                return Ok(ImportOutcome::Unchanged);
+            }
+
+            // Only replace notes written by an earlier import and not edited since
+            let imported_unedited = manifest.get(relative_path) == Some(&existing_hash);
+            let source_newer = target
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .map_or(false, |target_modified| target_modified < modified);
+            if !(imported_unedited && source_newer) {
+                return Ok(ImportOutcome::Conflict);
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).context("Failed to read existing note"),
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).context("Failed to create parent directories")?;
+    }
+
+    fs::write(&target, &content).context("Failed to copy note")?;
+    fs::File::options()
+        .write(true)
+        .open(&target)
+        .and_then(|file| file.set_modified(modified))
+        .context("Failed to set modification time")?;
+
+    manifest.insert(relative_path.to_string(), hash);
+
+    Ok(ImportOutcome::Imported)
+}
+
+/// Gets the path of the import manifest of a vault
+fn manifest_path(note_manager: &NoteManager) -> PathBuf {
+    note_manager.notes_dir().join(APP_DIR).join(IMPORT_MANIFEST_FILE)
+}
+
+/// Reads the import manifest, starting over if it is missing or invalid
+fn load_manifest(path: &Path) -> ImportManifest {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| {
+            serde_json::from_str(&contents)
+                .map_err(|e| log::warn!("Ignoring invalid import manifest: {}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Writes the import manifest
+fn save_manifest(path: &Path, manifest: &ImportManifest) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create application data directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(manifest).context("Failed to serialize import manifest")?;
+    fs::write(path, contents).context("Failed to write import manifest")
+}
+
+/// Computes the hex-encoded SHA-256 hash of file content
+fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_import_from_obsidian() {
+        let vault = TempDir::new().unwrap();
+        let notes_dir = TempDir::new().unwrap();
+        fs::create_dir_all(vault.path().join(".obsidian")).unwrap();
+        fs::create_dir_all(vault.path().join("work")).unwrap();
+        fs::write(vault.path().join(".obsidian").join("app.md"), "config").unwrap();
+        fs::write(vault.path().join("work").join("a.md"), "---\ntags: [x]\n---\n# A").unwrap();
+        fs::write(vault.path().join("image.png"), "png").unwrap();
+        let manager = NoteManager::new(notes_dir.path().to_path_buf());
+
+        let report = import_from_obsidian(vault.path(), &manager).unwrap();
+        assert_eq!((report.imported, report.skipped), (1, 0));
+        assert!(!notes_dir.path().join(".obsidian").exists());
+        assert!(!notes_dir.path().join("image.png").exists());
+        assert_eq!(
+            fs::read_to_string(notes_dir.path().join("work").join("a.md")).unwrap(),
+            "---\ntags: [x]\n---\n# A"
+        );
+
+        let report = import_from_obsidian(vault.path(), &manager).unwrap();
+        assert_eq!((report.imported, report.skipped), (0, 1));
+    }
+
+    #[test]
+    fn test_import_from_obsidian_keeps_edited_notes() {
+        let vault = TempDir::new().unwrap();
+        let notes_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(notes_dir.path().to_path_buf());
+        let set_modified = |path: &Path, seconds: u64| {
+            let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds);
+            fs::File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+        };
+
+        // A note that was never imported is not overwritten
+        fs::write(notes_dir.path().join("existing.md"), "# Mine").unwrap();
+        fs::write(vault.path().join("existing.md"), "# Theirs").unwrap();
+        fs::write(vault.path().join("a.md"), "# A").unwrap();
+        set_modified(&vault.path().join("a.md"), 1_000);
+
+        let report = import_from_obsidian(vault.path(), &manager).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.conflicts, vec!["existing.md"]);
+        assert_eq!(fs::read_to_string(notes_dir.path().join("existing.md")).unwrap(), "# Mine");
+
+        // A newer source replaces an imported note that was not edited
+        fs::write(vault.path().join("a.md"), "# A v2").unwrap();
+        set_modified(&vault.path().join("a.md"), 2_000);
+        let report = import_from_obsidian(vault.path(), &manager).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(fs::read_to_string(notes_dir.path().join("a.md")).unwrap(), "# A v2");
+
+        // Edits made after the import are kept
+        fs::write(notes_dir.path().join("a.md"), "# A edited").unwrap();
+        set_modified(&notes_dir.path().join("a.md"), 2_000);
+        fs::write(vault.path().join("a.md"), "# A v3").unwrap();
+        set_modified(&vault.path().join("a.md"), 3_000);
+        let mut report = import_from_obsidian(vault.path(), &manager).unwrap();
+        report.conflicts.sort();
+        assert_eq!(report.imported, 0);
+        assert_eq!(report.conflicts, vec!["a.md", "existing.md"]);
+        assert_eq!(fs::read_to_string(notes_dir.path().join("a.md")).unwrap(), "# A edited");
+    }
+}
@@ -1,27 +1,117 @@
+mod backup;
 mod config;
+mod export;
+mod import;
 mod notes;
 mod search;
+mod watcher;
+
+/// Items used by the benchmarks in `benches/`, which can only reach public items
+#[doc(hidden)]
+pub mod bench {
+    pub use crate::config::AppConfig;
+    pub use crate::notes::{Note, NoteManager, NoteType};
+    pub use crate::search::SearchService;
+}
 
 use anyhow::Result;
-use log::info;
-use std::path::PathBuf;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::RwLock;
 
-use config::{AppConfig, ConfigManager};
-use notes::{Note, NoteManager, NoteSummary};
-use search::{SearchResult, SearchService};
+use backup::{BackupInfo, BackupService};
+use config::{AppConfig, ConfigManager, NoteUiState};
+use export::ExportReport;
+use notes::{
+    BatchUpdateResult, BrokenLink, DirValidation, DuplicateGroup, FolderNode, GraphData, IdScheme,
+    ImportReport, Note, NoteError, NoteListDiagnostics, NoteLoadError, NoteManager, NoteSummary,
+    NoteTemplate, NoteVersion, PaginatedNotes, RelatedNote, TagStats, TrashedNote, VaultStats,
+};
+use search::error::SearchError;
+use search::{
+    DateField, IndexHealth, IndexProgress, PaginatedSearch, SearchOptions, SearchResult,
+    SearchService,
+};
+use watcher::WatcherService;
 
 #[cfg(target_os = "ios")]
 use std::sync::Arc;
 
+/// Name of the event emitted while the search index is rebuilt
+const INDEX_REBUILD_PROGRESS_EVENT: &str = "index-rebuild-progress";
+
+/// How often the backup scheduler checks whether a backup is due
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Number of words returned by the word frequency commands if no limit is given
+const DEFAULT_WORD_FREQUENCY_LIMIT: usize = 50;
+
 /// Application state shared between commands
 struct AppState {
     config_manager: Mutex<ConfigManager>,
-    note_manager: Mutex<Option<NoteManager>>,
+    note_manager: RwLock<Option<NoteManager>>,
     search_service: Mutex<SearchService>,
     last_index_rebuild: Mutex<Instant>,
+    /// Whether the last index rebuild failed, so searches scan the notes instead
+    search_index_failed: Mutex<bool>,
+    watcher_service: Mutex<Option<WatcherService>>,
+}
+
+/// Creates a note manager for a notes directory using the configured options
+///
+/// # Parameters
+/// * `notes_dir` - Path to the notes directory
+/// * `config` - Current application configuration
+///
+/// # Returns
+/// A configured NoteManager
+fn create_note_manager(notes_dir: PathBuf, config: &AppConfig) -> NoteManager {
+    let note_manager = NoteManager::new(notes_dir)
+        .with_max_history_versions(config.max_history_versions)
+        .with_excluded_folders(config.excluded_folders.clone())
+        .with_id_scheme(config.id_scheme)
+        .with_org_files(config.include_org_files)
+        .with_custom_extensions(config.custom_extensions.clone())
+        .with_walk_options(config.follow_symlinks, config.max_walk_depth)
+        .with_auto_create_folders(config.auto_create_folders)
+        .with_custom_stop_words(config.custom_stop_words.clone())
+        .with_summary_cache(config.watch_external_changes);
+
+    note_manager.rebuild_id_cache();
+
+    note_manager
+}
+
+/// Emits the progress of a search index rebuild to the frontend
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle used to emit events
+/// * `progress` - Current rebuild progress
+fn emit_index_progress(app_handle: &AppHandle, progress: IndexProgress) {
+    if let Err(e) = app_handle.emit(INDEX_REBUILD_PROGRESS_EVENT, progress) {
+        warn!("Failed to emit {} event: {}", INDEX_REBUILD_PROGRESS_EVENT, e);
+    }
+}
+
+/// Re-creates the note manager so it picks up changed configuration
+///
+/// # Parameters
+/// * `state` - Application state
+/// * `config` - Updated application configuration
+///
+/// # Returns
+/// Result indicating success or failure
+async fn refresh_note_manager(state: &State<'_, AppState>, config: &AppConfig) -> Result<(), String> {
+    if let Some(notes_dir) = config.notes_dir.clone() {
+        *state.note_manager.write().await =
+            Some(create_note_manager(notes_dir, config));
+    }
+
+    Ok(())
 }
 
 /// Gets the current configuration
@@ -34,23 +124,128 @@ async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
     Ok(config_manager.get_config())
 }
 
+/// Applies a configuration that replaced all settings at once
+///
+/// Re-creates the note manager and search service, restarts the watcher and
+/// rebuilds the search index.
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle
+/// * `state` - Application state
+/// * `config` - The new application configuration
+///
+/// # Returns
+/// Result indicating success or failure
+async fn apply_config(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    config: &AppConfig,
+) -> Result<(), String> {
+    refresh_note_manager(&state, config).await?;
+
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    let search_service = SearchService::new(&app_dir, config).map_err(|e| e.to_string())?;
+    *state.search_service.lock().map_err(|e| e.to_string())? = search_service;
+
+    update_watcher(&app_handle, &state, config)?;
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(())
+}
+
+/// Resets all settings except the notes directory to their defaults
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn reset_config(app_handle: AppHandle, state: State<'_, AppState>) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .reset_to_defaults()
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    apply_config(app_handle, state, &config).await?;
+
+    Ok(config)
+}
+
+/// Exports the configuration to a JSON file
+///
+/// # Parameters
+/// * `output_path` - Path of the file to write
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn export_config(output_path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .export_config(&PathBuf::from(output_path))
+        .map_err(|e| e.to_string())
+}
+
+/// Imports the configuration from a JSON file
+///
+/// # Parameters
+/// * `input_path` - Path of the file to read
+///
+/// # Returns
+/// The imported application configuration
+#[tauri::command]
+async fn import_config(
+    app_handle: AppHandle,
+    input_path: String,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .import_config(&PathBuf::from(input_path))
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    apply_config(app_handle, state, &config).await?;
+
+    Ok(config)
+}
+
 /// Sets the note naming pattern
 ///
 /// # Parameters
 /// * `pattern` - Pattern for naming new notes
 ///
 /// # Returns
-/// The updated application configuration
+/// The updated application configuration, or the error with any unknown placeholders
 #[tauri::command]
 async fn set_note_naming_pattern(
     pattern: String,
     state: State<'_, AppState>,
-) -> Result<AppConfig, String> {
-    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+) -> Result<AppConfig, config::PatternValidationError> {
+    let mut config_manager = state
+        .config_manager
+        .lock()
+        .map_err(|e| config::PatternValidationError {
+            message: e.to_string(),
+            invalid_placeholders: Vec::new(),
+        })?;
 
-    config_manager
-        .set_note_naming_pattern(pattern)
-        .map_err(|e| e.to_string())?;
+    config_manager.set_note_naming_pattern(pattern)?;
 
     Ok(config_manager.get_config())
 }
@@ -139,560 +334,3541 @@ async fn set_auto_update_interval(
     Ok(config_manager.get_config())
 }
 
-/// Selects a folder for storing notes
+/// Sets the default sort order used when listing notes
 ///
 /// # Parameters
-/// * `path` - Path to the notes directory
+/// * `sort` - Sort option used when none is specified
 ///
 /// # Returns
 /// The updated application configuration
 #[tauri::command]
-async fn select_folder(path: String, state: State<'_, AppState>) -> Result<AppConfig, String> {
-    let folder = PathBuf::from(path);
-
-    // Validate folder
-    if !folder.is_dir() {
-        return Err("Invalid directory path".into());
-    }
-
-    // Initialize note manager
-    let note_manager = NoteManager::new(folder.clone());
-    
-    // Get all notes
-    let note_summaries = note_manager.list_notes(None).map_err(|e| e.to_string())?;
-    let mut notes = Vec::new();
-
-    // Load full notes
-    for summary in note_summaries {
-        let note = note_manager
-            .get_note(&summary.id)
-            .map_err(|e| e.to_string())?;
-        notes.push(note);
-    }
-    
-    // Update config
+async fn set_default_sort(
+    sort: notes::SortOption,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
     let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
-    config_manager
-        .set_notes_dir(folder)
-        .map_err(|e| e.to_string())?;
-    
-    // Update note manager
-    *state.note_manager.lock().map_err(|e| e.to_string())? = Some(note_manager);
 
-    // Rebuild search index with all notes
-    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
-
-    // Rebuild index
-    search_service
-        .rebuild_index(&notes)
+    config_manager
+        .set_default_sort(sort)
         .map_err(|e| e.to_string())?;
 
     Ok(config_manager.get_config())
 }
 
-/// Lists all notes in the configured directory
+/// Sets the folders to hide from listing and indexing
+///
+/// The search index is rebuilt so that it matches the new exclusions.
 ///
 /// # Parameters
-/// * `sort` - Optional sort option to determine the order of notes
+/// * `folders` - Names of folders to exclude at any depth
 ///
 /// # Returns
-/// A list of note summaries
+/// The updated application configuration
 #[tauri::command]
-async fn list_notes(
-    sort: Option<notes::SortOption>,
+async fn set_excluded_folders(
+    app_handle: AppHandle,
+    folders: Vec<String>,
     state: State<'_, AppState>,
-) -> Result<Vec<NoteSummary>, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
 
-    let Some(note_manager) = note_manager_lock.as_ref() else {
-        return Err("Note manager not initialized".into());
+        config_manager
+            .set_excluded_folders(folders)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
     };
 
-    note_manager.list_notes(sort).map_err(|e| e.to_string())
+    refresh_note_manager(&state, &config).await?;
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(config)
 }
 
-/// Gets a note by ID
+/// Gets the folders hidden from listing and indexing
+///
+/// # Returns
+/// Names of the excluded folders
+#[tauri::command]
+async fn get_excluded_folders(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+    Ok(config_manager.get_config().excluded_folders)
+}
+
+/// Sets additional file extensions to list as plain text notes
+///
+/// The search index is rebuilt so that it includes the matching files.
 ///
 /// # Parameters
-/// * `id` - ID of the note to retrieve
+/// * `extensions` - Extensions without the leading dot, e.g. `rst`
 ///
 /// # Returns
-/// The note if found
+/// The updated application configuration
 #[tauri::command]
-async fn get_note(id: String, state: State<'_, AppState>) -> Result<Note, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+async fn set_custom_extensions(
+    app_handle: AppHandle,
+    extensions: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
 
-    let Some(note_manager) = note_manager_lock.as_ref() else {
-        return Err("Note manager not initialized".into());
+        config_manager
+            .set_custom_extensions(extensions)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
     };
 
-    note_manager.get_note(&id).map_err(|e| e.to_string())
+    refresh_note_manager(&state, &config).await?;
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(config)
 }
 
-/// Updates the content of a note
+/// Sets whether symbolic links in the notes directory are followed
+///
+/// The search index is rebuilt so that it matches the listed notes.
 ///
 /// # Parameters
-/// * `id` - ID of the note to update
-/// * `content` - New content for the note
+/// * `enabled` - Whether to follow symbolic links
 ///
 /// # Returns
-/// The updated note
+/// The updated application configuration
 #[tauri::command]
-async fn update_note_content(
+async fn set_follow_symlinks(
     app_handle: AppHandle,
-    id: String,
-    content: String,
+    enabled: bool,
     state: State<'_, AppState>,
-) -> Result<Note, String> {
-    // Get the note manager
-    let note_manager = {
-        let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
-        
-        match note_manager_lock.as_ref() {
-            Some(nm) => nm.clone(),
-            None => return Err("Note manager not initialized".into()),
-        }
-    };
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
 
-    // Update the note content
-    let updated_note = note_manager
-        .update_note_content(&id, &content)
-        .map_err(|e| e.to_string())?;
+        config_manager
+            .set_follow_symlinks(enabled)
+            .map_err(|e| e.to_string())?;
 
-    // Check if we should update the search index
-    let should_update_index = {
-        let config = state
-            .config_manager
-            .lock()
-            .map_err(|e| e.to_string())?
-            .get_config();
-        
-        (config.auto_update_search_index, config.auto_update_mode)
+        config_manager.get_config()
     };
 
-    if should_update_index.0 {
-        // Update the search index with the new note content
-        match should_update_index.1 {
-            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
-                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
-                search_service
-                    .index_note(&updated_note)
-                    .map_err(|e| e.to_string())?;
-                info!(
-                    "Incrementally updated search index for note: {}",
-                    updated_note.id
-                );
-            },
-            config::AutoUpdateMode::Periodic => {
-                // For periodic mode, we don't update the index immediately
-                // It will be updated during the next scheduled rebuild
-            }
-        }
+    refresh_note_manager(&state, &config).await?;
 
-        // Check if we need to do a periodic rebuild
-        check_periodic_rebuild(app_handle, state).await?;
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
     }
 
-    Ok(updated_note)
+    Ok(config)
 }
 
-/// Helper function to update backlinks when a note is renamed
+/// Sets whether moving notes into a missing folder creates the folder
 ///
 /// # Parameters
-/// * `note_manager` - The note manager instance
-/// * `old_title` - The original title of the note
-/// * `new_title` - The new title of the note
+/// * `enabled` - Whether to create missing folders
 ///
 /// # Returns
-/// Result indicating success or failure
-fn update_backlinks(note_manager: &NoteManager, old_title: &str, new_title: &str) -> Result<(), String> {
-    // Find all notes that link to the old title
-    let backlinks = note_manager.find_backlinks(old_title).map_err(|e| e.to_string())?;
-    
-    // Update each backlink
-    for backlink in backlinks {
-        // Get the full note content
-        let backlink_note = note_manager.get_note(&backlink.id).map_err(|e| e.to_string())?;
-        
-        // Replace [[Old Title]] with [[New Title]] in the content
-        let updated_content = backlink_note.content.replace(
-            &format!("[[{}]]", old_title),
-            &format!("[[{}]]", new_title)
-        );
-        
-        // Save the updated content
-        note_manager.update_note_content(&backlink.id, &updated_content)
+/// The updated application configuration
+#[tauri::command]
+async fn set_auto_create_folders(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_auto_create_folders(enabled)
             .map_err(|e| e.to_string())?;
-    }
-    
-    Ok(())
+
+        config_manager.get_config()
+    };
+
+    refresh_note_manager(&state, &config).await?;
+
+    Ok(config)
 }
 
-/// Renames a note file
+/// Sets the words left out of word frequencies
 ///
 /// # Parameters
-/// * `id` - ID of the note to rename
-/// * `new_name` - New name for the note file (without extension)
+/// * `words` - Words to ignore in addition to common English words
 ///
 /// # Returns
-/// The updated note with new ID
+/// The updated application configuration
 #[tauri::command]
-async fn rename_note(
-    app_handle: AppHandle,
-    id: String,
-    new_name: String,
+async fn set_custom_stop_words(
+    words: Vec<String>,
     state: State<'_, AppState>,
-) -> Result<Note, String> {
-    // Get the note manager
-    let note_manager = {
-        let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
-        
-        match note_manager_lock.as_ref() {
-            Some(nm) => nm.clone(),
-            None => return Err("Note manager not initialized".into()),
-        }
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_custom_stop_words(words)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
     };
 
-    // Get the original note to remove from index and to get the old title
-    let original_note = note_manager.get_note(&id).map_err(|e| e.to_string())?;
-    let old_title = original_note.title.clone();
+    refresh_note_manager(&state, &config).await?;
 
-    // Rename the note (synchronous operation)
-    let updated_note = note_manager
-        .rename_note(&id, &new_name)
+    Ok(config)
+}
+
+/// Sets the maximum folder depth scanned for notes
+///
+/// The search index is rebuilt so that it matches the listed notes.
+///
+/// # Parameters
+/// * `depth` - Maximum depth below the notes directory
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_max_walk_depth(
+    app_handle: AppHandle,
+    depth: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_max_walk_depth(depth)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    refresh_note_manager(&state, &config).await?;
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(config)
+}
+
+/// Gets the additional file extensions listed as plain text notes
+///
+/// # Returns
+/// Extensions without the leading dot
+#[tauri::command]
+async fn get_custom_extensions(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+    Ok(config_manager.get_config().custom_extensions)
+}
+
+/// Sets how IDs are assigned to new notes
+///
+/// Markdown notes with a frontmatter UUID change their ID with the scheme, so
+/// their pins, editor states, recently viewed entries and history are moved to
+/// the new IDs and the search index is rebuilt.
+///
+/// # Parameters
+/// * `scheme` - ID scheme to use
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_id_scheme(
+    app_handle: AppHandle,
+    scheme: IdScheme,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    // IDs have to be mapped while the old scheme is still in use
+    let new_ids = match state.note_manager.read().await.as_ref() {
+        Some(note_manager) => note_manager.id_scheme_changes(scheme),
+        None => HashMap::new(),
+    };
+
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_id_scheme(scheme)
+            .map_err(|e| e.to_string())?;
+        config_manager
+            .replace_note_ids(&new_ids)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    refresh_note_manager(&state, &config).await?;
+
+    if let Some(note_manager) = state.note_manager.read().await.as_ref() {
+        note_manager
+            .migrate_history(&new_ids)
+            .map_err(|e| e.to_string())?;
+    }
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(config)
+}
+
+/// Sets how many days trashed notes are kept
+///
+/// # Parameters
+/// * `days` - Retention window in days
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_trash_retention_days(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_trash_retention_days(days)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Starts, restarts or stops the external change watcher to match the configuration
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle used to emit events
+/// * `state` - Application state
+/// * `config` - Current application configuration
+///
+/// # Returns
+/// Result indicating success or failure
+fn update_watcher(
+    app_handle: &AppHandle,
+    state: &State<'_, AppState>,
+    config: &AppConfig,
+) -> Result<(), String> {
+    let mut watcher_lock = state.watcher_service.lock().map_err(|e| e.to_string())?;
+
+    let notes_dir = config
+        .notes_dir
+        .as_deref()
+        .filter(|_| config.watch_external_changes);
+
+    let Some(notes_dir) = notes_dir else {
+        // Dropping the watcher stops it
+        *watcher_lock = None;
+        return Ok(());
+    };
+
+    // Keep the running watcher if it already watches this directory
+    if watcher_lock
+        .as_ref()
+        .map_or(false, |watcher| watcher.notes_dir() == notes_dir)
+    {
+        return Ok(());
+    }
+
+    *watcher_lock = None;
+    let watcher = WatcherService::start(app_handle.clone(), notes_dir, is_external_note_change, invalidate_note_cache)
+        .map_err(|e| e.to_string())?;
+    *watcher_lock = Some(watcher);
+
+    Ok(())
+}
+
+/// Checks whether a changed path is a listed note file that was changed outside the app
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle holding the application state
+/// * `path` - Absolute path of the changed file
+///
+/// # Returns
+/// False for files that are not notes and for the app's own recent writes
+fn is_external_note_change(app_handle: &AppHandle, path: &Path) -> bool {
+    let state = app_handle.state::<AppState>();
+
+    // Called on the watcher thread, outside the async runtime
+    let note_manager = state.note_manager.blocking_read();
+    let Some(note_manager) = note_manager.as_ref() else {
+        return false;
+    };
+
+    note_manager.is_listed_note_file(path) && !note_manager.is_own_write(path)
+}
+
+/// Clears the note summary cache after note files changed outside the app
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle holding the application state
+fn invalidate_note_cache(app_handle: &AppHandle) {
+    let state = app_handle.state::<AppState>();
+
+    // Called on the watcher thread, outside the async runtime
+    if let Some(note_manager) = state.note_manager.blocking_read().as_ref() {
+        note_manager.invalidate_cache();
+    }
+}
+
+/// Sets whether to watch the notes directory for changes made outside the app
+///
+/// When enabled, a `note-changed` event is emitted whenever a note file is
+/// created, modified or deleted.
+///
+/// # Parameters
+/// * `enabled` - Whether to watch for external changes
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_watch_external_changes(
+    app_handle: AppHandle,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_watch_external_changes(enabled)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    // Summaries are only cached while the watcher invalidates them
+    refresh_note_manager(&state, &config).await?;
+    update_watcher(&app_handle, &state, &config)?;
+
+    Ok(config)
+}
+
+/// Sets the maximum number of history snapshots kept per note
+///
+/// # Parameters
+/// * `max_versions` - Maximum number of snapshots
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_max_history_versions(
+    max_versions: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_max_history_versions(max_versions)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    refresh_note_manager(&state, &config).await?;
+
+    Ok(config)
+}
+
+/// Sets the heap size of the search index writer
+///
+/// The search service is re-created so the new size applies immediately.
+///
+/// # Parameters
+/// * `mb` - Heap size in megabytes (at least 15)
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_search_writer_memory(
+    app_handle: AppHandle,
+    mb: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_search_writer_memory(mb)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    let search_service = SearchService::new(&app_dir, &config).map_err(|e| e.to_string())?;
+    *state.search_service.lock().map_err(|e| e.to_string())? = search_service;
+
+    Ok(config)
+}
+
+/// Sets the maximum number of hits fetched for a paginated search
+///
+/// # Parameters
+/// * `max_results` - Maximum of `page * page_size` before results are cut off
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_max_paginated_results(
+    max_results: usize,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_max_paginated_results(max_results)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    state
+        .search_service
+        .lock()
+        .map_err(|e| e.to_string())?
+        .set_max_paginated_results(config.max_paginated_results);
+
+    Ok(config)
+}
+
+/// Sets the language used to tokenize and stem search terms
+///
+/// The search index is recreated with the new language and rebuilt.
+///
+/// # Parameters
+/// * `language` - ISO 639-1 language code, e.g. `de`
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_search_language(
+    app_handle: AppHandle,
+    language: String,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+        config_manager
+            .set_search_language(&language)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
         .map_err(|e| e.to_string())?;
+
+    let search_service = SearchService::new(&app_dir, &config).map_err(|e| e.to_string())?;
+    *state.search_service.lock().map_err(|e| e.to_string())? = search_service;
+
+    if config.notes_dir.is_some() {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(config)
+}
+
+/// Runs pre-flight checks on a directory before it is selected as the notes directory
+///
+/// # Parameters
+/// * `path` - Path of the directory to check
+///
+/// # Returns
+/// The results of the checks
+#[tauri::command]
+async fn validate_notes_dir(path: String) -> Result<DirValidation, String> {
+    let folder = config::expand_path(&PathBuf::from(path));
+
+    notes::validate_notes_dir(&folder).map_err(|e| e.to_string())
+}
+
+/// Selects a folder for storing notes
+///
+/// # Parameters
+/// * `path` - Path to the notes directory
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn select_folder(
+    app_handle: AppHandle,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let folder = config::expand_path(&PathBuf::from(path));
+
+    // Validate folder
+    let validation = notes::validate_notes_dir(&folder).map_err(|e| e.to_string())?;
+    if !validation.is_dir {
+        return Err("Invalid directory path".into());
+    }
+    if !validation.can_read || !validation.can_write {
+        return Err("Directory must be readable and writable".into());
+    }
+
+    // Initialize note manager
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+    let note_manager = create_note_manager(folder.clone(), &config);
     
-    // Update backlinks synchronously
-    if let Err(e) = update_backlinks(&note_manager, &old_title, &updated_note.title) {
-        eprintln!("Error updating backlinks: {}", e);
-        // We don't return an error here because the note rename was successful
-        // The backlinks update is a secondary operation
+    // Get all notes
+    let note_summaries = note_manager.list_notes(None).map_err(|e| e.to_string())?;
+    let mut notes = Vec::new();
+
+    // Load full notes
+    for summary in note_summaries {
+        let note = note_manager
+            .get_note(&summary.id)
+            .map_err(note_error_to_string)?;
+        notes.push(note);
     }
+    
+    // Update config
+    let config = {
+        let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+        config_manager
+            .set_notes_dir(folder)
+            .map_err(|e| e.to_string())?;
+
+        config_manager.get_config()
+    };
+    
+    // Update note manager
+    *state.note_manager.write().await = Some(note_manager);
+
+    // Rebuild search index with all notes
+    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+    // Rebuild index
+    search_service
+        .rebuild_index(&notes, |progress| emit_index_progress(&app_handle, progress))
+        .map_err(|e| e.to_string())?;
+
+    // Watch the new folder for external changes if enabled
+    update_watcher(&app_handle, &state, &config)?;
+
+    Ok(config)
+}
+
+/// Lists all notes in the configured directory
+///
+/// # Parameters
+/// * `sort` - Optional sort options applied left to right, later ones breaking
+///   ties; the configured default sort is used if omitted
+/// * `pin_first` - Whether to place pinned notes before all other notes (optional)
+///
+/// # Returns
+/// A list of note summaries
+#[tauri::command]
+async fn list_notes(
+    sort: Option<Vec<notes::SortOption>>,
+    pin_first: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let sort = sort.unwrap_or_else(|| vec![config.default_sort.clone()]);
+    let mut notes = note_manager.list_notes(Some(sort)).map_err(|e| e.to_string())?;
+
+    if pin_first.unwrap_or(false) {
+        // Stable sort keeps the requested order within pinned and unpinned notes
+        notes.sort_by_key(|note| !config.pinned_notes.contains(&note.id));
+    }
+
+    Ok(notes)
+}
+
+/// Lists all notes along with the note files that could not be loaded
+///
+/// # Parameters
+/// * `sort` - Optional sort options applied left to right, later ones breaking
+///   ties; the configured default sort is used if omitted
+///
+/// # Returns
+/// The loaded note summaries and an error for every unreadable note file
+#[tauri::command]
+async fn list_notes_with_diagnostics(
+    sort: Option<Vec<notes::SortOption>>,
+    state: State<'_, AppState>,
+) -> Result<NoteListDiagnostics, String> {
+    let default_sort = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config()
+        .default_sort;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let sort = sort.unwrap_or_else(|| vec![default_sort]);
+    let (notes, errors) = note_manager.list_notes_with_errors(Some(sort));
+
+    let errors = errors
+        .into_iter()
+        .map(|(path, error)| NoteLoadError {
+            path: path.to_string_lossy().to_string(),
+            error: format!("{:#}", error),
+        })
+        .collect();
+
+    Ok(NoteListDiagnostics { notes, errors })
+}
+
+/// Lists all notes with a preview of their content
+///
+/// # Parameters
+/// * `sort` - Optional sort options applied left to right, later ones breaking
+///   ties; the configured default sort is used if omitted
+///
+/// # Returns
+/// A list of note summaries with previews
+#[tauri::command]
+async fn list_notes_with_preview(
+    sort: Option<Vec<notes::SortOption>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let sort = sort.unwrap_or_else(|| vec![config.default_sort.clone()]);
+    note_manager
+        .list_notes_with_preview(Some(sort), config.preview_length)
+        .map_err(|e| e.to_string())
+}
+
+/// Remembers the editor state of a note
+///
+/// # Parameters
+/// * `id` - ID of the note
+/// * `ui_state` - Cursor position and scroll offset to restore later
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn set_note_ui_state(
+    id: String,
+    ui_state: NoteUiState,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_note_ui_state(&id, ui_state)
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the remembered editor state of a note
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// The editor state, or None if the note was never opened
+#[tauri::command]
+async fn get_note_ui_state(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<NoteUiState>, String> {
+    let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_note_ui_state(&id))
+}
+
+/// Lists one page of notes
+///
+/// # Parameters
+/// * `page` - Page to return (1-indexed)
+/// * `page_size` - Maximum number of notes per page
+/// * `sort` - Optional sort options applied left to right, later ones breaking
+///   ties; the configured default sort is used if omitted
+///
+/// # Returns
+/// The notes on the page and the page position
+#[tauri::command]
+async fn list_notes_paginated(
+    page: usize,
+    page_size: usize,
+    sort: Option<Vec<notes::SortOption>>,
+    state: State<'_, AppState>,
+) -> Result<PaginatedNotes, String> {
+    let default_sort = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config()
+        .default_sort;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let sort = sort.unwrap_or_else(|| vec![default_sort]);
+    let (notes, page_info) = note_manager
+        .list_notes_paginated(page, page_size, Some(sort))
+        .map_err(|e| e.to_string())?;
+
+    Ok(PaginatedNotes { notes, page_info })
+}
+
+/// Pins a note
+///
+/// # Parameters
+/// * `id` - ID of the note to pin
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn pin_note(id: String, state: State<'_, AppState>) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager.pin_note(&id).map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Unpins a note
+///
+/// # Parameters
+/// * `id` - ID of the note to unpin
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn unpin_note(id: String, state: State<'_, AppState>) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager.unpin_note(&id).map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Records that a note was viewed
+///
+/// # Parameters
+/// * `id` - ID of the viewed note
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_note_viewed(id: String, state: State<'_, AppState>) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager.record_note_viewed(&id).map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Lists recently viewed notes
+///
+/// Viewed notes that no longer exist are skipped.
+///
+/// # Parameters
+/// * `limit` - Maximum number of notes to return (optional)
+///
+/// # Returns
+/// Summaries of the recently viewed notes, newest first
+#[tauri::command]
+async fn get_recently_viewed_notes(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let recently_viewed = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config()
+        .recently_viewed;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let mut notes_by_id: HashMap<String, NoteSummary> = note_manager
+        .list_notes(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|note| (note.id.clone(), note))
+        .collect();
+
+    Ok(recently_viewed
+        .iter()
+        .filter_map(|(id, _)| notes_by_id.remove(id))
+        .take(limit.unwrap_or(config::MAX_RECENTLY_VIEWED))
+        .collect())
+}
+
+/// Lists all pinned notes
+///
+/// Pinned notes that no longer exist are skipped.
+///
+/// # Returns
+/// Summaries of the pinned notes, in the order they were pinned
+#[tauri::command]
+async fn list_pinned_notes(state: State<'_, AppState>) -> Result<Vec<NoteSummary>, String> {
+    let pinned_notes = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config()
+        .pinned_notes;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let mut notes_by_id: HashMap<String, NoteSummary> = note_manager
+        .list_notes(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|note| (note.id.clone(), note))
+        .collect();
+
+    Ok(pinned_notes
+        .iter()
+        .filter_map(|id| notes_by_id.remove(id))
+        .collect())
+}
+
+/// Gets a note by ID
+///
+/// # Parameters
+/// * `id` - ID of the note to retrieve
+///
+/// # Returns
+/// The note if found
+#[tauri::command]
+async fn get_note(id: String, state: State<'_, AppState>) -> Result<Note, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_note(&id).map_err(note_error_to_string)
+}
+
+/// Gets the metadata of a note without loading its full content
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// The summary of the note
+#[tauri::command]
+async fn get_note_metadata(id: String, state: State<'_, AppState>) -> Result<NoteSummary, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_note_metadata(&id).map_err(note_error_to_string)
+}
+
+/// Checks whether a note exists without reading it
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// True if the note exists
+#[tauri::command]
+async fn note_exists(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    Ok(note_manager.check_note_exists(&id))
+}
+
+/// Gets multiple notes by ID in a single call
+///
+/// Notes that cannot be loaded are skipped.
+///
+/// # Parameters
+/// * `ids` - IDs of the notes to retrieve
+///
+/// # Returns
+/// The loaded notes, in the order of `ids`
+#[tauri::command]
+async fn batch_get_notes(ids: Vec<String>, state: State<'_, AppState>) -> Result<Vec<Note>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    note_manager.batch_get_notes(&ids).map_err(|e| e.to_string())
+}
+
+/// Updates the content of a note
+///
+/// # Parameters
+/// * `id` - ID of the note to update
+/// * `content` - New content for the note
+///
+/// # Returns
+/// The updated note
+#[tauri::command]
+async fn update_note_content(
+    app_handle: AppHandle,
+    id: String,
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    // Update the note content while holding the write lock
+    let updated_note = {
+        let note_manager_lock = state.note_manager.write().await;
+        
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .update_note_content(&id, &content)
+            .map_err(note_error_to_string)?
+    };
+
+    // Check if we should update the search index
+    let should_update_index = {
+        let config = state
+            .config_manager
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get_config();
+        
+        (config.auto_update_search_index, config.auto_update_mode)
+    };
+
+    if should_update_index.0 {
+        // Update the search index with the new note content
+        match should_update_index.1 {
+            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
+                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+                search_service
+                    .index_note(&updated_note)
+                    .map_err(|e| e.to_string())?;
+                info!(
+                    "Incrementally updated search index for note: {}",
+                    updated_note.id
+                );
+            },
+            config::AutoUpdateMode::Periodic => {
+                // For periodic mode, we don't update the index immediately
+                // It will be updated during the next scheduled rebuild
+            }
+        }
+
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(updated_note)
+}
+
+/// Saves the current content of a note as a history snapshot
+///
+/// # Parameters
+/// * `id` - ID of the note to snapshot
+///
+/// # Returns
+/// The saved version
+#[tauri::command]
+async fn save_note_version(id: String, state: State<'_, AppState>) -> Result<NoteVersion, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.save_version(&id).map_err(note_error_to_string)
+}
+
+/// Gets the saved versions of a note
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// Saved versions, newest first
+#[tauri::command]
+async fn get_note_history(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteVersion>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_note_history(&id).map_err(note_error_to_string)
+}
+
+/// Restores a note to a previously saved version
+///
+/// # Parameters
+/// * `id` - ID of the note to restore
+/// * `timestamp` - Timestamp of the version to restore
+///
+/// # Returns
+/// The restored note
+#[tauri::command]
+async fn restore_note_version(
+    app_handle: AppHandle,
+    id: String,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let restored_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .restore_note_version(&id, timestamp)
+            .map_err(note_error_to_string)?
+    };
+
+    if apply_index_updates(&state, &[&restored_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(restored_note)
+}
+
+/// Adds and removes inline tags on multiple notes
+///
+/// # Parameters
+/// * `ids` - IDs of the notes to update
+/// * `add` - Tags to add
+/// * `remove` - Tags to remove
+///
+/// # Returns
+/// The number of changed notes and the IDs of notes that failed
+#[tauri::command]
+async fn batch_update_tags(
+    app_handle: AppHandle,
+    ids: Vec<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<BatchUpdateResult, String> {
+    let (result, updated_notes) = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let add: Vec<&str> = add.iter().map(String::as_str).collect();
+        let remove: Vec<&str> = remove.iter().map(String::as_str).collect();
+
+        let result = note_manager
+            .batch_update_tags(&ids, &add, &remove)
+            .map_err(|e| e.to_string())?;
+
+        // Unchanged notes are re-indexed too, which is harmless
+        let succeeded: Vec<&str> = ids
+            .into_iter()
+            .filter(|id| !result.failed.iter().any(|failed| failed.as_str() == *id))
+            .collect();
+        let updated_notes = note_manager
+            .batch_get_notes(&succeeded)
+            .map_err(|e| e.to_string())?;
+
+        (result, updated_notes)
+    };
+
+    let updated: Vec<&Note> = updated_notes.iter().collect();
+    if apply_index_updates(&state, &updated, &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(result)
+}
+
+/// Links two notes to each other with wikilinks
+///
+/// Notes that already link to the other note are left unchanged.
+///
+/// # Parameters
+/// * `source_id` - ID of the note to link from
+/// * `target_id` - ID of the note to link to
+/// * `link_text` - Alias shown for the link in the source note (optional)
+///
+/// # Returns
+/// The source and target notes after linking
+#[tauri::command]
+async fn link_notes(
+    app_handle: AppHandle,
+    source_id: String,
+    target_id: String,
+    link_text: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(Note, Note), String> {
+    let (source, target) = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .link_notes(&source_id, &target_id, link_text.as_deref())
+            .map_err(note_error_to_string)?;
+
+        let source = note_manager.get_note(&source_id).map_err(note_error_to_string)?;
+        let target = note_manager.get_note(&target_id).map_err(note_error_to_string)?;
+
+        (source, target)
+    };
+
+    if apply_index_updates(&state, &[&source, &target], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok((source, target))
+}
+
+/// Removes the wikilinks between two notes
+///
+/// # Parameters
+/// * `source_id` - ID of the note to remove the links from
+/// * `target_id` - ID of the note the links point to
+/// * `bidirectional` - Whether to also remove links from the target note to the source note
+///
+/// # Returns
+/// The source and target notes after unlinking
+#[tauri::command]
+async fn unlink_notes(
+    app_handle: AppHandle,
+    source_id: String,
+    target_id: String,
+    bidirectional: bool,
+    state: State<'_, AppState>,
+) -> Result<(Note, Note), String> {
+    let (source, target) = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .unlink_notes(&source_id, &target_id, bidirectional)
+            .map_err(note_error_to_string)?;
+
+        let source = note_manager.get_note(&source_id).map_err(note_error_to_string)?;
+        let target = note_manager.get_note(&target_id).map_err(note_error_to_string)?;
+
+        (source, target)
+    };
+
+    if apply_index_updates(&state, &[&source, &target], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok((source, target))
+}
+
+/// Splits a note into two notes at a line
+///
+/// # Parameters
+/// * `id` - ID of the note to split
+/// * `split_at_line` - Zero-based index of the first line of the new note
+/// * `new_title` - Title of the new note (a Markdown heading at the split line takes precedence)
+///
+/// # Returns
+/// The shortened original note and the new note
+#[tauri::command]
+async fn split_note(
+    app_handle: AppHandle,
+    id: String,
+    split_at_line: usize,
+    new_title: String,
+    state: State<'_, AppState>,
+) -> Result<(Note, Note), String> {
+    let (original_note, new_note) = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .split_note(&id, split_at_line, &new_title)
+            .map_err(note_error_to_string)?
+    };
+
+    if apply_index_updates(&state, &[&original_note, &new_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok((original_note, new_note))
+}
+
+/// Merges one note into another
+///
+/// Links to the source note are updated to point to the target note.
+///
+/// # Parameters
+/// * `target_id` - ID of the note to merge into
+/// * `source_id` - ID of the note to append and remove
+/// * `separator` - Text placed between the two notes (optional)
+///
+/// # Returns
+/// The merged target note
+#[tauri::command]
+async fn merge_notes(
+    app_handle: AppHandle,
+    target_id: String,
+    source_id: String,
+    separator: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let merged_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .merge_notes(&target_id, &source_id, separator.as_deref())
+            .map_err(note_error_to_string)?
+    };
+
+    state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove_note_references(&source_id)
+        .map_err(|e| e.to_string())?;
+
+    if apply_index_updates(&state, &[&merged_note], &[source_id.as_str()])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(merged_note)
+}
+
+/// Converts a note between Markdown and plain text
+///
+/// # Parameters
+/// * `id` - ID of the note to convert
+/// * `new_type` - Type to convert the note to
+///
+/// # Returns
+/// The converted note with its new ID
+#[tauri::command]
+async fn convert_note_type(
+    app_handle: AppHandle,
+    id: String,
+    new_type: notes::NoteType,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let converted_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .convert_note_type(&id, new_type)
+            .map_err(note_error_to_string)?
+    };
+
+    replace_note_ids(&state, [(id.as_str(), converted_note.id.as_str())])?;
+
+    if apply_index_updates(&state, &[&converted_note], &[id.as_str()])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(converted_note)
+}
+
+/// Renames a note file
+///
+/// # Parameters
+/// * `id` - ID of the note to rename
+/// * `new_name` - New name for the note file (without extension)
+/// * `update_heading` - Whether to also update the title heading of Markdown notes (default true)
+///
+/// # Returns
+/// The updated note with new ID
+#[tauri::command]
+async fn rename_note(
+    app_handle: AppHandle,
+    id: String,
+    new_name: String,
+    update_heading: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let (original_note, updated_note) = {
+        let note_manager_lock = state.note_manager.write().await;
+        
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        // Get the original note to remove from index and to get the old title
+        let original_note = note_manager.get_note(&id).map_err(note_error_to_string)?;
+        let old_title = original_note.title.clone();
+
+        // Rename the note (synchronous operation)
+        let updated_note = note_manager
+            .rename_note(&id, &new_name, update_heading.unwrap_or(true))
+            .map_err(note_error_to_string)?;
+        
+        // Update backlinks synchronously
+        if let Err(e) = note_manager.update_backlinks(&old_title, &updated_note.title) {
+            eprintln!("Error updating backlinks: {}", e);
+            // We don't return an error here because the note rename was successful
+            // The backlinks update is a secondary operation
+        }
+
+        (original_note, updated_note)
+    };
+
+    replace_note_ids(&state, [(original_note.id.as_str(), updated_note.id.as_str())])?;
+
+    // Check if we should update the search index
+    let should_update_index = {
+        let config = state
+            .config_manager
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get_config();
+        
+        (config.auto_update_search_index, config.auto_update_mode)
+    };
+
+    if should_update_index.0 {
+        // Update the search index
+        match should_update_index.1 {
+            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
+                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+                // Remove the old note from the index
+                search_service
+                    .remove_note(&original_note.id)
+                    .map_err(|e| e.to_string())?;
+
+                // Add the updated note to the index
+                search_service
+                    .index_note(&updated_note)
+                    .map_err(|e| e.to_string())?;
+
+                info!(
+                    "Incrementally updated search index for renamed note: {} -> {}",
+                    original_note.id, updated_note.id
+                );
+            },
+            config::AutoUpdateMode::Periodic => {
+                // For periodic mode, we don't update the index immediately
+                // It will be updated during the next scheduled rebuild
+            }
+        }
+
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(updated_note)
+}
+
+/// Renames all notes whose file name matches a regular expression
+///
+/// A failed rename does not stop the others; its error is reported in its
+/// preview. The search index is rebuilt after the renames, since links in
+/// other notes change as well.
+///
+/// # Parameters
+/// * `find` - Regular expression matched against file names without extension
+/// * `replace` - Replacement, may reference capture groups as `$1`
+/// * `dry_run` - Whether to only preview the renames
+///
+/// # Returns
+/// The planned renames; colliding ones are flagged and not executed, failed ones carry their error
+#[tauri::command]
+async fn bulk_rename_by_pattern(
+    app_handle: AppHandle,
+    find: String,
+    replace: String,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<notes::BulkRenamePreview>, String> {
+    let previews = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .bulk_rename_by_pattern(&find, &replace, dry_run)
+            .map_err(|e| e.to_string())?
+    };
+
+    replace_note_ids(
+        &state,
+        previews
+            .iter()
+            .filter_map(|preview| Some((preview.id.as_str(), preview.new_id.as_deref()?))),
+    )?;
+
+    if !dry_run && previews.iter().any(notes::BulkRenamePreview::renamed) {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(previews)
+}
+
+/// Moves a note and updates links to it if its title changed
+///
+/// # Parameters
+/// * `note_manager` - The note manager instance
+/// * `id` - ID of the note to move
+/// * `new_path` - New relative path for the note (including filename)
+///
+/// # Returns
+/// The note before and after the move
+fn move_note_with_backlinks(
+    note_manager: &NoteManager,
+    id: &str,
+    new_path: &str,
+) -> Result<(Note, Note), String> {
+    // Get the original note to remove from index and to get the old title
+    let original_note = note_manager.get_note(id).map_err(note_error_to_string)?;
+
+    // Move the note
+    let updated_note = note_manager
+        .move_note(id, new_path)
+        .map_err(note_error_to_string)?;
+
+    // Plain text titles come from the file name, so a move can rename the note
+    if original_note.title != updated_note.title {
+        if let Err(e) = note_manager.update_backlinks(&original_note.title, &updated_note.title) {
+            eprintln!("Error updating backlinks: {}", e);
+            // The move itself succeeded; the backlinks update is a secondary operation
+        }
+    }
+
+    Ok((original_note, updated_note))
+}
+
+/// Moves a note to a different path
+///
+/// # Parameters
+/// * `id` - ID of the note to move
+/// * `new_path` - New relative path for the note (including filename)
+///
+/// # Returns
+/// The updated note with new ID
+#[tauri::command]
+async fn move_note(
+    app_handle: AppHandle,
+    id: String,
+    new_path: String,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let (original_note, updated_note) = {
+        let note_manager_lock = state.note_manager.write().await;
+        
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        move_note_with_backlinks(note_manager, &id, &new_path)?
+    };
+
+    replace_note_ids(&state, [(original_note.id.as_str(), updated_note.id.as_str())])?;
+
+    // Check if we should update the search index
+    let should_update_index = {
+        let config = state
+            .config_manager
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get_config();
+        
+        (config.auto_update_search_index, config.auto_update_mode)
+    };
+
+    if should_update_index.0 {
+        // Update the search index
+        match should_update_index.1 {
+            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
+                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+                // Remove the old note from the index
+                search_service
+                    .remove_note(&original_note.id)
+                    .map_err(|e| e.to_string())?;
+
+                // Add the updated note to the index
+                search_service
+                    .index_note(&updated_note)
+                    .map_err(|e| e.to_string())?;
+
+                info!(
+                    "Incrementally updated search index for moved note: {} -> {}",
+                    original_note.id, updated_note.id
+                );
+            },
+            config::AutoUpdateMode::Periodic => {
+                // For periodic mode, we don't update the index immediately
+                // It will be updated during the next scheduled rebuild
+            }
+        }
+
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(updated_note)
+}
+
+/// Copies a note into another notes directory
+///
+/// The primary notes directory and its search index are not changed.
+///
+/// # Parameters
+/// * `id` - ID of the note to copy
+/// * `target_vault_path` - Path of the destination notes directory
+///
+/// # Returns
+/// The created note in the destination
+#[tauri::command]
+async fn copy_note_to_vault(
+    id: String,
+    target_vault_path: String,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let target = config::expand_path(&PathBuf::from(target_vault_path));
+
+    let validation = notes::validate_notes_dir(&target).map_err(|e| e.to_string())?;
+    if !validation.is_dir {
+        return Err("Invalid directory path".into());
+    }
+    if !validation.can_write {
+        return Err("Directory must be writable".into());
+    }
+
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+    let target_manager = create_note_manager(target, &config);
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .copy_note_to_folder(&id, &target_manager)
+        .map_err(note_error_to_string)
+}
+
+/// Moves several notes into a folder, keeping their file names
+///
+/// # Parameters
+/// * `ids` - IDs of the notes to move
+/// * `destination_folder` - Folder relative to the notes directory
+///
+/// # Returns
+/// The moved notes and the notes that could not be moved with the reason
+#[tauri::command]
+async fn move_notes_batch(
+    app_handle: AppHandle,
+    ids: Vec<String>,
+    destination_folder: String,
+    state: State<'_, AppState>,
+) -> Result<notes::BatchMoveResult, String> {
+    let result = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        note_manager
+            .move_notes_batch(&ids, &destination_folder)
+            .map_err(|e| e.to_string())?
+    };
+
+    let moved_ids: Vec<&str> = ids
+        .iter()
+        .map(String::as_str)
+        .filter(|id| !result.failed.iter().any(|(failed, _)| failed.as_str() == *id))
+        .collect();
+    let moved: Vec<&Note> = result.moved.iter().collect();
+
+    // Moved notes are listed in the order of their IDs
+    replace_note_ids(
+        &state,
+        moved_ids.iter().copied().zip(moved.iter().map(|note| note.id.as_str())),
+    )?;
+
+    if apply_index_updates(&state, &moved, &moved_ids)? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(result)
+}
+
+/// Suggests a title for a new note that does not collide with an existing file
+///
+/// # Parameters
+/// * `title` - Desired title of the note
+/// * `file_type` - Type of the note
+///
+/// # Returns
+/// The title, with a `_2`, `_3`, ... suffix if the name is taken
+#[tauri::command]
+async fn suggest_note_filename(
+    title: String,
+    file_type: notes::NoteType,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    Ok(note_manager.suggest_note_filename(&title, &file_type))
+}
+
+/// Creates a new note
+///
+/// # Parameters
+/// * `title` - Title of the note
+/// * `content` - Initial content of the note
+/// * `file_type` - Type of note (Markdown or PlainText)
+/// * `pattern` - Optional naming pattern (e.g., "{number}-{title}")
+///
+/// # Returns
+/// The newly created note
+#[tauri::command]
+async fn create_note(
+    app_handle: AppHandle,
+    title: String,
+    content: String,
+    file_type: notes::NoteType,
+    pattern: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let new_note = {
+        let note_manager_lock = state.note_manager.write().await;
+        
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        let pattern_ref = pattern.as_deref();
+        note_manager
+            .create_note(&title, &content, file_type, pattern_ref)
+            .map_err(|e| e.to_string())?
+    };
+
+    // Check if we should update the search index
+    let should_update_index = {
+        let config = state
+            .config_manager
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get_config();
+        
+        (config.auto_update_search_index, config.auto_update_mode)
+    };
+
+    if should_update_index.0 {
+        // Update the search index
+        match should_update_index.1 {
+            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
+                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+                search_service
+                    .index_note(&new_note)
+                    .map_err(|e| e.to_string())?;
+                info!(
+                    "Incrementally updated search index for new note: {}",
+                    new_note.id
+                );
+            },
+            config::AutoUpdateMode::Periodic => {
+                // For periodic mode, we don't update the index immediately
+                // It will be updated during the next scheduled rebuild
+            }
+        }
+
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(new_note)
+}
+
+/// Adds or replaces a user-defined note template
+///
+/// # Parameters
+/// * `name` - Name of the template
+/// * `content` - Template content with `{{placeholder}}` variables
+///
+/// # Returns
+/// All available templates
+#[tauri::command]
+async fn add_template(
+    name: String,
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteTemplate>, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .add_template(&name, &content)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.list_templates())
+}
+
+/// Lists all available note templates
+///
+/// # Returns
+/// Built-in and user-defined templates sorted by name
+#[tauri::command]
+async fn list_templates(state: State<'_, AppState>) -> Result<Vec<NoteTemplate>, String> {
+    let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+    Ok(config_manager.list_templates())
+}
+
+/// Deletes a user-defined note template
+///
+/// # Parameters
+/// * `name` - Name of the template
+///
+/// # Returns
+/// All remaining templates
+#[tauri::command]
+async fn delete_template(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteTemplate>, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .delete_template(&name)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.list_templates())
+}
+
+/// Creates a new note from a template
+///
+/// # Parameters
+/// * `template_name` - Name of the template to use
+/// * `title` - Title of the note
+/// * `variables` - Values for custom `{{placeholder}}` variables in the template
+///
+/// # Returns
+/// The newly created note
+#[tauri::command]
+async fn create_note_from_template(
+    app_handle: AppHandle,
+    template_name: String,
+    title: String,
+    variables: HashMap<String, String>,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let (template, config) = {
+        let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+        (config_manager.get_template(&template_name), config_manager.get_config())
+    };
+
+    let Some(template) = template else {
+        return Err(format!("Template '{}' not found", template_name));
+    };
+
+    let content = notes::render_template(&template, &title, &variables);
+    let file_type = config.default_note_type.unwrap_or(notes::NoteType::Markdown);
+
+    let new_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .create_note(&title, &content, file_type, config.note_naming_pattern.as_deref())
+            .map_err(|e| e.to_string())?
+    };
+
+    if apply_index_updates(&state, &[&new_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(new_note)
+}
+
+/// Gets today's daily note, creating it if it does not exist yet
+///
+/// The note is looked up by its path (`{daily_notes_folder}/{YYYY-MM-DD}.md`)
+/// rather than by title, so a note with the same title elsewhere in the vault
+/// is never mistaken for today's daily note.
+///
+/// # Returns
+/// Today's daily note
+#[tauri::command]
+async fn create_or_get_daily_note(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let (template, config) = {
+        let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+        let config = config_manager.get_config();
+        let template_name = config.daily_note_template.as_deref().unwrap_or("daily");
+        (config_manager.get_template(template_name), config)
+    };
+
+    let Some(template) = template else {
+        return Err("Daily note template not found".into());
+    };
+
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let relative_path = match config.daily_notes_folder.as_deref() {
+        Some(folder) if !folder.trim().is_empty() => format!("{}/{}.md", folder.trim(), date),
+        _ => format!("{}.md", date),
+    };
+
+    let content = notes::render_template(&template, &date, &HashMap::new());
+    let daily_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .get_or_create_note_at(&relative_path, &content)
+            .map_err(|e| e.to_string())?
+    };
+
+    if apply_index_updates(&state, &[&daily_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(daily_note)
+}
+
+/// Sets the folder where daily notes are created
+///
+/// # Parameters
+/// * `folder` - Folder relative to the notes directory, or None for the notes directory itself
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_daily_notes_folder(
+    folder: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_daily_notes_folder(folder)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Sets the template used for new daily notes
+///
+/// # Parameters
+/// * `template` - Name of the template, or None for the built-in "daily" template
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_daily_note_template(
+    template: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_daily_note_template(template)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Moves a note to the trash
+///
+/// # Parameters
+/// * `id` - ID of the note to trash
+///
+/// # Returns
+/// Information about the trashed note
+#[tauri::command]
+async fn trash_note(
+    app_handle: AppHandle,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<TrashedNote, String> {
+    let trashed = {
+        let note_manager_lock = state.note_manager.write().await;
+        
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager.trash_note(&id).map_err(note_error_to_string)?
+    };
+
+    // Editor state of deleted notes is no longer needed
+    state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove_note_ui_state(&id)
+        .map_err(|e| e.to_string())?;
+
+    if apply_index_updates(&state, &[], &[id.as_str()])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(trashed)
+}
+
+/// Deletes a note
+///
+/// Notes are never removed immediately; they are moved to the trash and
+/// permanently deleted by `empty_trash` once the retention window has passed.
+///
+/// # Parameters
+/// * `id` - ID of the note to delete
+///
+/// # Returns
+/// Information about the trashed note
+#[tauri::command]
+async fn delete_note(
+    app_handle: AppHandle,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<TrashedNote, String> {
+    trash_note(app_handle, id, state).await
+}
+
+/// Lists all notes in the trash
+///
+/// # Returns
+/// Trashed notes, most recently deleted first
+#[tauri::command]
+async fn list_trashed_notes(state: State<'_, AppState>) -> Result<Vec<TrashedNote>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.list_trashed_notes().map_err(|e| e.to_string())
+}
+
+/// Restores a trashed note to its original location
+///
+/// # Parameters
+/// * `trash_id` - Identifier of the trashed note
+///
+/// # Returns
+/// The restored note
+#[tauri::command]
+async fn restore_trashed_note(
+    app_handle: AppHandle,
+    trash_id: String,
+    state: State<'_, AppState>,
+) -> Result<Note, String> {
+    let restored_note = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .restore_trashed_note(&trash_id)
+            .map_err(|e| e.to_string())?
+    };
+
+    if apply_index_updates(&state, &[&restored_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(restored_note)
+}
+
+/// Permanently removes trashed notes older than the configured retention window
+///
+/// # Returns
+/// The number of notes permanently removed
+#[tauri::command]
+async fn empty_trash(state: State<'_, AppState>) -> Result<usize, String> {
+    let retention_days = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config()
+        .trash_retention_days;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .empty_trash(retention_days)
+        .map_err(|e| e.to_string())
+}
+
+/// Imports notes from an external directory into the notes directory
+///
+/// The search index is rebuilt afterwards so imported notes are searchable.
+///
+/// # Parameters
+/// * `source_dir` - Directory to import notes from
+/// * `overwrite` - Whether to overwrite notes that already exist
+///
+/// # Returns
+/// A report of imported, skipped and failed files
+#[tauri::command]
+async fn import_notes_from_directory(
+    app_handle: AppHandle,
+    source_dir: String,
+    overwrite: bool,
+    state: State<'_, AppState>,
+) -> Result<ImportReport, String> {
+    let report = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .import_notes_from_directory(&PathBuf::from(source_dir), overwrite)
+            .map_err(|e| e.to_string())?
+    };
+
+    info!(
+        "Imported {} notes ({} skipped, {} errors)",
+        report.imported,
+        report.skipped,
+        report.errors.len()
+    );
+
+    if report.imported > 0 {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(report)
+}
+
+/// Imports the Markdown notes of an Obsidian vault into the notes directory
+///
+/// The search index is rebuilt afterwards so imported notes are searchable.
+///
+/// # Parameters
+/// * `source_dir` - Path to the Obsidian vault
+///
+/// # Returns
+/// A report of imported, skipped, conflicting and failed files
+#[tauri::command]
+async fn import_from_obsidian(
+    app_handle: AppHandle,
+    source_dir: String,
+    state: State<'_, AppState>,
+) -> Result<ImportReport, String> {
+    let report = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        import::import_from_obsidian(&PathBuf::from(source_dir), note_manager)
+            .map_err(|e| e.to_string())?
+    };
+
+    info!(
+        "Imported {} notes from Obsidian ({} skipped, {} conflicts, {} errors)",
+        report.imported,
+        report.skipped,
+        report.conflicts.len(),
+        report.errors.len()
+    );
+
+    if report.imported > 0 {
+        rebuild_search_index(app_handle, state).await?;
+    }
+
+    Ok(report)
+}
+
+/// Exports a note to an HTML file
+///
+/// # Parameters
+/// * `id` - ID of the note to export
+/// * `output_path` - Path of the HTML file to write
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn export_note_to_html(
+    id: String,
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let note = {
+        let note_manager_lock = state.note_manager.read().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager.get_note(&id).map_err(note_error_to_string)?
+    };
+
+    export::export_note_to_html(&note, &PathBuf::from(output_path)).map_err(|e| e.to_string())
+}
+
+/// Exports multiple notes to HTML files, one file per note
+///
+/// # Parameters
+/// * `ids` - IDs of the notes to export
+/// * `output_dir` - Directory to write the HTML files to (created if absent)
+///
+/// # Returns
+/// Paths of the written files
+#[tauri::command]
+async fn export_notes_to_html(
+    ids: Vec<String>,
+    output_dir: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let notes = {
+        let note_manager_lock = state.note_manager.read().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        ids.iter()
+            .map(|id| note_manager.get_note(id))
+            .collect::<Result<Vec<_>>>()
+            .map_err(note_error_to_string)?
+    };
+
+    let written = export::export_notes_to_html(&notes, &PathBuf::from(output_dir))
+        .map_err(|e| e.to_string())?;
+
+    Ok(written
+        .into_iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Exports the notes directory as an Obsidian vault
+///
+/// # Parameters
+/// * `output_dir` - Directory to write the Obsidian vault to (created if absent)
+///
+/// # Returns
+/// The number of copied and converted notes and per-note errors
+#[tauri::command]
+async fn export_to_obsidian(
+    output_dir: String,
+    state: State<'_, AppState>,
+) -> Result<ExportReport, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    export::export_to_obsidian(note_manager.notes_dir(), &PathBuf::from(output_dir))
+        .map_err(|e| e.to_string())
+}
+
+/// Searches for notes matching the query
+///
+/// While the search index cannot be rebuilt, notes containing the query are
+/// found by scanning the notes directory instead.
+///
+/// # Parameters
+/// * `query` - The search query
+/// * `limit` - Maximum number of results to return (optional)
+///
+/// # Returns
+/// List of search results
+#[tauri::command]
+async fn search_notes(
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let limit = limit.unwrap_or(100);
+
+    if *state.search_index_failed.lock()? {
+        warn!("Search index unavailable, scanning notes for '{}'", query);
+
+        let note_manager_lock = state.note_manager.read().await;
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err(SearchError::SearchExecutionError("Note manager not initialized".into()));
+        };
+
+        let notes = note_manager
+            .find_notes_by_content_contains(query.trim(), false, limit)
+            .map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
+
+        return Ok(notes
+            .into_iter()
+            .map(|note| SearchResult {
+                note,
+                snippets: Vec::new(),
+                match_ranges: Vec::new(),
+                score: 0.0,
+            })
+            .collect());
+    }
+
+    let search_service = state.search_service.lock()?;
+
+    search_service.search(&query, limit)
+}
+
+/// Searches for notes containing a substring without using the search index
+///
+/// # Parameters
+/// * `query` - Text the notes must contain within a single line
+/// * `case_sensitive` - Whether letter case must match
+/// * `limit` - Maximum number of results to return (default 100)
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn search_notes_fallback(
+    query: String,
+    case_sensitive: bool,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_by_content_contains(&query, case_sensitive, limit.unwrap_or(100))
+        .map_err(|e| e.to_string())
+}
+
+/// Searches a folder for notes containing text without using the search index
+///
+/// # Parameters
+/// * `folder` - Folder relative to the notes directory, including its subfolders
+/// * `query` - Text the notes must contain within a single line, ignoring letter case
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn search_notes_in_path(
+    folder: String,
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .search_notes_in_path(&folder, &query)
+        .map_err(|e| e.to_string())
+}
+
+/// Fields that can be searched with `search_notes_by_field`
+const SEARCHABLE_FIELDS: &[&str] = &["title", "tags", "id", "file_type", "links"];
+
+/// Searches for notes with an exact value in a single indexed field
+///
+/// # Parameters
+/// * `field` - Field to search: "title", "tags", "id", "file_type" or "links"
+/// * `value` - Value to match (a single word for "title" and "tags")
+/// * `limit` - Maximum number of results to return (optional)
+///
+/// # Returns
+/// A list of search results
+#[tauri::command]
+async fn search_notes_by_field(
+    field: String,
+    value: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    if !SEARCHABLE_FIELDS.contains(&field.as_str()) {
+        return Err(SearchError::QueryParseError(format!(
+            "Cannot search by field '{}'; expected one of: {}",
+            field,
+            SEARCHABLE_FIELDS.join(", ")
+        )));
+    }
+
+    let search_service = state.search_service.lock()?;
+
+    search_service.search_by_field(&field, &value, limit.unwrap_or(100))
+}
+
+/// Searches for notes and returns one page of results
+///
+/// # Parameters
+/// * `query` - The search query
+/// * `page` - Page to return (1-indexed)
+/// * `page_size` - Maximum number of results per page
+///
+/// # Returns
+/// The results on the page and the total number of matches
+#[tauri::command]
+async fn search_notes_paginated(
+    query: String,
+    page: usize,
+    page_size: usize,
+    state: State<'_, AppState>,
+) -> Result<PaginatedSearch, SearchError> {
+    let search_service = state.search_service.lock()?;
+
+    search_service.search_paginated(&query, page, page_size)
+}
+
+/// Searches for notes using custom search options
+///
+/// # Parameters
+/// * `query` - The search query
+/// * `options` - Options controlling limits, boosts, fuzzy matching, date filters, the minimum
+///   score, tags to exclude and the folder to search in
+///
+/// # Returns
+/// A list of search results with the byte ranges of matched terms in each note's content;
+/// an empty query only matches notes if `match_all_on_empty` is set
+#[tauri::command]
+async fn advanced_search(
+    query: String,
+    options: SearchOptions,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let search_service = state.search_service.lock()?;
+
+    search_service.search_with_options(&query, &options)
+}
+
+/// Searches for notes within a date range
+///
+/// An empty query returns all notes whose date falls within the range.
+///
+/// # Parameters
+/// * `query` - The search query
+/// * `from_ts` - Start of the range as a Unix timestamp in seconds (inclusive)
+/// * `to_ts` - End of the range as a Unix timestamp in seconds (inclusive)
+/// * `date_field` - Date to filter on, either "created" or "modified"
+/// * `limit` - Maximum number of results to return
+///
+/// # Returns
+/// A list of search results
+#[tauri::command]
+async fn search_notes_in_date_range(
+    query: String,
+    from_ts: i64,
+    to_ts: i64,
+    date_field: String,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let from = chrono::DateTime::<chrono::Utc>::from_timestamp(from_ts, 0).ok_or_else(|| {
+        SearchError::QueryParseError(format!("Invalid start timestamp: {}", from_ts))
+    })?;
+    let to = chrono::DateTime::<chrono::Utc>::from_timestamp(to_ts, 0).ok_or_else(|| {
+        SearchError::QueryParseError(format!("Invalid end timestamp: {}", to_ts))
+    })?;
+    let date_field = date_field.parse::<DateField>()?;
+
+    let options = SearchOptions {
+        limit,
+        date_range: Some((from, to)),
+        date_field,
+        match_all_on_empty: true,
+        ..Default::default()
+    };
+
+    let search_service = state.search_service.lock()?;
+
+    search_service.search_with_options(&query, &options)
+}
+
+/// Gets aggregated statistics for all notes
+///
+/// Alias of `get_vault_stats`, kept for frontends using the older command name.
+///
+/// # Returns
+/// Note, word, character, tag, type, age and size statistics for the vault
+#[tauri::command]
+async fn get_note_stats(state: State<'_, AppState>) -> Result<VaultStats, String> {
+    get_vault_stats(state).await
+}
+
+/// Gets vault-wide statistics for a vault health overview
+///
+/// # Returns
+/// Note, word, character, tag, type, age and size statistics for the vault
+#[tauri::command]
+async fn get_vault_stats(state: State<'_, AppState>) -> Result<VaultStats, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_vault_stats().map_err(|e| e.to_string())
+}
+
+/// Gets usage statistics for every tag
+///
+/// # Parameters
+/// * `min_count` - Leave out tags used by fewer notes (optional)
+/// * `max_count` - Leave out tags used by more notes (optional)
+///
+/// # Returns
+/// Statistics per tag, most used first
+#[tauri::command]
+async fn get_tag_stats(
+    min_count: Option<usize>,
+    max_count: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TagStats>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .get_tag_stats(min_count.unwrap_or(0), max_count.unwrap_or(usize::MAX))
+        .map_err(|e| e.to_string())
+}
+
+/// Searches for notes with specific tags
+///
+/// # Parameters
+/// * `tags` - List of tags to filter by
+/// * `match_all` - If true, notes must have all tags; if false, notes can have any of the tags
+/// * `sort` - Optional sort options applied left to right, later ones breaking ties
+///
+/// # Returns
+/// A list of note summaries
+#[tauri::command]
+async fn filter_notes_by_tags(
+    tags: Vec<String>,
+    match_all: bool,
+    sort: Option<Vec<notes::SortOption>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    // Get all notes
+    let all_notes = note_manager
+        .list_notes(sort)
+        .map_err(|e| e.to_string())?;
+
+    // Filter notes by tags
+    let filtered_notes = if match_all {
+        // Notes must have all specified tags
+        all_notes
+            .into_iter()
+            .filter(|note| tags.iter().all(|tag| note.tags.contains(tag)))
+            .collect()
+    } else {
+        // Notes can have any of the specified tags
+        all_notes
+            .into_iter()
+            .filter(|note| tags.iter().any(|tag| note.tags.contains(tag)))
+            .collect()
+    };
+
+    Ok(filtered_notes)
+}
+
+/// Searches for notes with specific tags using the search index
+///
+/// Faster than `filter_notes_by_tags` for large vaults, but only as fresh as
+/// the index. Tags are compared case-insensitively.
+///
+/// # Parameters
+/// * `tags` - List of tags to filter by
+/// * `match_all` - If true, notes must have all tags; if false, notes can have any of the tags
+/// * `limit` - Maximum number of results to return
+///
+/// # Returns
+/// A list of search results
+#[tauri::command]
+async fn filter_notes_by_tags_fast(
+    tags: Vec<String>,
+    match_all: bool,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let search_service = state.search_service.lock()?;
+    let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+
+    search_service.search_by_tags(&tags, match_all, limit)
+}
+
+/// Finds a note by its title
+///
+/// # Parameters
+/// * `title` - Title of the note to find
+///
+/// # Returns
+/// The note ID if found, None otherwise
+#[tauri::command]
+async fn find_note_by_title(
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_note_by_title(&title)
+        .map_err(|e| e.to_string())
+}
+
+/// Finds all notes with a title
+///
+/// # Parameters
+/// * `title` - Title of the notes to find (case-insensitive)
+///
+/// # Returns
+/// Summaries of all notes with a matching title
+#[tauri::command]
+async fn find_notes_by_title_all(
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_by_title_all(&title)
+        .map_err(|e| e.to_string())
+}
+
+/// Finds all notes that link to a specific note
+///
+/// Uses the wikilinks stored in the search index while it is updated on
+/// every change, falling back to scanning the note files otherwise or when
+/// the index is empty or cannot be queried.
+///
+/// # Parameters
+/// * `note_title` - Title of the note to find backlinks for
+///
+/// # Returns
+/// A list of note summaries that link to the specified note
+#[tauri::command]
+async fn find_backlinks(
+    note_title: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    if let Some(backlinks) = find_backlinks_indexed(&state, note_manager, &note_title) {
+        return Ok(backlinks);
+    }
+
+    note_manager
+        .find_backlinks(&note_title)
+        .map_err(|e| e.to_string())
+}
+
+/// Looks up backlinks in the search index
+///
+/// # Parameters
+/// * `state` - Application state holding the configuration and search service
+/// * `note_manager` - Note manager used to read the summaries of the linking notes
+/// * `note_title` - Title of the note to find backlinks for
+///
+/// # Returns
+/// The linking notes, or None if the index is not updated on every change,
+/// is empty or the lookup failed
+fn find_backlinks_indexed(
+    state: &State<'_, AppState>,
+    note_manager: &NoteManager,
+    note_title: &str,
+) -> Option<Vec<NoteSummary>> {
+    let config = state.config_manager.lock().ok()?.get_config();
+
+    // Without incremental updates the index may miss recent links
+    let index_current = config.auto_update_search_index
+        && matches!(
+            config.auto_update_mode,
+            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid
+        );
+    if !index_current {
+        return None;
+    }
+
+    let search_service = state.search_service.lock().ok()?;
+
+    // An empty index has not been built yet, so it cannot be trusted
+    let document_count = match search_service.document_count() {
+        Ok(0) => return None,
+        Ok(count) => count,
+        Err(e) => {
+            warn!("Failed to read search index size: {}", e);
+            return None;
+        }
+    };
+
+    match search_service.find_backlinks_indexed(note_title, document_count) {
+        // Search results carry no content statistics, so summaries are read from the notes
+        Ok(results) => Some(
+            results
+                .into_iter()
+                .filter_map(|result| match note_manager.get_note_metadata(&result.note.id) {
+                    Ok(summary) => Some(summary),
+                    Err(e) => {
+                        warn!("Failed to read backlink {}: {}", result.note.id, e);
+                        None
+                    }
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            warn!("Indexed backlink lookup failed, scanning notes instead: {}", e);
+            None
+        }
+    }
+}
+
+/// Gets the targets of all wikilinks in a note
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// The link targets in the order they appear
+#[tauri::command]
+async fn get_note_wikilinks(id: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.extract_wikilinks(&id).map_err(note_error_to_string)
+}
+
+/// Suggests note titles for wikilink autocompletion
+///
+/// # Parameters
+/// * `partial` - Partially typed link target
+/// * `limit` - Maximum number of suggestions to return (optional)
+///
+/// # Returns
+/// Matching note summaries
+#[tauri::command]
+async fn suggest_wikilinks(
+    partial: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .suggest_wikilinks(&partial, limit.unwrap_or(10))
+        .map_err(|e| e.to_string())
+}
+
+/// Finds notes with a line matching a regular expression
+///
+/// # Parameters
+/// * `pattern` - Regular expression to search for
+/// * `max_results` - Maximum number of notes to return (optional)
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn search_notes_by_regex(
+    pattern: String,
+    max_results: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_matching_regex(&pattern, max_results.unwrap_or(100))
+        .map_err(|e| e.to_string())
+}
+
+/// Filters notes by title without using the search index
+///
+/// # Parameters
+/// * `prefix` - Search string to match titles against
+/// * `limit` - Maximum number of notes to return (optional)
+///
+/// # Returns
+/// Matching note summaries, prefix matches first
+#[tauri::command]
+async fn search_notes_by_title(
+    prefix: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .search_titles(&prefix, limit.unwrap_or(50))
+        .map_err(|e| e.to_string())
+}
+
+/// Gets notes that have not been modified for a while
+///
+/// # Parameters
+/// * `days` - Notes not modified for more than this many days are returned
+///   (defaults to the configured stale notes threshold)
+///
+/// # Returns
+/// Summaries of the stale notes, oldest first
+#[tauri::command]
+async fn get_stale_notes(days: Option<u32>, state: State<'_, AppState>) -> Result<Vec<NoteSummary>, String> {
+    let days = match days {
+        Some(days) => days,
+        None => {
+            state
+                .config_manager
+                .lock()
+                .map_err(|e| e.to_string())?
+                .get_config()
+                .stale_notes_threshold_days
+        }
+    };
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_stale_notes(days).map_err(|e| e.to_string())
+}
+
+/// Gets notes with a number of tags within a range
+///
+/// # Parameters
+/// * `min` - Minimum number of tags
+/// * `max` - Maximum number of tags
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn filter_notes_by_tag_count(
+    min: usize,
+    max: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_with_tag_count(min, max)
+        .map_err(|e| e.to_string())
+}
+
+/// Gets notes without any tags
+///
+/// # Returns
+/// Summaries of the untagged notes
+#[tauri::command]
+async fn find_untagged_notes(state: State<'_, AppState>) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_without_tags()
+        .map_err(|e| e.to_string())
+}
+
+/// Gets notes with an exact number of tags
+///
+/// # Parameters
+/// * `n` - Number of tags
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn find_notes_with_exactly_n_tags(
+    n: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .find_notes_with_exactly_n_tags(n)
+        .map_err(|e| e.to_string())
+}
+
+/// Gets notes with a number of tags within a range in a given order
+///
+/// # Parameters
+/// * `min` - Minimum number of tags
+/// * `max` - Maximum number of tags
+/// * `sort` - Optional sort options applied left to right, later ones breaking
+///   ties; the configured default sort is used if omitted
+///
+/// # Returns
+/// Summaries of the matching notes
+#[tauri::command]
+async fn filter_notes_by_tag_count_and_sort(
+    min: usize,
+    max: usize,
+    sort: Option<Vec<notes::SortOption>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let sort = sort.unwrap_or_else(|| vec![config.default_sort.clone()]);
+    note_manager
+        .find_notes_with_tag_count_sorted(min, max, Some(sort))
+        .map_err(|e| e.to_string())
+}
+
+/// Sets after how many days without changes a note is considered stale
+///
+/// # Parameters
+/// * `days` - Stale note threshold in days
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_stale_notes_threshold(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_stale_notes_threshold(days)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
+/// Gets the size of a note file
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// Size of the note file in bytes
+#[tauri::command]
+async fn get_note_size(id: String, state: State<'_, AppState>) -> Result<u64, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_note_size(&id).map_err(note_error_to_string)
+}
+
+/// Gets the absolute path of a note file
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// The absolute path; the file is not required to exist
+#[tauri::command]
+async fn get_note_absolute_path(id: String, state: State<'_, AppState>) -> Result<String, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .get_note_absolute_path(&id)
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(note_error_to_string)
+}
+
+/// Opens a note in the default application for its file type
+///
+/// # Parameters
+/// * `id` - ID of the note
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn open_note_in_system_editor(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let path = {
+        let note_manager_lock = state.note_manager.read().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        let path = note_manager.get_note_absolute_path(&id).map_err(note_error_to_string)?;
+
+        // Only hand notes to the OS opener, never scripts or executables in the vault
+        if !path.is_file() || !note_manager.is_listed_note_file(&path) {
+            return Err(note_error_to_string(NoteError::NotFound(id)));
+        }
+
+        path
+    };
+
+    tauri_plugin_opener::open_path(&path, None::<&str>).map_err(|e| e.to_string())
+}
+
+/// Gets the notes modified after a point in time
+///
+/// # Parameters
+/// * `since_ts` - Unix timestamp (seconds); only later modifications are returned
+///
+/// # Returns
+/// Summaries of the modified notes
+#[tauri::command]
+async fn get_notes_modified_after(
+    since_ts: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let since = chrono::DateTime::<chrono::Utc>::from_timestamp(since_ts, 0)
+        .ok_or_else(|| format!("Invalid timestamp: {}", since_ts))?;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .get_notes_modified_after(since)
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the notes created after a point in time
+///
+/// # Parameters
+/// * `since_ts` - Unix timestamp (seconds); only notes created later are returned
+///
+/// # Returns
+/// Summaries of the created notes
+#[tauri::command]
+async fn get_notes_created_after(
+    since_ts: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSummary>, String> {
+    let since = chrono::DateTime::<chrono::Utc>::from_timestamp(since_ts, 0)
+        .ok_or_else(|| format!("Invalid timestamp: {}", since_ts))?;
+
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .get_notes_created_after(since)
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the folder structure of the notes directory
+///
+/// # Returns
+/// The root folder of the vault with all its subfolders
+#[tauri::command]
+async fn get_folder_tree(state: State<'_, AppState>) -> Result<FolderNode, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_folder_tree().map_err(|e| e.to_string())
+}
+
+/// Creates a folder inside the notes directory
+///
+/// # Parameters
+/// * `path` - Path of the folder relative to the notes directory
+///
+/// # Returns
+/// The absolute path of the folder
+#[tauri::command]
+async fn create_folder(path: String, state: State<'_, AppState>) -> Result<PathBuf, String> {
+    let note_manager_lock = state.note_manager.write().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.create_folder(&path).map_err(|e| e.to_string())
+}
+
+/// Deletes a folder inside the notes directory
+///
+/// # Parameters
+/// * `path` - Path of the folder relative to the notes directory
+/// * `force` - Whether to delete a non-empty folder, moving its notes to the vault root
+///
+/// # Returns
+/// The notes that were moved to the vault root
+#[tauri::command]
+async fn delete_folder(
+    app_handle: AppHandle,
+    path: String,
+    force: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<Note>, String> {
+    let moved = {
+        let note_manager_lock = state.note_manager.write().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
 
-    // Check if we should update the search index
-    let should_update_index = {
-        let config = state
-            .config_manager
-            .lock()
+        note_manager
+            .delete_folder(&path, force)
             .map_err(|e| e.to_string())?
-            .get_config();
-        
-        (config.auto_update_search_index, config.auto_update_mode)
     };
 
-    if should_update_index.0 {
-        // Update the search index
-        match should_update_index.1 {
-            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
-                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
-
-                // Remove the old note from the index
-                search_service
-                    .remove_note(&original_note.id)
-                    .map_err(|e| e.to_string())?;
-
-                // Add the updated note to the index
-                search_service
-                    .index_note(&updated_note)
-                    .map_err(|e| e.to_string())?;
+    let old_ids: Vec<&str> = moved.iter().map(|(old_id, _)| old_id.as_str()).collect();
+    let notes: Vec<&Note> = moved.iter().map(|(_, note)| note).collect();
 
-                info!(
-                    "Incrementally updated search index for renamed note: {} -> {}",
-                    original_note.id, updated_note.id
-                );
-            },
-            config::AutoUpdateMode::Periodic => {
-                // For periodic mode, we don't update the index immediately
-                // It will be updated during the next scheduled rebuild
-            }
-        }
+    replace_note_ids(
+        &state,
+        moved.iter().map(|(old_id, note)| (old_id.as_str(), note.id.as_str())),
+    )?;
 
+    if apply_index_updates(&state, &notes, &old_ids)? {
         // Check if we need to do a periodic rebuild
         check_periodic_rebuild(app_handle, state).await?;
     }
 
-    Ok(updated_note)
+    Ok(moved.into_iter().map(|(_, note)| note).collect())
 }
 
-/// Moves a note to a different path
-///
-/// # Parameters
-/// * `id` - ID of the note to move
-/// * `new_path` - New relative path for the note (including filename)
+/// Gets the graph of wikilinks between all notes
 ///
 /// # Returns
-/// The updated note with new ID
+/// All notes as nodes and the resolved links between them as edges
 #[tauri::command]
-async fn move_note(
-    app_handle: AppHandle,
-    id: String,
-    new_path: String,
-    state: State<'_, AppState>,
-) -> Result<Note, String> {
-    // Get the note manager
-    let note_manager = {
-        let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
-        
-        match note_manager_lock.as_ref() {
-            Some(nm) => nm.clone(),
-            None => return Err("Note manager not initialized".into()),
-        }
+async fn get_link_graph(state: State<'_, AppState>) -> Result<GraphData, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
     };
 
-    // Get the original note to remove from index
-    let original_note = note_manager.get_note(&id).map_err(|e| e.to_string())?;
+    note_manager.get_link_graph().map_err(|e| e.to_string())
+}
 
-    // Move the note
-    let updated_note = note_manager
-        .move_note(&id, &new_path)
-        .map_err(|e| e.to_string())?;
+/// Finds wikilinks that do not resolve to any note
+///
+/// # Parameters
+/// * `ids` - IDs of the notes to check (optional, all notes if omitted)
+///
+/// # Returns
+/// The broken links, sorted by source note title and then target
+#[tauri::command]
+async fn validate_wikilinks(
+    ids: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<BrokenLink>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
-    // Check if we should update the search index
-    let should_update_index = {
-        let config = state
-            .config_manager
-            .lock()
-            .map_err(|e| e.to_string())?
-            .get_config();
-        
-        (config.auto_update_search_index, config.auto_update_mode)
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
     };
 
-    if should_update_index.0 {
-        // Update the search index
-        match should_update_index.1 {
-            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
-                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+    let ids: Option<Vec<&str>> = ids
+        .as_ref()
+        .map(|ids| ids.iter().map(String::as_str).collect());
 
-                // Remove the old note from the index
-                search_service
-                    .remove_note(&original_note.id)
-                    .map_err(|e| e.to_string())?;
+    note_manager
+        .validate_wikilinks(ids.as_deref())
+        .map_err(|e| e.to_string())
+}
 
-                // Add the updated note to the index
-                search_service
-                    .index_note(&updated_note)
-                    .map_err(|e| e.to_string())?;
+/// Finds the notes most related to a note
+///
+/// # Parameters
+/// * `id` - ID of the note to find related notes for
+/// * `limit` - Maximum number of related notes to return (optional)
+///
+/// # Returns
+/// Related notes with their scores, most related first
+#[tauri::command]
+async fn get_related_notes(
+    id: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RelatedNote>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
-                info!(
-                    "Incrementally updated search index for moved note: {} -> {}",
-                    original_note.id, updated_note.id
-                );
-            },
-            config::AutoUpdateMode::Periodic => {
-                // For periodic mode, we don't update the index immediately
-                // It will be updated during the next scheduled rebuild
-            }
-        }
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
 
-        // Check if we need to do a periodic rebuild
-        check_periodic_rebuild(app_handle, state).await?;
-    }
+    let related = note_manager
+        .get_related_notes(&id, limit.unwrap_or(10))
+        .map_err(note_error_to_string)?;
 
-    Ok(updated_note)
+    Ok(related
+        .into_iter()
+        .map(|(note, score)| RelatedNote { note, score })
+        .collect())
 }
 
-/// Creates a new note
+/// Counts the most frequent words of a note
 ///
 /// # Parameters
-/// * `title` - Title of the note
-/// * `content` - Initial content of the note
-/// * `file_type` - Type of note (Markdown or PlainText)
-/// * `pattern` - Optional naming pattern (e.g., "{number}-{title}")
+/// * `id` - ID of the note
+/// * `top_n` - Maximum number of words to return (optional)
 ///
 /// # Returns
-/// The newly created note
+/// Words with their counts, most frequent first
 #[tauri::command]
-async fn create_note(
-    app_handle: AppHandle,
-    title: String,
-    content: String,
-    file_type: notes::NoteType,
-    pattern: Option<String>,
+async fn get_note_word_frequency(
+    id: String,
+    top_n: Option<usize>,
     state: State<'_, AppState>,
-) -> Result<Note, String> {
-    // Get the note manager
-    let note_manager = {
-        let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
-        
-        match note_manager_lock.as_ref() {
-            Some(nm) => nm.clone(),
-            None => return Err("Note manager not initialized".into()),
-        }
-    };
-
-    let pattern_ref = pattern.as_deref();
-    let new_note = note_manager
-        .create_note(&title, &content, file_type, pattern_ref)
-        .map_err(|e| e.to_string())?;
+) -> Result<Vec<(String, usize)>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
-    // Check if we should update the search index
-    let should_update_index = {
-        let config = state
-            .config_manager
-            .lock()
-            .map_err(|e| e.to_string())?
-            .get_config();
-        
-        (config.auto_update_search_index, config.auto_update_mode)
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
     };
 
-    if should_update_index.0 {
-        // Update the search index
-        match should_update_index.1 {
-            config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
-                let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
-                search_service
-                    .index_note(&new_note)
-                    .map_err(|e| e.to_string())?;
-                info!(
-                    "Incrementally updated search index for new note: {}",
-                    new_note.id
-                );
-            },
-            config::AutoUpdateMode::Periodic => {
-                // For periodic mode, we don't update the index immediately
-                // It will be updated during the next scheduled rebuild
-            }
-        }
+    note_manager
+        .get_word_frequency(&id, top_n.unwrap_or(DEFAULT_WORD_FREQUENCY_LIMIT))
+        .map_err(note_error_to_string)
+}
 
-        // Check if we need to do a periodic rebuild
-        check_periodic_rebuild(app_handle, state).await?;
-    }
+/// Counts the most frequent words across all notes
+///
+/// # Parameters
+/// * `top_n` - Maximum number of words to return (optional)
+///
+/// # Returns
+/// Words with their counts, most frequent first
+#[tauri::command]
+async fn get_vault_word_frequency(
+    top_n: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<(String, usize)>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
-    Ok(new_note)
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager
+        .get_vault_word_frequency(top_n.unwrap_or(DEFAULT_WORD_FREQUENCY_LIMIT))
+        .map_err(|e| e.to_string())
 }
 
-/// Searches for notes matching the query
+/// Finds groups of notes with identical or near-identical content
 ///
 /// # Parameters
-/// * `query` - The search query
-/// * `limit` - Maximum number of results to return (optional)
+/// * `exact_only` - Whether to only report notes whose files are identical
 ///
 /// # Returns
-/// List of search results
+/// Groups of duplicate notes of the same type
 #[tauri::command]
-async fn search_notes(
-    query: String,
-    limit: Option<usize>,
+async fn find_duplicate_notes(
+    exact_only: bool,
     state: State<'_, AppState>,
-) -> Result<Vec<SearchResult>, String> {
-    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
-    let limit = limit.unwrap_or(100);
+) -> Result<Vec<DuplicateGroup>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
-    search_service
-        .search(&query, limit)
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    let similarity_threshold = if exact_only {
+        1.0
+    } else {
+        notes::DEFAULT_NEAR_DUPLICATE_SIMILARITY
+    };
+
+    note_manager
+        .find_duplicate_notes(similarity_threshold)
         .map_err(|e| e.to_string())
 }
 
-/// Searches for notes with specific tags
+/// Gets all subnotes for a parent note
 ///
 /// # Parameters
-/// * `tags` - List of tags to filter by
-/// * `match_all` - If true, notes must have all tags; if false, notes can have any of the tags
-/// * `sort` - Optional sort option to determine the order of notes
+/// * `parent_id` - ID of the parent note
 ///
 /// # Returns
-/// A list of note summaries
+/// List of subnotes with their hierarchy depth
 #[tauri::command]
-async fn filter_notes_by_tags(
-    tags: Vec<String>,
-    match_all: bool,
-    sort: Option<notes::SortOption>,
+async fn get_subnotes(
+    parent_id: String,
     state: State<'_, AppState>,
-) -> Result<Vec<NoteSummary>, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+) -> Result<Vec<notes::SubnoteInfo>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
     let Some(note_manager) = note_manager_lock.as_ref() else {
         return Err("Note manager not initialized".into());
     };
 
-    // Get all notes
-    let all_notes = note_manager.list_notes(sort).map_err(|e| e.to_string())?;
+    note_manager.get_subnotes(&parent_id).map_err(note_error_to_string)
+}
 
-    // Filter notes by tags
-    let filtered_notes = if match_all {
-        // Notes must have all specified tags
-        all_notes
-            .into_iter()
-            .filter(|note| tags.iter().all(|tag| note.tags.contains(tag)))
-            .collect()
-    } else {
-        // Notes can have any of the specified tags
-        all_notes
-            .into_iter()
-            .filter(|note| tags.iter().any(|tag| note.tags.contains(tag)))
-            .collect()
+/// Gets all subnotes of a note level by level, up to a maximum depth
+///
+/// # Parameters
+/// * `parent_id` - ID of the parent note
+/// * `max_depth` - Deepest level to include, 1 for direct children only (unlimited if omitted)
+///
+/// # Returns
+/// List of subnotes with their depth, each followed by its own subnotes
+#[tauri::command]
+async fn list_all_subnotes_flat(
+    parent_id: String,
+    max_depth: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<notes::SubnoteInfo>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
     };
 
-    Ok(filtered_notes)
+    note_manager
+        .list_all_subnotes_flat(&parent_id, max_depth)
+        .map_err(note_error_to_string)
 }
 
-/// Finds a note by its title
+/// Gets the parent of a note in the Zettelkasten hierarchy
 ///
 /// # Parameters
-/// * `title` - Title of the note to find
+/// * `id` - ID of the note
 ///
 /// # Returns
-/// The note ID if found, None otherwise
+/// The parent note summary, if any
 #[tauri::command]
-async fn find_note_by_title(
-    title: String,
+async fn get_parent_note(
+    id: String,
     state: State<'_, AppState>,
-) -> Result<Option<String>, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+) -> Result<Option<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
+
+    let Some(note_manager) = note_manager_lock.as_ref() else {
+        return Err("Note manager not initialized".into());
+    };
+
+    note_manager.get_parent_note(&id).map_err(note_error_to_string)
+}
+
+/// Gets the root notes of the Zettelkasten hierarchy
+///
+/// # Returns
+/// Notes with a purely numeric identifier, in Zettelkasten order
+#[tauri::command]
+async fn get_zettelkasten_root_notes(state: State<'_, AppState>) -> Result<Vec<NoteSummary>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
     let Some(note_manager) = note_manager_lock.as_ref() else {
         return Err("Note manager not initialized".into());
     };
 
-    note_manager
-        .find_note_by_title(&title)
-        .map_err(|e| e.to_string())
+    note_manager.get_root_notes().map_err(|e| e.to_string())
 }
 
-/// Finds all notes that link to a specific note
-///
-/// # Parameters
-/// * `note_title` - Title of the note to find backlinks for
+/// Gets the full Zettelkasten tree of all notes
 ///
 /// # Returns
-/// A list of note summaries that link to the specified note
+/// The trees of all root notes, in Zettelkasten order
 #[tauri::command]
-async fn find_backlinks(
-    note_title: String,
-    state: State<'_, AppState>,
-) -> Result<Vec<NoteSummary>, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+async fn get_zettelkasten_index(state: State<'_, AppState>) -> Result<Vec<notes::ZettelNode>, String> {
+    let note_manager_lock = state.note_manager.read().await;
 
     let Some(note_manager) = note_manager_lock.as_ref() else {
         return Err("Note manager not initialized".into());
     };
 
-    note_manager
-        .find_backlinks(&note_title)
-        .map_err(|e| e.to_string())
+    note_manager.get_zettelkasten_index().map_err(|e| e.to_string())
 }
 
-/// Gets all subnotes for a parent note
+/// Creates a subnote with the next free Zettelkasten identifier
 ///
 /// # Parameters
 /// * `parent_id` - ID of the parent note
+/// * `title` - Title of the subnote (without identifier)
+/// * `content` - Content of the subnote
 ///
 /// # Returns
-/// List of subnotes with their hierarchy depth
+/// The created subnote
 #[tauri::command]
-async fn get_subnotes(
+async fn create_subnote(
+    app_handle: AppHandle,
     parent_id: String,
+    title: String,
+    content: String,
     state: State<'_, AppState>,
-) -> Result<Vec<notes::SubnoteInfo>, String> {
-    let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+) -> Result<Note, String> {
+    let new_note = {
+        let note_manager_lock = state.note_manager.write().await;
 
-    let Some(note_manager) = note_manager_lock.as_ref() else {
-        return Err("Note manager not initialized".into());
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .create_subnote(&parent_id, &title, &content)
+            .map_err(note_error_to_string)?
     };
 
-    note_manager.get_subnotes(&parent_id).map_err(|e| e.to_string())
+    if apply_index_updates(&state, &[&new_note], &[])? {
+        // Check if we need to do a periodic rebuild
+        check_periodic_rebuild(app_handle, state).await?;
+    }
+
+    Ok(new_note)
 }
 
 /// Rebuilds the search index with all notes
@@ -714,39 +3890,45 @@ async fn rebuild_search_index(
 
     // Get note manager and all notes
     let notes = {
-        let note_manager_lock = state.note_manager.lock().map_err(|e| e.to_string())?;
+        let note_manager_lock = state.note_manager.read().await;
         
         let Some(note_manager) = note_manager_lock.as_ref() else {
             return Err("Note manager not initialized".into());
         };
         
-        // Get all notes
+        // A full rebuild re-reads the notes directory, including external changes
+        note_manager.invalidate_cache();
+        
+        // Get all notes (notes in excluded folders are skipped)
         info!("Getting all notes...");
         let note_summaries = note_manager.list_notes(None).map_err(|e| e.to_string())?;
-        let mut notes = Vec::new();
+        let ids: Vec<&str> = note_summaries.iter().map(|summary| summary.id.as_str()).collect();
         
         // Load full notes
         info!("Loading full notes...");
-        for summary in note_summaries {
-            let note = note_manager
-                .get_note(&summary.id)
-                .map_err(|e| e.to_string())?;
-            notes.push(note);
-        }
-        
-        notes
+        note_manager.batch_get_notes(&ids).map_err(|e| e.to_string())?
+    };
+
+    let config = {
+        let config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+        config_manager.get_config()
     };
 
-    // Create a new search service
+    // Create a new search service and rebuild the index with it
     info!("Creating new search service...");
-    let new_search_service = SearchService::new(&app_dir)
-        .map_err(|e| format!("Failed to create new search service: {}", e))?;
+    let rebuilt = SearchService::new(&app_dir, &config)
+        .map_err(|e| format!("Failed to create new search service: {}", e))
+        .and_then(|new_search_service| {
+            info!("Rebuilding index with {} notes...", notes.len());
+            new_search_service
+                .rebuild_index(&notes, |progress| emit_index_progress(&app_handle, progress))
+                .map_err(|e| format!("Failed to rebuild index: {}", e))?;
+            Ok(new_search_service)
+        });
 
-    // Rebuild index with the new search service
-    info!("Rebuilding index with {} notes...", notes.len());
-    new_search_service
-        .rebuild_index(&notes)
-        .map_err(|e| format!("Failed to rebuild index: {}", e))?;
+    // Until a rebuild succeeds, searches fall back to scanning the notes
+    *state.search_index_failed.lock().map_err(|e| e.to_string())? = rebuilt.is_err();
+    let new_search_service = rebuilt?;
 
     // Update the search service in the app state
     info!("Updating search service in app state...");
@@ -764,6 +3946,197 @@ async fn rebuild_search_index(
     Ok(())
 }
 
+/// Gets health information about the search index
+///
+/// # Returns
+/// Document count, size, location, last rebuild time and segment count of the index, with a
+/// `NeedsOptimization` status once so many segments accumulated that `optimize_search_index`
+/// should be suggested
+#[tauri::command]
+async fn get_search_index_health(state: State<'_, AppState>) -> Result<IndexHealth, String> {
+    let last_rebuilt = {
+        let last_rebuild = state.last_index_rebuild.lock().map_err(|e| e.to_string())?;
+        let elapsed = chrono::Duration::from_std(last_rebuild.elapsed()).map_err(|e| e.to_string())?;
+        chrono::Utc::now() - elapsed
+    };
+
+    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+    search_service
+        .get_health(last_rebuilt)
+        .map_err(|e| e.to_string())
+}
+
+/// Checks whether the search index contains every note on disk
+///
+/// # Returns
+/// True if the index document count equals the number of notes
+#[tauri::command]
+async fn verify_search_index(state: State<'_, AppState>) -> Result<bool, String> {
+    let note_count = {
+        let note_manager_lock = state.note_manager.read().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager.list_notes(None).map_err(|e| e.to_string())?.len()
+    };
+
+    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+    search_service
+        .verify_index(note_count)
+        .map_err(|e| e.to_string())
+}
+
+/// Merges the search index segments to speed up searching
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn optimize_search_index(state: State<'_, AppState>) -> Result<(), SearchError> {
+    let search_service = state.search_service.lock()?;
+
+    search_service.optimize()
+}
+
+/// Removes search index entries for notes that no longer exist on disk
+///
+/// # Returns
+/// The number of stale entries removed
+#[tauri::command]
+async fn clean_stale_search_entries(state: State<'_, AppState>) -> Result<usize, String> {
+    let note_ids: Vec<String> = {
+        let note_manager_lock = state.note_manager.read().await;
+
+        let Some(note_manager) = note_manager_lock.as_ref() else {
+            return Err("Note manager not initialized".into());
+        };
+
+        note_manager
+            .list_notes(None)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|summary| summary.id)
+            .collect()
+    };
+
+    let valid_ids: Vec<&str> = note_ids.iter().map(String::as_str).collect();
+
+    let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+    search_service
+        .remove_stale_documents(&valid_ids)
+        .map_err(|e| e.to_string())
+}
+
+/// Converts the error of a note operation to a command error
+///
+/// Errors resolving a note ID are prefixed with their code, e.g.
+/// `NOT_FOUND: <id>` or `INVALID_ID: <reason>`, so the frontend can tell
+/// them apart; other errors are converted to their message.
+///
+/// # Parameters
+/// * `error` - The error of the note operation
+///
+/// # Returns
+/// The error message
+fn note_error_to_string(error: impl Into<anyhow::Error>) -> String {
+    let error = error.into();
+    let Some(note_error) = error.chain().find_map(|cause| cause.downcast_ref::<NoteError>()) else {
+        return error.to_string();
+    };
+
+    let detail = match note_error {
+        NoteError::NotFound(id) => id.clone(),
+        NoteError::InvalidId(reason) => reason.clone(),
+        other => other.to_string(),
+    };
+
+    format!("{}: {}", note_error.code(), detail)
+}
+
+/// Moves pins, editor states and recently viewed entries to the new IDs of
+/// renamed, moved or converted notes
+///
+/// # Parameters
+/// * `state` - Application state
+/// * `new_ids` - Pairs of old and new note ID
+///
+/// # Returns
+/// Result indicating success or failure
+fn replace_note_ids<'a>(
+    state: &State<'_, AppState>,
+    new_ids: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<(), String> {
+    let new_ids: HashMap<String, String> = new_ids
+        .into_iter()
+        .filter(|(old_id, new_id)| old_id != new_id)
+        .map(|(old_id, new_id)| (old_id.to_string(), new_id.to_string()))
+        .collect();
+
+    state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .replace_note_ids(&new_ids)
+        .map_err(|e| e.to_string())
+}
+
+/// Incrementally updates the search index after notes change
+///
+/// Nothing is done unless automatic index updates are enabled in incremental
+/// or hybrid mode; periodic mode picks the changes up on the next rebuild.
+///
+/// # Parameters
+/// * `state` - Application state
+/// * `updated` - Notes that were created or modified
+/// * `removed_ids` - IDs of notes that no longer exist under that ID
+///
+/// # Returns
+/// Whether automatic index updates are enabled
+fn apply_index_updates(
+    state: &State<'_, AppState>,
+    updated: &[&Note],
+    removed_ids: &[&str],
+) -> Result<bool, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    if !config.auto_update_search_index {
+        return Ok(false);
+    }
+
+    match config.auto_update_mode {
+        config::AutoUpdateMode::Incremental | config::AutoUpdateMode::Hybrid => {
+            let search_service = state.search_service.lock().map_err(|e| e.to_string())?;
+
+            for id in removed_ids {
+                search_service.remove_note(id).map_err(|e| e.to_string())?;
+            }
+
+            let updated: Vec<Note> = updated.iter().map(|&note| note.clone()).collect();
+            search_service.batch_index_notes(&updated).map_err(|e| e.to_string())?;
+
+            info!(
+                "Incrementally updated search index ({} updated, {} removed)",
+                updated.len(),
+                removed_ids.len()
+            );
+        },
+        config::AutoUpdateMode::Periodic => {
+            // For periodic mode, we don't update the index immediately
+            // It will be updated during the next scheduled rebuild
+        }
+    }
+
+    Ok(true)
+}
+
 /// Checks if a periodic rebuild is needed and performs it if necessary
 ///
 /// # Parameters
@@ -810,14 +4183,203 @@ async fn check_periodic_rebuild(
     Ok(())
 }
 
+/// Gets the directory backups are stored in
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle
+/// * `config` - Current application configuration
+///
+/// # Returns
+/// The configured backup directory, or `backups` in the app data directory
+fn resolve_backup_dir(app_handle: &AppHandle, config: &AppConfig) -> Result<PathBuf, String> {
+    match &config.backup_dir {
+        Some(backup_dir) => Ok(backup_dir.clone()),
+        None => app_handle
+            .path()
+            .app_data_dir()
+            .map(|app_dir| app_dir.join("backups"))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Creates a backup of the notes directory and deletes the oldest backups
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle
+/// * `config` - Current application configuration
+///
+/// # Returns
+/// Path of the created backup
+fn run_backup(app_handle: &AppHandle, config: &AppConfig) -> Result<PathBuf, String> {
+    let Some(notes_dir) = &config.notes_dir else {
+        return Err("Notes directory not set".into());
+    };
+
+    let backup_dir = resolve_backup_dir(app_handle, config)?;
+
+    let backup_path =
+        BackupService::create_backup(notes_dir, &backup_dir).map_err(|e| e.to_string())?;
+    BackupService::prune_backups(&backup_dir, config.max_backups).map_err(|e| e.to_string())?;
+
+    info!("Created backup at {:?}", backup_path);
+    Ok(backup_path)
+}
+
+/// Creates a backup if automatic backups are enabled and the interval has elapsed
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle
+///
+/// # Returns
+/// Result indicating success or failure
+fn check_scheduled_backup(app_handle: &AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    if !config.backup_enabled || config.notes_dir.is_none() {
+        return Ok(());
+    }
+
+    let backup_dir = resolve_backup_dir(app_handle, &config)?;
+    if BackupService::is_backup_due(&backup_dir, config.backup_interval_hours)
+        .map_err(|e| e.to_string())?
+    {
+        run_backup(app_handle, &config)?;
+    }
+
+    Ok(())
+}
+
+/// Starts a background thread that creates automatic backups when they are due
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle
+fn start_backup_scheduler(app_handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        if let Err(e) = check_scheduled_backup(&app_handle) {
+            warn!("Scheduled backup failed: {}", e);
+        }
+
+        std::thread::sleep(BACKUP_CHECK_INTERVAL);
+    });
+}
+
+/// Creates a backup of the notes directory
+///
+/// # Returns
+/// Path of the created backup
+#[tauri::command]
+async fn create_backup(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PathBuf, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    run_backup(&app_handle, &config)
+}
+
+/// Lists the available backups
+///
+/// # Returns
+/// Backups, newest first
+#[tauri::command]
+async fn list_backups(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<BackupInfo>, String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    let backup_dir = resolve_backup_dir(&app_handle, &config)?;
+
+    BackupService::list_backups(&backup_dir).map_err(|e| e.to_string())
+}
+
+/// Restores the notes directory from a backup
+///
+/// Files in the backup replace the current files; the search index is rebuilt afterwards.
+///
+/// # Parameters
+/// * `backup_path` - Path of the backup archive
+///
+/// # Returns
+/// Result indicating success or failure
+#[tauri::command]
+async fn restore_from_backup(
+    app_handle: AppHandle,
+    backup_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let config = state
+        .config_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_config();
+
+    let Some(notes_dir) = &config.notes_dir else {
+        return Err("Notes directory not set".into());
+    };
+
+    {
+        // Keep other commands from touching notes while files are replaced
+        let _note_manager_lock = state.note_manager.write().await;
+
+        BackupService::restore_from_backup(&PathBuf::from(backup_path), notes_dir)
+            .map_err(|e| e.to_string())?;
+    }
+
+    refresh_note_manager(&state, &config).await?;
+    rebuild_search_index(app_handle, state).await
+}
+
+/// Sets the automatic backup options
+///
+/// # Parameters
+/// * `enabled` - Whether to create backups automatically
+/// * `interval_hours` - Minimum number of hours between automatic backups
+/// * `backup_dir` - Directory for backups (a folder in the app data directory if omitted)
+/// * `max_backups` - Number of backups to keep (0 keeps all backups)
+///
+/// # Returns
+/// The updated application configuration
+#[tauri::command]
+async fn set_backup_settings(
+    enabled: bool,
+    interval_hours: u32,
+    backup_dir: Option<String>,
+    max_backups: u32,
+    state: State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut config_manager = state.config_manager.lock().map_err(|e| e.to_string())?;
+
+    config_manager
+        .set_backup_settings(enabled, interval_hours, backup_dir.map(PathBuf::from), max_backups)
+        .map_err(|e| e.to_string())?;
+
+    Ok(config_manager.get_config())
+}
+
 /// iOS-specific initialization
+///
+/// Uses a `Notes` folder in the app's documents directory as the notes
+/// directory, so no folder has to be selected on iOS.
 #[cfg(target_os = "ios")]
 fn ios_init(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     println!("Initializing iOS-specific functionality");
 
     // Get the app's documents directory on iOS
-    let documents_dir =
-        tauri::api::path::document_dir().ok_or("Failed to get documents directory")?;
+    let documents_dir = app.path().document_dir()?;
 
     println!("iOS documents directory: {:?}", documents_dir);
 
@@ -836,8 +4398,8 @@ fn ios_init(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     config_manager.set_notes_dir(notes_dir.clone())?;
 
     // Initialize note manager with the iOS documents directory
-    let note_manager = NoteManager::new(notes_dir);
-    *state.note_manager.lock().map_err(|e| e.to_string())? = Some(note_manager);
+    let note_manager = create_note_manager(notes_dir, &config_manager.get_config());
+    *state.note_manager.blocking_write() = Some(note_manager);
 
     Ok(())
 }
@@ -858,30 +4420,85 @@ pub fn run() {
             let config_manager =
                 ConfigManager::new(&config_dir).expect("Failed to initialize config manager");
 
+            let config = config_manager.get_config();
+
             // Initialize search service
-            let search_service =
-                SearchService::new(&app_dir).expect("Failed to initialize search service");
+            let search_service = SearchService::new(&app_dir, &config)
+                .expect("Failed to initialize search service");
 
             // Initialize note manager if notes directory is configured
-            let note_manager = if let Some(notes_dir) = config_manager.get_config().notes_dir {
-                Some(NoteManager::new(notes_dir))
-            } else {
-                None
+            let note_manager = config
+                .notes_dir
+                .clone()
+                .map(|notes_dir| create_note_manager(notes_dir, &config));
+
+            // Drop index entries for notes deleted while the app was closed
+            let mut needs_index_rebuild = false;
+            if let Some(note_manager) = &note_manager {
+                match note_manager.list_notes(None) {
+                    Ok(summaries) => {
+                        let valid_ids: Vec<&str> =
+                            summaries.iter().map(|summary| summary.id.as_str()).collect();
+                        if let Err(e) = search_service.remove_stale_documents(&valid_ids) {
+                            eprintln!("Error cleaning stale search entries: {}", e);
+                        }
+                        needs_index_rebuild = search_service.needs_rebuild(summaries.len());
+                    }
+                    Err(e) => eprintln!("Error listing notes: {}", e),
+                }
+            }
+
+            // Watch the notes directory for external changes if enabled
+            let watcher_service = match (&config.notes_dir, config.watch_external_changes) {
+                (Some(notes_dir), true) => {
+                    WatcherService::start(
+                        app.handle().clone(),
+                        notes_dir,
+                        is_external_note_change,
+                        invalidate_note_cache,
+                    )
+                        .map_err(|e| eprintln!("Error starting file watcher: {}", e))
+                        .ok()
+                }
+                _ => None,
             };
 
             // Set up app state
             app.manage(AppState {
                 config_manager: Mutex::new(config_manager),
-                note_manager: Mutex::new(note_manager),
+                note_manager: RwLock::new(note_manager),
                 search_service: Mutex::new(search_service),
                 last_index_rebuild: Mutex::new(Instant::now()),
+                search_index_failed: Mutex::new(false),
+                watcher_service: Mutex::new(watcher_service),
             });
 
+            // Create automatic backups in the background
+            start_backup_scheduler(app.handle().clone());
+
+            // Repopulate an index that was recreated after a schema change or is missing notes;
+            // iOS rebuilds the index below anyway
+            if needs_index_rebuild && cfg!(not(target_os = "ios")) {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = rebuild_search_index(app_handle.clone(), app_handle.state()).await {
+                        eprintln!("Error building search index: {}", e);
+                    }
+                });
+            }
+
             // Initialize iOS-specific functionality
             #[cfg(target_os = "ios")]
             {
-                if let Err(e) = ios_init(app) {
-                    eprintln!("Error initializing iOS: {}", e);
+                match ios_init(app) {
+                    // Index the notes before the first command is called
+                    Ok(()) => {
+                        let rebuild = rebuild_search_index(app.handle().clone(), app.state());
+                        if let Err(e) = tauri::async_runtime::block_on(rebuild) {
+                            eprintln!("Error building search index: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("Error initializing iOS: {}", e),
                 }
             }
 
@@ -889,25 +4506,202 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
+            reset_config,
+            export_config,
+            import_config,
             select_folder,
+            validate_notes_dir,
             set_note_naming_pattern,
             set_default_note_type,
             set_auto_update_search_index,
             set_auto_update_mode,
             set_auto_update_interval,
+            set_default_sort,
+            set_excluded_folders,
+            get_excluded_folders,
+            set_custom_extensions,
+            get_custom_extensions,
+            set_follow_symlinks,
+            set_max_walk_depth,
+            set_auto_create_folders,
+            set_custom_stop_words,
+            set_id_scheme,
+            set_trash_retention_days,
+            set_max_history_versions,
+            set_search_writer_memory,
+            set_max_paginated_results,
+            set_search_language,
+            set_watch_external_changes,
+            set_backup_settings,
+            create_backup,
+            list_backups,
+            restore_from_backup,
             list_notes,
+            list_notes_with_diagnostics,
+            list_notes_with_preview,
+            list_notes_paginated,
+            pin_note,
+            unpin_note,
+            list_pinned_notes,
+            set_note_viewed,
+            get_recently_viewed_notes,
+            set_note_ui_state,
+            get_note_ui_state,
+            get_note_stats,
+            get_vault_stats,
             get_note,
+            get_note_metadata,
+            note_exists,
+            batch_get_notes,
             update_note_content,
+            save_note_version,
+            get_note_history,
+            restore_note_version,
             rename_note,
+            bulk_rename_by_pattern,
+            batch_update_tags,
+            split_note,
+            merge_notes,
+            convert_note_type,
             move_note,
+            move_notes_batch,
+            copy_note_to_vault,
             search_notes,
+            search_notes_fallback,
+            search_notes_in_path,
+            search_notes_paginated,
+            search_notes_in_date_range,
+            advanced_search,
+            search_notes_by_field,
+            import_notes_from_directory,
+            import_from_obsidian,
+            export_note_to_html,
+            export_notes_to_html,
+            export_to_obsidian,
             rebuild_search_index,
+            get_search_index_health,
+            verify_search_index,
+            optimize_search_index,
+            clean_stale_search_entries,
             create_note,
+            suggest_note_filename,
+            add_template,
+            list_templates,
+            delete_template,
+            create_note_from_template,
+            create_or_get_daily_note,
+            set_daily_notes_folder,
+            set_daily_note_template,
+            trash_note,
+            delete_note,
+            list_trashed_notes,
+            restore_trashed_note,
+            empty_trash,
             filter_notes_by_tags,
+            filter_notes_by_tags_fast,
+            get_tag_stats,
             find_note_by_title,
+            find_notes_by_title_all,
             find_backlinks,
+            get_note_wikilinks,
+            suggest_wikilinks,
+            link_notes,
+            unlink_notes,
+            search_notes_by_title,
+            search_notes_by_regex,
+            get_link_graph,
+            validate_wikilinks,
+            get_note_size,
+            get_note_absolute_path,
+            open_note_in_system_editor,
+            get_stale_notes,
+            filter_notes_by_tag_count,
+            filter_notes_by_tag_count_and_sort,
+            find_untagged_notes,
+            find_notes_with_exactly_n_tags,
+            set_stale_notes_threshold,
+            get_notes_modified_after,
+            get_notes_created_after,
+            get_folder_tree,
+            create_folder,
+            delete_folder,
+            get_related_notes,
+            find_duplicate_notes,
+            get_note_word_frequency,
+            get_vault_word_frequency,
             get_subnotes,
+            list_all_subnotes_flat,
+            create_subnote,
+            get_parent_note,
+            get_zettelkasten_root_notes,
+            get_zettelkasten_index,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_concurrent_reads_of_shared_note_manager() {
+        const READERS: usize = 4;
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        for i in 0..20 {
+            manager
+                .create_note(&format!("Note {}", i), "shared content", notes::NoteType::PlainText, None)
+                .unwrap();
+        }
+
+        let note_manager = Arc::new(RwLock::new(Some(manager)));
+        let barrier = Arc::new(Barrier::new(READERS));
+
+        let readers: Vec<_> = (0..READERS)
+            .map(|_| {
+                let note_manager = Arc::clone(&note_manager);
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    let note_manager = note_manager.blocking_read();
+                    let note_manager = note_manager.as_ref().unwrap();
+
+                    // Every reader holds its lock here, so this only passes if reads run concurrently
+                    barrier.wait();
+
+                    let notes = note_manager.list_notes(None).unwrap();
+                    for note in &notes {
+                        assert_eq!(note_manager.get_note(&note.id).unwrap().content, "shared content");
+                    }
+                    notes.len()
+                })
+            })
+            .collect();
+
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), 20);
+        }
+        assert!(note_manager.try_write().is_ok());
+    }
+
+    #[test]
+    fn test_move_note_updates_backlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Target.txt"), "target").unwrap();
+        std::fs::write(temp_dir.path().join("Source.md"), "# Source\n\nSee [[Target]]\n").unwrap();
+
+        let note_manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let target_id = note_manager.find_note_by_title("Target").unwrap().unwrap();
+        let source_id = note_manager.find_note_by_title("Source").unwrap().unwrap();
+
+        let (_, moved) =
+            move_note_with_backlinks(&note_manager, &target_id, "archive/Renamed.txt").unwrap();
+        assert_eq!(moved.title, "Renamed");
+
+        let source = note_manager.get_note(&source_id).unwrap();
+        assert_eq!(source.content, "# Source\n\nSee [[Renamed]]\n");
+    }
+}
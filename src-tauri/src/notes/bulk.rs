@@ -0,0 +1,208 @@
+use crate::notes::NoteManager;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// A planned rename of a note file
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkRenamePreview {
+    /// ID of the note before the rename
+    pub id: String,
+    /// Current file name without extension
+    pub old_name: String,
+    /// New file name without extension
+    pub new_name: String,
+    /// Whether another file already has the new name; such notes are not renamed
+    pub collides: bool,
+    /// Why the rename failed, if it was attempted and failed
+    pub error: Option<String>,
+    /// ID of the note after the rename, once it was renamed
+    pub new_id: Option<String>,
+}
+
+impl BulkRenamePreview {
+    /// Checks whether the note was renamed
+    pub fn renamed(&self) -> bool {
+        !self.collides && self.error.is_none()
+    }
+}
+
+impl NoteManager {
+    /// Renames every note whose file name matches a regular expression
+    ///
+    /// All renames are planned before any file changes. Notes whose new name
+    /// is taken by an existing file or by another note of the batch are
+    /// flagged and left unchanged. A failed rename is recorded in its preview
+    /// and the remaining notes are still renamed. Links to renamed notes are
+    /// updated.
+    ///
+    /// # Parameters
+    /// * `find_pattern` - Regular expression matched against file names without extension
+    /// * `replace_pattern` - Replacement, may reference capture groups as `$1` or `${name}`
+    /// * `dry_run` - Whether to only return the planned renames
+    ///
+    /// # Returns
+    /// The planned renames, ordered by old name
+    pub fn bulk_rename_by_pattern(
+        &self,
+        find_pattern: &str,
+        replace_pattern: &str,
+        dry_run: bool,
+    ) -> Result<Vec<BulkRenamePreview>> {
+        let regex = Regex::new(find_pattern).context("Invalid find pattern")?;
+
+        let mut previews = Vec::new();
+        let mut targets = HashSet::new();
+
+        let mut notes = self.list_notes(None)?;
+        notes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for note in notes {
+            let path = self.get_note_path(&note.id)?;
+            let Some(old_name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            if !regex.is_match(old_name) {
+                continue;
+            }
+
+            let new_name = regex.replace_all(old_name, replace_pattern).to_string();
+            if new_name == old_name {
+                continue;
+            }
+
+            if new_name.trim().is_empty() || new_name.contains(['/', '\\']) {
+                anyhow::bail!("Invalid new name for {}: '{}'", old_name, new_name);
+            }
+
+            let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("txt");
+            let new_path = path.with_file_name(format!("{}.{}", new_name, extension));
+
+            // Case-only renames are allowed on case-insensitive file systems
+            let case_only_difference = old_name.to_lowercase() == new_name.to_lowercase();
+            let taken = new_path.exists() && !case_only_difference;
+            let duplicate = !targets.insert(new_path.to_string_lossy().to_lowercase());
+
+            previews.push(BulkRenamePreview {
+                id: note.id,
+                old_name: old_name.to_string(),
+                new_name,
+                collides: taken || duplicate,
+                error: None,
+                new_id: None,
+            });
+        }
+
+        previews.sort_by(|a, b| a.old_name.cmp(&b.old_name));
+
+        if dry_run {
+            return Ok(previews);
+        }
+
+        for preview in previews.iter_mut().filter(|preview| !preview.collides) {
+            let renamed = self.get_note(&preview.id).and_then(|original| {
+                let renamed = self.rename_note(&preview.id, &preview.new_name, true)?;
+                Ok((original, renamed))
+            });
+
+            let (original, renamed) = match renamed {
+                Ok(notes) => notes,
+                Err(e) => {
+                    log::warn!("Failed to rename {}: {}", preview.old_name, e);
+                    preview.error = Some(e.to_string());
+                    continue;
+                }
+            };
+
+            if let Err(e) = self.update_backlinks(&original.title, &renamed.title) {
+                // The rename itself succeeded; the backlinks update is a secondary operation
+                log::warn!("Failed to update backlinks of {}: {}", renamed.id, e);
+            }
+            
+            preview.new_id = Some(renamed.id);
+        }
+
+        Ok(previews)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bulk_rename_by_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("draft-a", "a", NoteType::PlainText, None).unwrap();
+        manager.create_note("draft-b", "b", NoteType::PlainText, None).unwrap();
+        manager.create_note("final-b", "taken", NoteType::PlainText, None).unwrap();
+        manager.create_note("Index", "See [[draft-a]]", NoteType::PlainText, None).unwrap();
+
+        let previews = manager.bulk_rename_by_pattern("^draft-(.*)$", "final-$1", true).unwrap();
+
+        let planned: Vec<(&str, &str, bool)> = previews
+            .iter()
+            .map(|preview| (preview.old_name.as_str(), preview.new_name.as_str(), preview.collides))
+            .collect();
+        assert_eq!(planned, vec![("draft-a", "final-a", false), ("draft-b", "final-b", true)]);
+        assert!(temp_dir.path().join("draft-a.txt").exists());
+
+        manager.bulk_rename_by_pattern("^draft-(.*)$", "final-$1", false).unwrap();
+
+        assert!(temp_dir.path().join("final-a.txt").exists());
+        assert!(temp_dir.path().join("draft-b.txt").exists());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("final-b.txt")).unwrap(), "taken");
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Index.txt")).unwrap(),
+            "See [[final-a]]"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bulk_rename_continues_after_failures() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let locked_dir = temp_dir.path().join("locked");
+        fs::create_dir(&locked_dir).unwrap();
+        fs::write(locked_dir.join("draft-a.txt"), "a").unwrap();
+        manager.create_note("draft-b", "b", NoteType::PlainText, None).unwrap();
+
+        // Renaming inside a read-only folder fails
+        fs::set_permissions(&locked_dir, fs::Permissions::from_mode(0o555)).unwrap();
+        let result = manager.bulk_rename_by_pattern("^draft-(.*)$", "final-$1", false);
+        fs::set_permissions(&locked_dir, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let previews = result.unwrap();
+        if locked_dir.join("final-a.txt").exists() {
+            // Permissions are not enforced, e.g. when running as root
+            return;
+        }
+
+        let outcome: Vec<(&str, bool)> = previews
+            .iter()
+            .map(|preview| (preview.old_name.as_str(), preview.renamed()))
+            .collect();
+        assert_eq!(outcome, vec![("draft-a", false), ("draft-b", true)]);
+        assert!(previews[0].error.is_some());
+        assert!(temp_dir.path().join("final-b.txt").exists());
+    }
+
+    #[test]
+    fn test_bulk_rename_rejects_invalid_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("note", "", NoteType::PlainText, None).unwrap();
+
+        assert!(manager.bulk_rename_by_pattern("(", "x", true).is_err());
+        assert!(manager.bulk_rename_by_pattern("note", "a/b", true).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::notes::{NoteManager, NoteSummary};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long a file change is attributed to a write made through the NoteManager
+const OWN_WRITE_WINDOW: Duration = Duration::from_millis(500);
+
+impl NoteManager {
+    /// Clears the cached note summaries
+    ///
+    /// The next `list_notes` call reads every note from disk again. Use this
+    /// after changes made outside the NoteManager, e.g. by other applications.
+    pub fn invalidate_cache(&self) {
+        #[cfg(feature = "summary-cache")]
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    /// Gets all cached note summaries
+    ///
+    /// # Returns
+    /// The summaries, or None if the cache has not been filled or is disabled
+    pub(super) fn cached_summaries(&self) -> Option<Vec<NoteSummary>> {
+        #[cfg(feature = "summary-cache")]
+        if self.use_cache {
+            let cache = self.cache.lock().ok()?;
+            if !cache.is_empty() {
+                return Some(cache.values().cloned().collect());
+            }
+        }
+
+        None
+    }
+
+    /// Replaces the cached note summaries with a full listing
+    ///
+    /// # Parameters
+    /// * `summaries` - Summaries of all notes
+    #[cfg_attr(not(feature = "summary-cache"), allow(unused_variables))]
+    pub(super) fn fill_cache(&self, summaries: &[NoteSummary]) {
+        #[cfg(feature = "summary-cache")]
+        if self.use_cache {
+            if let Ok(mut cache) = self.cache.lock() {
+                *cache = summaries
+                    .iter()
+                    .map(|summary| (summary.id.clone(), summary.clone()))
+                    .collect();
+            }
+        }
+    }
+
+    /// Updates the cached summary of a note after it was written
+    ///
+    /// The written paths are remembered as own writes. The cache is not
+    /// updated while it is empty, since a partial cache would hide the notes
+    /// that are missing from it.
+    ///
+    /// # Parameters
+    /// * `old_id` - Previous ID of the note if it was renamed or moved
+    /// * `path` - Current path of the note
+    pub(super) fn cache_note(&self, old_id: Option<&str>, path: &Path) {
+        self.record_write(path);
+        if let Some(old_path) = old_id.and_then(|id| self.get_note_absolute_path(id).ok()) {
+            self.record_write(&old_path);
+        }
+
+        #[cfg(feature = "summary-cache")]
+        if self.use_cache {
+            // Read the summary before locking so the cache is never held during I/O
+            let summary = if self.is_listed_path(path) {
+                self.get_note_summary(path).ok()
+            } else {
+                None
+            };
+
+            let Ok(mut cache) = self.cache.lock() else {
+                return;
+            };
+
+            if cache.is_empty() {
+                return;
+            }
+
+            if let Some(old_id) = old_id {
+                cache.remove(old_id);
+            }
+
+            if let Some(summary) = summary {
+                cache.insert(summary.id.clone(), summary);
+            }
+        }
+    }
+
+    /// Removes the cached summary of a deleted note
+    ///
+    /// The note's path is remembered as an own write.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the deleted note
+    pub(super) fn uncache_note(&self, id: &str) {
+        if let Ok(path) = self.get_note_absolute_path(id) {
+            self.record_write(&path);
+        }
+
+        #[cfg(feature = "summary-cache")]
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.remove(id);
+        }
+    }
+
+    /// Checks whether a file was written through this NoteManager moments ago
+    ///
+    /// Used to ignore the file system events caused by the app's own writes.
+    ///
+    /// # Parameters
+    /// * `path` - Absolute path of the file
+    ///
+    /// # Returns
+    /// True if the file was written, moved or deleted within the last 500 ms
+    pub fn is_own_write(&self, path: &Path) -> bool {
+        let Ok(mut recent_writes) = self.recent_writes.lock() else {
+            return false;
+        };
+
+        recent_writes.retain(|_, written| written.elapsed() < OWN_WRITE_WINDOW);
+        recent_writes.contains_key(path)
+    }
+
+    /// Remembers that a file was written through this NoteManager
+    fn record_write(&self, path: &Path) {
+        if let Ok(mut recent_writes) = self.recent_writes.lock() {
+            recent_writes.insert(path.to_path_buf(), Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod own_write_tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_own_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("A", "a", NoteType::PlainText, None).unwrap();
+
+        assert!(manager.is_own_write(&temp_dir.path().join("A.txt")));
+        assert!(!manager.is_own_write(&temp_dir.path().join("B.txt")));
+
+        let renamed = manager.rename_note(&note.id, "B", true).unwrap();
+        assert!(manager.is_own_write(&temp_dir.path().join("B.txt")));
+        assert_eq!(renamed.title, "B");
+    }
+}
+
+#[cfg(all(test, feature = "summary-cache"))]
+mod tests {
+    use super::*;
+    use crate::notes::{NoteType, SortOption};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn titles(manager: &NoteManager) -> Vec<String> {
+        manager
+            .list_notes(Some(vec![SortOption::TitleAsc]))
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_cache_tracks_changes_made_through_manager() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("A", "a", NoteType::PlainText, None).unwrap();
+        manager.create_note("B", "b", NoteType::PlainText, None).unwrap();
+
+        // The first listing fills the cache
+        assert_eq!(titles(&manager), vec!["A", "B"]);
+
+        manager.create_note("C", "c", NoteType::PlainText, None).unwrap();
+        let renamed = manager.rename_note(&a.id, "D", true).unwrap();
+        assert_eq!(titles(&manager), vec!["B", "C", "D"]);
+
+        manager.trash_note(&renamed.id).unwrap();
+        assert_eq!(titles(&manager), vec!["B", "C"]);
+    }
+
+    #[test]
+    fn test_invalidate_cache_picks_up_external_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("A", "a", NoteType::PlainText, None).unwrap();
+        assert_eq!(titles(&manager), vec!["A"]);
+
+        fs::write(temp_dir.path().join("B.txt"), "b").unwrap();
+        assert_eq!(titles(&manager), vec!["A"]);
+
+        manager.invalidate_cache();
+        assert_eq!(titles(&manager), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_disabled_cache_picks_up_external_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_summary_cache(false);
+        manager.create_note("A", "a", NoteType::PlainText, None).unwrap();
+        assert_eq!(titles(&manager), vec!["A"]);
+
+        fs::write(temp_dir.path().join("B.txt"), "b").unwrap();
+        assert_eq!(titles(&manager), vec!["A", "B"]);
+    }
+}
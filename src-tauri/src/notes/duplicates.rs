@@ -0,0 +1,285 @@
+use crate::notes::{split_frontmatter, NoteManager, NoteSummary, NoteType};
+use anyhow::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Number of characters of normalized content compared for near-duplicates
+const NEAR_DUPLICATE_PREFIX_CHARS: usize = 500;
+
+/// Similarity threshold used when near-duplicates are requested without a threshold
+pub const DEFAULT_NEAR_DUPLICATE_SIMILARITY: f32 = 0.9;
+
+/// A group of notes with identical or near-identical content
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    /// The duplicate notes, ordered by ID
+    pub notes: Vec<NoteSummary>,
+    /// Lowest similarity between the first note and the others (1.0 for exact duplicates)
+    pub similarity: f32,
+}
+
+impl NoteManager {
+    /// Finds notes with identical or near-identical content
+    ///
+    /// Only notes of the same type are compared. With a threshold of 1.0, notes
+    /// are duplicates if their files have the same SHA-256 hash. Otherwise the
+    /// first 500 characters of the note bodies are normalized (frontmatter
+    /// removed, whitespace collapsed, lowercased) and their word sets compared;
+    /// a note joins the first group whose first note is at least as similar
+    /// as the threshold.
+    ///
+    /// # Parameters
+    /// * `similarity_threshold` - Minimum similarity between 0.0 and 1.0
+    ///
+    /// # Returns
+    /// Groups of at least two notes, ordered by the ID of their first note
+    pub fn find_duplicate_notes(&self, similarity_threshold: f32) -> Result<Vec<DuplicateGroup>> {
+        let mut groups = Vec::new();
+
+        if similarity_threshold >= 1.0 {
+            for notes in self.fingerprints_by_type(file_hash)?.into_values() {
+                groups.extend(exact_duplicates(notes));
+            }
+        } else {
+            for notes in self.fingerprints_by_type(file_prefix_words)?.into_values() {
+                groups.extend(near_duplicates(notes, similarity_threshold));
+            }
+        }
+
+        groups.sort_by(|a, b| a.notes[0].id.cmp(&b.notes[0].id));
+
+        Ok(groups)
+    }
+
+    /// Computes a fingerprint of every note, grouped by note type
+    ///
+    /// Notes that cannot be found or read are logged and skipped.
+    ///
+    /// # Parameters
+    /// * `fingerprint` - Computes the fingerprint of a note file
+    ///
+    /// # Returns
+    /// The notes with their fingerprints for each type, ordered by ID
+    fn fingerprints_by_type<T>(
+        &self,
+        fingerprint: fn(&Path) -> io::Result<T>,
+    ) -> Result<HashMap<NoteType, Vec<(NoteSummary, T)>>> {
+        let mut by_type: HashMap<NoteType, Vec<(NoteSummary, T)>> = HashMap::new();
+
+        for summary in self.list_notes(None)? {
+            let result = self
+                .get_note_path(&summary.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| Ok(fingerprint(&path)?));
+
+            match result {
+                Ok(value) => by_type.entry(summary.file_type.clone()).or_default().push((summary, value)),
+                Err(e) => log::warn!("Failed to read note {}: {}", summary.id, e),
+            }
+        }
+
+        for notes in by_type.values_mut() {
+            notes.sort_by(|(a, _), (b, _)| a.id.cmp(&b.id));
+        }
+
+        Ok(by_type)
+    }
+}
+
+/// Computes the SHA-256 hash of a file without reading it into memory at once
+fn file_hash(path: &Path) -> io::Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().into())
+}
+
+/// Gets the distinct words of the normalized beginning of a note's body
+fn file_prefix_words(path: &Path) -> io::Result<HashSet<String>> {
+    let content = fs::read(path)?;
+    let content = String::from_utf8_lossy(&content);
+    let (_, body) = split_frontmatter(&content);
+    Ok(prefix_words(body))
+}
+
+/// Groups notes whose files have the same SHA-256 hash
+fn exact_duplicates(notes: Vec<(NoteSummary, [u8; 32])>) -> Vec<DuplicateGroup> {
+    let mut by_hash: HashMap<[u8; 32], Vec<NoteSummary>> = HashMap::new();
+    let mut hashes = Vec::new();
+
+    for (summary, hash) in notes {
+        if !by_hash.contains_key(&hash) {
+            hashes.push(hash);
+        }
+        by_hash.entry(hash).or_default().push(summary);
+    }
+
+    hashes
+        .into_iter()
+        .filter_map(|hash| by_hash.remove(&hash))
+        .filter(|notes| notes.len() > 1)
+        .map(|notes| DuplicateGroup { notes, similarity: 1.0 })
+        .collect()
+}
+
+/// Groups notes whose normalized content prefixes are similar
+fn near_duplicates(notes: Vec<(NoteSummary, HashSet<String>)>, threshold: f32) -> Vec<DuplicateGroup> {
+    // Each group keeps the words of its first note, which the others are compared to
+    let mut groups: Vec<(HashSet<String>, DuplicateGroup)> = Vec::new();
+
+    for (summary, words) in notes {
+        let best = groups
+            .iter_mut()
+            .map(|(first_words, group)| (word_similarity(first_words, &words), group))
+            .find(|(similarity, _)| *similarity >= threshold);
+
+        match best {
+            Some((similarity, group)) => {
+                group.similarity = group.similarity.min(similarity);
+                group.notes.push(summary);
+            }
+            None => groups.push((
+                words,
+                DuplicateGroup {
+                    notes: vec![summary],
+                    similarity: 1.0,
+                },
+            )),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .filter(|group| group.notes.len() > 1)
+        .collect()
+}
+
+/// Gets the distinct words of the normalized beginning of note content
+///
+/// Only the first 500 characters of the normalized content are built.
+fn prefix_words(body: &str) -> HashSet<String> {
+    let mut normalized = String::new();
+    for word in body.split_whitespace() {
+        if normalized.chars().count() >= NEAR_DUPLICATE_PREFIX_CHARS {
+            break;
+        }
+
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        normalized.push_str(&word.to_lowercase());
+    }
+
+    normalized
+        .chars()
+        .take(NEAR_DUPLICATE_PREFIX_CHARS)
+        .collect::<String>()
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Computes the Jaccard similarity of two word sets
+///
+/// Two empty sets are identical.
+fn word_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+
+    a.intersection(b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn titles(group: &DuplicateGroup) -> Vec<&str> {
+        group.notes.iter().map(|note| note.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_find_exact_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "Same content").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "Same content").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "Same  content").unwrap();
+        // Notes of other types are never duplicates
+        fs::write(temp_dir.path().join("d.md"), "Same content").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let groups = manager.find_duplicate_notes(1.0).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(titles(&groups[0]), vec!["a", "b"]);
+        assert_eq!(groups[0].similarity, 1.0);
+    }
+
+    #[test]
+    fn test_find_near_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let text = "one two three four five six seven eight nine ten";
+        fs::write(temp_dir.path().join("a.txt"), text).unwrap();
+        fs::write(temp_dir.path().join("b.txt"), format!("  {}\n", text.to_uppercase())).unwrap();
+        fs::write(temp_dir.path().join("c.txt"), text.replace("ten", "eleven")).unwrap();
+        fs::write(temp_dir.path().join("d.txt"), "something else entirely").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let groups = manager.find_duplicate_notes(0.8).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(titles(&groups[0]), vec!["a", "b", "c"]);
+        // 9 shared words out of 11
+        assert!((groups[0].similarity - 9.0 / 11.0).abs() < 1e-6);
+
+        let groups = manager.find_duplicate_notes(0.9).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(titles(&groups[0]), vec!["a", "b"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_duplicates_skips_unreadable_notes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "Same content").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "Same content").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "Same content").unwrap();
+        let locked = temp_dir.path().join("c.txt");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let groups = manager.find_duplicate_notes(1.0);
+        let readable = fs::read(&locked).is_ok();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let groups = groups.unwrap();
+        if readable {
+            // Permissions are not enforced, e.g. when running as root
+            return;
+        }
+        assert_eq!(groups.len(), 1);
+        assert_eq!(titles(&groups[0]), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_near_duplicates_only_compare_content_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let prefix = "word ".repeat(NEAR_DUPLICATE_PREFIX_CHARS);
+        fs::write(temp_dir.path().join("a.txt"), format!("{}first ending", prefix)).unwrap();
+        fs::write(temp_dir.path().join("b.txt"), format!("{}second ending", prefix)).unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        assert_eq!(manager.find_duplicate_notes(0.99).unwrap().len(), 1);
+        assert!(manager.find_duplicate_notes(1.0).unwrap().is_empty());
+    }
+}
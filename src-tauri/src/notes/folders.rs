@@ -0,0 +1,434 @@
+use crate::notes::{file_contains_line, Note, NoteManager, NoteSummary, RESERVED_DIRS};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Outcome of moving several notes into a folder
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchMoveResult {
+    /// The moved notes with their new IDs
+    pub moved: Vec<Note>,
+    /// ID and error message of every note that could not be moved
+    pub failed: Vec<(String, String)>,
+}
+
+impl NoteManager {
+    /// Creates a folder inside the notes directory
+    ///
+    /// Missing parent folders are created as well.
+    ///
+    /// # Parameters
+    /// * `relative_path` - Path of the folder relative to the notes directory
+    ///
+    /// # Returns
+    /// The absolute path of the folder
+    pub fn create_folder(&self, relative_path: &str) -> Result<PathBuf> {
+        let path = self.resolve_folder_path(relative_path)?;
+
+        fs::create_dir_all(&path).context("Failed to create folder")?;
+
+        Ok(path)
+    }
+
+    /// Deletes a folder inside the notes directory
+    ///
+    /// Non-empty folders are only deleted when `force` is set. In that case all
+    /// notes inside the folder (including subfolders) are first moved to the
+    /// root of the notes directory. Folders holding other files, such as
+    /// attachments, are never deleted.
+    ///
+    /// # Parameters
+    /// * `relative_path` - Path of the folder relative to the notes directory
+    /// * `force` - Whether to delete a non-empty folder
+    ///
+    /// # Returns
+    /// The previous ID and the moved note for every note moved to the root
+    pub fn delete_folder(&self, relative_path: &str, force: bool) -> Result<Vec<(String, Note)>> {
+        let path = self.resolve_folder_path(relative_path)?;
+
+        if !path.is_dir() {
+            anyhow::bail!("Folder not found: {}", relative_path);
+        }
+
+        let is_empty = fs::read_dir(&path)
+            .context("Failed to read folder")?
+            .next()
+            .is_none();
+
+        if !is_empty && !force {
+            anyhow::bail!("Folder is not empty: {}", relative_path);
+        }
+
+        let mut note_paths = Vec::new();
+        let mut other_files = Vec::new();
+        for entry in WalkDir::new(&path) {
+            let entry = entry.context("Failed to read folder")?;
+            if entry.file_type().is_dir() {
+                continue;
+            }
+
+            if entry.file_type().is_file() && self.has_note_extension(entry.path()) {
+                note_paths.push(entry.into_path());
+            } else {
+                other_files.push(entry.into_path());
+            }
+        }
+
+        // Files that are not notes never show up in the app, so they are not deleted silently
+        if let Some(other_file) = other_files.first() {
+            anyhow::bail!(
+                "Folder contains {} file(s) that are not notes, e.g. {}",
+                other_files.len(),
+                other_file.strip_prefix(&path).unwrap_or(other_file).display()
+            );
+        }
+
+        // Check for name clashes first so no note is moved if any would be overwritten
+        let mut targets = Vec::with_capacity(note_paths.len());
+        for note_path in &note_paths {
+            let file_name = note_path.file_name().context("Invalid note file name")?;
+            let target = self.notes_dir.join(file_name);
+
+            if target.exists() || targets.contains(&target) {
+                anyhow::bail!(
+                    "A note named {} already exists in the notes directory",
+                    file_name.to_string_lossy()
+                );
+            }
+
+            targets.push(target);
+        }
+
+        let mut moved_notes = Vec::with_capacity(note_paths.len());
+        for (note_path, target) in note_paths.iter().zip(targets) {
+            let old_id = self.path_to_id(note_path)?;
+            fs::rename(note_path, &target).context("Failed to move note out of folder")?;
+            self.cache_note(Some(&old_id), &target);
+            moved_notes.push((old_id, self.read_note(&target)?));
+        }
+
+        // Only empty folders are left
+        fs::remove_dir_all(&path).context("Failed to delete folder")?;
+
+        Ok(moved_notes)
+    }
+
+    /// Moves notes into a folder, keeping their file names
+    ///
+    /// Notes are moved one by one and links to notes whose title changes are
+    /// updated; a note that cannot be moved does not stop the others. A missing
+    /// destination folder is created only if automatic folder creation is enabled.
+    ///
+    /// # Parameters
+    /// * `ids` - IDs of the notes to move
+    /// * `destination_folder` - Folder relative to the notes directory, empty for the root
+    ///
+    /// # Returns
+    /// The moved notes and the IDs of notes that failed with the reason
+    pub fn move_notes_batch(&self, ids: &[&str], destination_folder: &str) -> Result<BatchMoveResult> {
+        let destination = self.resolve_relative_path(destination_folder)?;
+
+        if !destination.is_dir() {
+            if destination.exists() {
+                anyhow::bail!("Destination is not a folder: {}", destination_folder);
+            }
+            if !self.auto_create_folders {
+                anyhow::bail!("Folder not found: {}", destination_folder);
+            }
+
+            fs::create_dir_all(&destination).context("Failed to create folder")?;
+        }
+
+        let mut result = BatchMoveResult::default();
+
+        for id in ids {
+            match self.move_note_to_folder(id, destination_folder) {
+                Ok(note) => result.moved.push(note),
+                Err(e) => {
+                    log::warn!("Failed to move note {}: {}", id, e);
+                    result.failed.push((id.to_string(), e.to_string()));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Moves a single note into a folder and updates links to it
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// * `destination_folder` - Folder relative to the notes directory
+    ///
+    /// # Returns
+    /// The moved note
+    fn move_note_to_folder(&self, id: &str, destination_folder: &str) -> Result<Note> {
+        let current_path = self.get_note_path(id)?;
+        let file_name = current_path.file_name().context("Invalid note file name")?;
+        let new_relative_path = Path::new(destination_folder).join(file_name);
+
+        // Notes already in the folder are left as they are
+        if self.resolve_relative_path(&new_relative_path.to_string_lossy())? == current_path {
+            return self.read_note(&current_path);
+        }
+
+        let original = self.get_note(id)?;
+        let moved = self.move_note(id, &new_relative_path.to_string_lossy())?;
+
+        // Plain text titles come from the file name, so a move can rename the note
+        if original.title != moved.title {
+            if let Err(e) = self.update_backlinks(&original.title, &moved.title) {
+                log::warn!("Failed to update backlinks of {}: {}", moved.id, e);
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Copies a note into another notes directory
+    ///
+    /// The copy is created at the root of the destination with the same file
+    /// name, content and type. Notes are never overwritten.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to copy
+    /// * `destination_dir` - Note manager of the destination notes directory
+    ///
+    /// # Returns
+    /// The created note in the destination
+    pub fn copy_note_to_folder(&self, id: &str, destination_dir: &NoteManager) -> Result<Note> {
+        let note = self.get_note(id)?;
+        let path = self.get_note_path(id)?;
+
+        // The file name is kept; Markdown and Org titles come from the content
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .context("Invalid note file name")?;
+
+        destination_dir.create_note(name, &note.content, note.file_type, None)
+    }
+
+    /// Lists the notes inside a folder
+    ///
+    /// Notes in subfolders are included.
+    ///
+    /// # Parameters
+    /// * `folder` - Path of the folder relative to the notes directory, empty for all notes
+    ///
+    /// # Returns
+    /// Summaries of the notes in listing order
+    pub fn list_notes_in_folder(&self, folder: &str) -> Result<Vec<NoteSummary>> {
+        let folder_path = self.resolve_relative_path(folder)?;
+        if !folder_path.is_dir() {
+            anyhow::bail!("Folder not found: {}", folder);
+        }
+
+        let mut notes = self.list_notes(None)?;
+        notes.retain(|summary| {
+            self.get_note_absolute_path(&summary.id)
+                .map_or(false, |path| path.starts_with(&folder_path))
+        });
+
+        Ok(notes)
+    }
+
+    /// Finds notes inside a folder whose content contains a query
+    ///
+    /// Reads the notes like `find_notes_by_content_contains`, ignoring
+    /// letter case, so it works without the search index.
+    ///
+    /// # Parameters
+    /// * `folder` - Path of the folder relative to the notes directory, empty for all notes
+    /// * `query` - Text to look for
+    ///
+    /// # Returns
+    /// Summaries of the matching notes in listing order
+    pub fn search_notes_in_path(&self, folder: &str, query: &str) -> Result<Vec<NoteSummary>> {
+        let mut notes = self.list_notes_in_folder(folder)?;
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let needle = query.to_lowercase();
+        notes.retain(|summary| {
+            self.get_note_absolute_path(&summary.id)
+                .map_or(false, |path| file_contains_line(&path, &needle, false))
+        });
+
+        Ok(notes)
+    }
+
+    /// Resolves a folder path, rejecting the notes directory itself and reserved folders
+    ///
+    /// # Parameters
+    /// * `relative_path` - Path of the folder relative to the notes directory
+    ///
+    /// # Returns
+    /// The absolute path inside the notes directory
+    fn resolve_folder_path(&self, relative_path: &str) -> Result<PathBuf> {
+        let path = self.resolve_relative_path(relative_path)?;
+
+        let relative = path
+            .strip_prefix(&self.notes_dir)
+            .context("Path is not in notes directory")?;
+
+        let Some(first) = relative.components().next() else {
+            anyhow::bail!("Folder path must not be empty");
+        };
+
+        if RESERVED_DIRS.contains(&first.as_os_str().to_string_lossy().as_ref()) {
+            anyhow::bail!("Folder is reserved for application data");
+        }
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let path = manager.create_folder("projects/2024").unwrap();
+
+        assert_eq!(path, temp_dir.path().join("projects").join("2024"));
+        assert!(path.is_dir());
+        assert!(manager.create_folder("../outside").is_err());
+        assert!(manager.create_folder("").is_err());
+        assert!(manager.create_folder(".trash/nested").is_err());
+    }
+
+    #[test]
+    fn test_delete_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_folder("empty").unwrap();
+        manager.create_folder("full/nested").unwrap();
+        fs::write(temp_dir.path().join("full").join("a.md"), "# A").unwrap();
+        fs::write(temp_dir.path().join("full").join("nested").join("b.txt"), "b").unwrap();
+
+        manager.delete_folder("empty", false).unwrap();
+        assert!(!temp_dir.path().join("empty").exists());
+
+        assert!(manager.delete_folder("full", false).is_err());
+        assert!(manager.delete_folder("../", true).is_err());
+
+        let moved = manager.delete_folder("full", true).unwrap();
+
+        assert_eq!(moved.len(), 2);
+        assert!(!temp_dir.path().join("full").exists());
+        assert!(temp_dir.path().join("a.md").exists());
+        assert!(temp_dir.path().join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_move_notes_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("a", "a", NoteType::PlainText, None).unwrap();
+        let b = manager.create_note("b", "b", NoteType::PlainText, None).unwrap();
+        manager.create_folder("archive").unwrap();
+        fs::write(temp_dir.path().join("archive").join("b.txt"), "taken").unwrap();
+
+        let result = manager
+            .move_notes_batch(&[&a.id, &b.id, "missing.txt"], "archive/2024")
+            .unwrap();
+        assert_eq!(result.moved.len(), 2);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, "missing.txt");
+        assert!(temp_dir.path().join("archive").join("2024").join("a.txt").exists());
+
+        let moved_b = &result.moved[1];
+        let result = manager.move_notes_batch(&[&moved_b.id], "archive").unwrap();
+        assert!(result.moved.is_empty());
+        assert_eq!(result.failed.len(), 1);
+        assert!(temp_dir.path().join("archive").join("2024").join("b.txt").exists());
+
+        assert!(manager.move_notes_batch(&[&moved_b.id], "../outside").is_err());
+
+        let manager = manager.with_auto_create_folders(false);
+        assert!(manager.move_notes_batch(&[&moved_b.id], "new").is_err());
+    }
+
+    #[test]
+    fn test_copy_note_to_folder() {
+        let source_dir = TempDir::new().unwrap();
+        let destination_dir = TempDir::new().unwrap();
+        let source = NoteManager::new(source_dir.path().to_path_buf());
+        let destination = NoteManager::new(destination_dir.path().to_path_buf());
+        let note = source.create_note("file-name", "# Title\n\nBody", NoteType::Markdown, None).unwrap();
+
+        let copy = source.copy_note_to_folder(&note.id, &destination).unwrap();
+
+        assert_eq!(copy.title, "Title");
+        assert_eq!(copy.content, note.content);
+        assert_eq!(copy.file_type, NoteType::Markdown);
+        assert!(destination_dir.path().join("file-name.md").exists());
+        assert!(source_dir.path().join("file-name.md").exists());
+
+        assert!(source.copy_note_to_folder(&note.id, &destination).is_err());
+    }
+
+    #[test]
+    fn test_delete_folder_keeps_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_folder("full/images").unwrap();
+        fs::write(temp_dir.path().join("full").join("a.md"), "# A").unwrap();
+        fs::write(temp_dir.path().join("full/images").join("photo.png"), "png").unwrap();
+
+        assert!(manager.delete_folder("full", true).is_err());
+        assert!(temp_dir.path().join("full").join("a.md").exists());
+        assert!(temp_dir.path().join("full/images").join("photo.png").exists());
+    }
+
+    #[test]
+    fn test_delete_folder_refuses_to_overwrite_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_folder("full").unwrap();
+        fs::write(temp_dir.path().join("a.md"), "# Root").unwrap();
+        fs::write(temp_dir.path().join("full").join("a.md"), "# Nested").unwrap();
+
+        assert!(manager.delete_folder("full", true).is_err());
+        assert!(temp_dir.path().join("full").join("a.md").exists());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("a.md")).unwrap(), "# Root");
+    }
+
+    #[test]
+    fn test_search_notes_in_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_folder("projects/rust").unwrap();
+        manager.create_folder("projects-old").unwrap();
+        fs::write(temp_dir.path().join("root.txt"), "Tantivy at the root").unwrap();
+        fs::write(temp_dir.path().join("projects").join("a.txt"), "Uses TANTIVY").unwrap();
+        fs::write(temp_dir.path().join("projects").join("b.txt"), "Something else").unwrap();
+        fs::write(temp_dir.path().join("projects/rust").join("c.txt"), "tantivy index").unwrap();
+        fs::write(temp_dir.path().join("projects-old").join("d.txt"), "tantivy").unwrap();
+
+        let titles = |notes: Vec<NoteSummary>| {
+            let mut titles: Vec<String> = notes.into_iter().map(|note| note.title).collect();
+            titles.sort();
+            titles
+        };
+
+        assert_eq!(titles(manager.list_notes_in_folder("projects").unwrap()), vec!["a", "b", "c"]);
+        assert_eq!(titles(manager.search_notes_in_path("projects", "tantivy").unwrap()), vec!["a", "c"]);
+        assert_eq!(titles(manager.search_notes_in_path("projects/rust/", "TANTIVY").unwrap()), vec!["c"]);
+        assert_eq!(manager.search_notes_in_path("", "tantivy").unwrap().len(), 4);
+        assert!(manager.search_notes_in_path("projects", "").unwrap().is_empty());
+
+        assert!(manager.search_notes_in_path("missing", "tantivy").is_err());
+        assert!(manager.search_notes_in_path("../outside", "tantivy").is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::notes::{Note, NoteManager};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Name of the directory (inside the notes directory) holding application data
+pub(crate) const APP_DIR: &str = ".notter";
+
+/// Name of the history directory inside the application data directory
+const HISTORY_DIR: &str = "history";
+
+/// Extension used for history snapshots
+const SNAPSHOT_EXTENSION: &str = "bak";
+
+/// Default number of history snapshots kept per note
+pub(crate) const DEFAULT_MAX_HISTORY_VERSIONS: u32 = 50;
+
+/// A saved snapshot of a note's content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteVersion {
+    /// When the snapshot was taken
+    pub timestamp: DateTime<Utc>,
+    /// Size of the snapshot in bytes
+    pub size_bytes: u64,
+}
+
+impl NoteManager {
+    /// Saves the current content of a note as a history snapshot
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to snapshot
+    ///
+    /// # Returns
+    /// The saved version
+    pub fn save_version(&self, id: &str) -> Result<NoteVersion> {
+        let path = self.get_note_path(id)?;
+
+        let history_dir = self.history_dir(id);
+        fs::create_dir_all(&history_dir).context("Failed to create history directory")?;
+
+        // Snapshots are named by timestamp; a later millisecond is used if one is taken
+        let mut millis = Utc::now().timestamp_millis();
+        while self.snapshot_path(id, millis).exists() {
+            millis += 1;
+        }
+        let timestamp = DateTime::<Utc>::from_timestamp_millis(millis)
+            .context("Invalid snapshot timestamp")?;
+
+        let size_bytes = fs::copy(&path, self.snapshot_path(id, millis))
+            .context("Failed to save note version")?;
+
+        self.prune_history(id)?;
+
+        Ok(NoteVersion {
+            timestamp,
+            size_bytes,
+        })
+    }
+
+    /// Gets the saved versions of a note
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    ///
+    /// # Returns
+    /// Saved versions, newest first
+    pub fn get_note_history(&self, id: &str) -> Result<Vec<NoteVersion>> {
+        let history_dir = self.history_dir(id);
+        if !history_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut versions = Vec::new();
+
+        for entry in fs::read_dir(&history_dir).context("Failed to read history directory")? {
+            let path = entry.context("Failed to read history entry")?.path();
+
+            if path.extension().map_or(true, |ext| ext != SNAPSHOT_EXTENSION) {
+                continue;
+            }
+
+            let Some(timestamp) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<i64>().ok())
+                .and_then(DateTime::<Utc>::from_timestamp_millis)
+            else {
+                continue;
+            };
+
+            let size_bytes = path
+                .metadata()
+                .context("Failed to read snapshot metadata")?
+                .len();
+
+            versions.push(NoteVersion {
+                timestamp,
+                size_bytes,
+            });
+        }
+
+        versions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+        Ok(versions)
+    }
+
+    /// Restores a note to a previously saved version
+    ///
+    /// The current content is snapshotted first, so a restore can itself be undone.
+    /// The restored version is read beforehand, since that snapshot may be pruned.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to restore
+    /// * `timestamp` - Timestamp of the version to restore
+    ///
+    /// # Returns
+    /// The restored note
+    pub fn restore_note_version(&self, id: &str, timestamp: DateTime<Utc>) -> Result<Note> {
+        let path = self.get_note_path(id)?;
+
+        let snapshot_path = self.snapshot_path(id, timestamp.timestamp_millis());
+
+        if !snapshot_path.exists() {
+            anyhow::bail!("Note version not found: {}", timestamp);
+        }
+
+        let content = fs::read(&snapshot_path).context("Failed to read note version")?;
+
+        self.save_version(id)?;
+
+        fs::write(&path, content).context("Failed to restore note version")?;
+        self.cache_note(None, &path);
+
+        self.read_note(&path)
+    }
+
+    /// Moves the history of notes whose IDs changed to their new IDs
+    ///
+    /// Snapshots already present under a new ID are kept.
+    ///
+    /// # Parameters
+    /// * `new_ids` - New IDs keyed by the old IDs
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn migrate_history(&self, new_ids: &HashMap<String, String>) -> Result<()> {
+        for (old_id, new_id) in new_ids {
+            let old_dir = self.history_dir(old_id);
+            if !old_dir.exists() {
+                continue;
+            }
+
+            let new_dir = self.history_dir(new_id);
+            if !new_dir.exists() {
+                fs::rename(&old_dir, &new_dir).context("Failed to move note history")?;
+                continue;
+            }
+
+            for entry in fs::read_dir(&old_dir).context("Failed to read history directory")? {
+                let path = entry.context("Failed to read history entry")?.path();
+                let Some(file_name) = path.file_name() else {
+                    continue;
+                };
+
+                let target = new_dir.join(file_name);
+                if !target.exists() {
+                    fs::rename(&path, &target).context("Failed to move note version")?;
+                }
+            }
+
+            fs::remove_dir_all(&old_dir).context("Failed to remove old history directory")?;
+            self.prune_history(new_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the oldest snapshots beyond the configured maximum
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    fn prune_history(&self, id: &str) -> Result<()> {
+        let versions = self.get_note_history(id)?;
+
+        // The newest snapshot is always kept, even with a hand-edited limit of 0
+        let max_versions = self.max_history_versions.max(1) as usize;
+        for version in versions.iter().skip(max_versions) {
+            let snapshot_path = self.snapshot_path(id, version.timestamp.timestamp_millis());
+            fs::remove_file(snapshot_path).context("Failed to prune note history")?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the path of a history snapshot
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// * `millis` - Timestamp of the snapshot in milliseconds
+    fn snapshot_path(&self, id: &str, millis: i64) -> PathBuf {
+        self.history_dir(id)
+            .join(format!("{}.{}", millis, SNAPSHOT_EXTENSION))
+    }
+
+    /// Gets the history directory for a note
+    ///
+    /// Note IDs are standard base64 and may contain `/`, so they are mapped to
+    /// the URL-safe alphabet to get a single path component.
+    fn history_dir(&self, id: &str) -> PathBuf {
+        let dir_name: String = id
+            .chars()
+            .map(|c| match c {
+                '/' => '_',
+                '+' => '-',
+                other => other,
+            })
+            .collect();
+
+        self.notes_dir.join(APP_DIR).join(HISTORY_DIR).join(dir_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_list_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Note", "first", NoteType::PlainText, None).unwrap();
+
+        // Snapshots taken within the same millisecond get distinct timestamps
+        let first = manager.save_version(&note.id).unwrap();
+        let second = manager.save_version(&note.id).unwrap();
+        assert!(second.timestamp > first.timestamp);
+        assert_eq!(first.size_bytes, 5);
+
+        let history = manager.get_note_history(&note.id).unwrap();
+        let timestamps: Vec<_> = history.iter().map(|version| version.timestamp).collect();
+        assert_eq!(timestamps, vec![second.timestamp, first.timestamp]);
+    }
+
+    #[test]
+    fn test_restore_note_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Note", "first", NoteType::PlainText, None).unwrap();
+        let version = manager.save_version(&note.id).unwrap();
+        fs::write(temp_dir.path().join("Note.txt"), "second").unwrap();
+
+        let restored = manager.restore_note_version(&note.id, version.timestamp).unwrap();
+
+        assert_eq!(restored.content, "first");
+        // The replaced content was snapshotted as well
+        assert_eq!(manager.get_note_history(&note.id).unwrap().len(), 2);
+        assert!(manager.restore_note_version(&note.id, Utc::now() + chrono::Duration::days(1)).is_err());
+    }
+
+    #[test]
+    fn test_prune_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_max_history_versions(2);
+        let note = manager.create_note("Note", "content", NoteType::PlainText, None).unwrap();
+
+        let versions: Vec<_> = (0..3).map(|_| manager.save_version(&note.id).unwrap()).collect();
+
+        let timestamps: Vec<_> = manager
+            .get_note_history(&note.id)
+            .unwrap()
+            .iter()
+            .map(|version| version.timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![versions[2].timestamp, versions[1].timestamp]);
+
+        // A limit of 0 still keeps the snapshot just taken
+        let manager = manager.with_max_history_versions(0);
+        manager.save_version(&note.id).unwrap();
+        assert_eq!(manager.get_note_history(&note.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Note", "content", NoteType::PlainText, None).unwrap();
+        let version = manager.save_version(&note.id).unwrap();
+
+        let new_ids = HashMap::from([
+            (note.id.clone(), "new-id".to_string()),
+            ("missing".to_string(), "other-id".to_string()),
+        ]);
+        manager.migrate_history(&new_ids).unwrap();
+
+        assert!(manager.get_note_history(&note.id).unwrap().is_empty());
+        let history = manager.get_note_history("new-id").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].timestamp, version.timestamp);
+        assert!(manager.get_note_history("other-id").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_oldest_version_with_full_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_max_history_versions(2);
+        let note = manager.create_note("Note", "first", NoteType::PlainText, None).unwrap();
+        let oldest = manager.save_version(&note.id).unwrap();
+        fs::write(temp_dir.path().join("Note.txt"), "second").unwrap();
+        manager.save_version(&note.id).unwrap();
+        fs::write(temp_dir.path().join("Note.txt"), "third").unwrap();
+
+        // Snapshotting "third" prunes the restored version
+        let restored = manager.restore_note_version(&note.id, oldest.timestamp).unwrap();
+
+        assert_eq!(restored.content, "first");
+        assert_eq!(manager.get_note_history(&note.id).unwrap().len(), 2);
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
@@ -7,14 +8,42 @@ use walkdir::WalkDir;
 use base64::Engine;
 use natord::compare;
 use regex::Regex;
-#[cfg(target_os = "ios")]
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 
+mod bulk;
+mod cache;
+mod duplicates;
+mod folders;
+mod history;
+mod restructure;
 mod subnotes;
-pub use subnotes::SubnoteInfo;
+mod tags;
+mod templates;
+mod trash;
+mod words;
+pub use bulk::BulkRenamePreview;
+pub use duplicates::{DuplicateGroup, DEFAULT_NEAR_DUPLICATE_SIMILARITY};
+pub use folders::BatchMoveResult;
+pub use history::NoteVersion;
+pub(crate) use history::APP_DIR;
+pub use subnotes::{SubnoteInfo, ZettelNode};
+pub use tags::BatchUpdateResult;
+pub(crate) use tags::{inline_tags, remove_inline_tags};
+pub use templates::{builtin_template, builtin_templates, render_template, NoteTemplate};
+pub use trash::TrashedNote;
+
+/// Default maximum folder depth scanned for notes
+pub const DEFAULT_MAX_WALK_DEPTH: u32 = 20;
+
+/// Name of the conventional directory for archived notes
+const ARCHIVE_DIR: &str = ".archive";
+
+/// Directories inside the notes directory that never contain regular notes;
+/// they are always excluded from listing and indexing
+pub(crate) const RESERVED_DIRS: &[&str] = &[trash::TRASH_DIR, history::APP_DIR, ARCHIVE_DIR];
 
 /// Options for sorting notes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortOption {
     /// Sort by title alphabetically (A-Z)
     TitleAsc,
@@ -25,18 +54,42 @@ pub enum SortOption {
     /// Sort by creation date (oldest first)
     CreatedOldest,
     /// Sort by modification date (newest first)
+    #[default]
     ModifiedNewest,
     /// Sort by modification date (oldest first)
     ModifiedOldest,
+    /// Sort by word count (shortest first)
+    WordCountAsc,
+    /// Sort by word count (longest first)
+    WordCountDesc,
+    /// Sort by file size (smallest first)
+    SizeAsc,
+    /// Sort by file size (largest first)
+    SizeDesc,
+}
+
+/// How note IDs are assigned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdScheme {
+    /// IDs are the base64-encoded path relative to the notes directory, so
+    /// they change when a note is renamed or moved
+    #[default]
+    PathBased,
+    /// New Markdown notes get a random UUID stored as `id` in YAML
+    /// frontmatter, which stays the same when the note is renamed or moved.
+    /// Other notes keep path-based IDs.
+    Uuid,
 }
 
 /// Represents the type of a note file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NoteType {
     /// Markdown formatted note
     Markdown,
     /// Plain text note
     PlainText,
+    /// Emacs Org-mode note
+    Org,
 }
 
 /// Represents a note in the system
@@ -75,13 +128,268 @@ pub struct NoteSummary {
     pub tags: Vec<String>,
     /// Type of the note (markdown or plain text)
     pub file_type: NoteType,
+    /// Number of words in the note
+    pub word_count: u32,
+    /// Number of characters in the note, excluding line breaks
+    pub char_count: u32,
+    /// Estimated reading time in seconds
+    pub reading_time_secs: u32,
+    /// Size of the note file in bytes
+    pub size_bytes: u64,
+    /// Start of the note's text, without frontmatter and headings (only
+    /// populated by `list_notes_with_preview`)
+    pub preview: Option<String>,
+}
+
+/// Aggregated statistics for all notes in the vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultStats {
+    /// Number of notes in the vault
+    pub total_notes: usize,
+    /// Total number of words across all notes
+    pub total_words: u64,
+    /// Total number of characters across all notes
+    pub total_chars: u64,
+    /// Average estimated reading time per note in seconds
+    pub average_reading_time_secs: f64,
+    /// Number of tags across all notes, counting each note's tags separately
+    pub total_tags: usize,
+    /// Number of distinct tags
+    pub unique_tags: usize,
+    /// Number of notes per note type, e.g. "Markdown"
+    pub notes_by_type: HashMap<String, usize>,
+    /// Creation time of the oldest note
+    pub oldest_note: Option<DateTime<Utc>>,
+    /// Creation time of the newest note
+    pub newest_note: Option<DateTime<Utc>>,
+    /// Average file size of a note in bytes
+    pub average_note_size_bytes: f64,
+}
+
+/// Usage statistics for a tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagStats {
+    /// The tag
+    pub tag: String,
+    /// Number of notes with the tag
+    pub count: usize,
+    /// IDs of the notes with the tag
+    pub note_ids: Vec<String>,
+    /// Titles of the notes with the tag, in the same order as `note_ids`
+    pub note_titles: Vec<String>,
+}
+
+/// A folder in the notes directory tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderNode {
+    /// Name of the folder
+    pub name: String,
+    /// Path relative to the notes directory (empty for the root)
+    pub path: String,
+    /// Number of notes directly inside the folder
+    pub note_count: usize,
+    /// Subfolders, sorted by name
+    pub children: Vec<FolderNode>,
+}
+
+/// A note in the link graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// ID of the note
+    pub id: String,
+    /// Title of the note
+    pub title: String,
+    /// Tags associated with the note
+    pub tags: Vec<String>,
+}
+
+/// A directed wikilink between two notes in the link graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// ID of the note containing the link
+    pub source_id: String,
+    /// ID of the note the link points to
+    pub target_id: String,
+}
+
+/// Notes and the wikilinks between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphData {
+    /// All notes in the vault
+    pub nodes: Vec<GraphNode>,
+    /// Resolved links between notes
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A wikilink whose target does not resolve to any note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// ID of the note containing the link
+    pub source_note_id: String,
+    /// Title of the note containing the link
+    pub source_note_title: String,
+    /// Target of the link as written
+    pub broken_target: String,
+    /// One-based line number of the link, if known
+    pub line_number: Option<u32>,
+}
+
+/// A note related to another note, with its similarity score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedNote {
+    /// The related note
+    pub note: NoteSummary,
+    /// Similarity score (higher is more related)
+    pub score: f32,
+}
+
+/// Position of a page within a paginated listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageInfo {
+    /// Current page (1-indexed)
+    pub page: usize,
+    /// Maximum number of items per page
+    pub page_size: usize,
+    /// Total number of items across all pages
+    pub total_count: usize,
+    /// Total number of pages
+    pub total_pages: usize,
+}
+
+/// A page of note summaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedNotes {
+    /// Notes on the current page
+    pub notes: Vec<NoteSummary>,
+    /// Position of the page within all notes
+    pub page_info: PageInfo,
+}
+
+/// Summary of a batch import of notes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Number of files imported
+    pub imported: usize,
+    /// Number of files skipped because they already exist
+    pub skipped: usize,
+    /// Errors for files that could not be imported
+    pub errors: Vec<String>,
+    /// Paths of files left alone because a different note already exists there
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+}
+
+/// Results of the pre-flight checks for a notes directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirValidation {
+    /// Whether the path is an existing directory
+    pub is_dir: bool,
+    /// Whether the directory contents can be listed
+    pub can_read: bool,
+    /// Whether files can be created in the directory
+    pub can_write: bool,
+    /// Number of note files in the directory and its subdirectories
+    pub estimated_note_count: usize,
+    /// Total size of those note files in bytes
+    pub estimated_size_bytes: u64,
+    /// Whether the directory appears to be synced by Dropbox, iCloud or OneDrive
+    pub is_cloud_synced: bool,
+}
+
+/// Error returned when a new note would overwrite an existing one
+#[derive(Debug, thiserror::Error)]
+#[error("A note with this name already exists; try \"{suggested_name}\"")]
+pub struct NoteExistsError {
+    /// Title that gives a free file name with the same naming pattern
+    pub suggested_name: String,
 }
 
+/// Error resolving a note ID to its file
+#[derive(Debug, thiserror::Error)]
+pub enum NoteError {
+    /// No note exists with the ID
+    #[error("Note not found: {0}")]
+    NotFound(String),
+    /// The ID cannot be decoded
+    #[error("Invalid note ID: {0}")]
+    InvalidId(String),
+    /// The ID decodes to a path outside the notes directory
+    #[error("Note ID points outside the notes directory")]
+    PathEscape,
+    /// The file system could not be accessed
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+impl NoteError {
+    /// Gets a stable code identifying the kind of error, e.g. `NOT_FOUND`
+    pub fn code(&self) -> &'static str {
+        match self {
+            NoteError::NotFound(_) => "NOT_FOUND",
+            NoteError::InvalidId(_) => "INVALID_ID",
+            NoteError::PathEscape => "PATH_ESCAPE",
+            NoteError::IoError(_) => "IO_ERROR",
+        }
+    }
+}
+
+/// A note file that could not be loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteLoadError {
+    /// Path of the file
+    pub path: String,
+    /// Why the file could not be loaded
+    pub error: String,
+}
+
+/// Result of listing notes with the files that failed to load
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteListDiagnostics {
+    /// Summaries of the notes that were loaded
+    pub notes: Vec<NoteSummary>,
+    /// Files that could not be loaded
+    pub errors: Vec<NoteLoadError>,
+}
+
+/// Reading speed used to estimate reading time
+const WORDS_PER_MINUTE: u32 = 200;
+
+/// Number of bytes read from a note file to build its summary
+const SUMMARY_READ_LIMIT: u64 = 64 * 1024;
+
 /// Manages notes in the file system
 #[derive(Clone)]
 pub struct NoteManager {
     /// Base directory for notes
     notes_dir: PathBuf,
+    /// Maximum number of history snapshots kept per note
+    max_history_versions: u32,
+    /// Names of folders hidden from listing and indexing
+    excluded_folders: Vec<String>,
+    /// How IDs are assigned to new notes
+    id_scheme: IdScheme,
+    /// Whether `.org` files are listed as notes
+    include_org_files: bool,
+    /// Additional extensions (lowercase, without the dot) listed as plain text notes
+    custom_extensions: Vec<String>,
+    /// Whether symbolic links are followed when scanning for notes
+    follow_symlinks: bool,
+    /// Maximum folder depth below the notes directory scanned for notes
+    max_walk_depth: u32,
+    /// Whether missing destination folders are created when moving notes in bulk
+    auto_create_folders: bool,
+    /// Words left out of word frequencies in addition to common English words
+    custom_stop_words: Vec<String>,
+    /// Paths of notes with a frontmatter UUID, keyed by that UUID
+    uuid_paths: Arc<Mutex<HashMap<String, PathBuf>>>,
+    /// Summaries of all listed notes keyed by ID, filled by the first `list_notes` call
+    #[cfg(feature = "summary-cache")]
+    cache: Arc<Mutex<HashMap<String, NoteSummary>>>,
+    /// Paths written through this NoteManager and when, to tell them apart from external changes
+    recent_writes: Arc<Mutex<HashMap<PathBuf, std::time::Instant>>>,
+    /// Whether summaries are cached; only safe while external changes invalidate the cache
+    #[cfg_attr(not(feature = "summary-cache"), allow(dead_code))]
+    use_cache: bool,
     /// Flag indicating if running on iOS
     #[cfg(target_os = "ios")]
     is_ios: bool,
@@ -100,696 +408,3867 @@ impl NoteManager {
         {
             Self { 
                 notes_dir,
+                max_history_versions: history::DEFAULT_MAX_HISTORY_VERSIONS,
+                excluded_folders: Vec::new(),
+                id_scheme: IdScheme::default(),
+                include_org_files: false,
+                custom_extensions: Vec::new(),
+                follow_symlinks: true,
+                max_walk_depth: DEFAULT_MAX_WALK_DEPTH,
+                auto_create_folders: true,
+                custom_stop_words: Vec::new(),
+                uuid_paths: Arc::new(Mutex::new(HashMap::new())),
+                #[cfg(feature = "summary-cache")]
+                cache: Arc::new(Mutex::new(HashMap::new())),
+                use_cache: true,
+                recent_writes: Arc::new(Mutex::new(HashMap::new())),
                 is_ios: true,
             }
         }
         
         #[cfg(not(target_os = "ios"))]
         {
-            Self { notes_dir }
+            Self {
+                notes_dir,
+                max_history_versions: history::DEFAULT_MAX_HISTORY_VERSIONS,
+                excluded_folders: Vec::new(),
+                id_scheme: IdScheme::default(),
+                include_org_files: false,
+                custom_extensions: Vec::new(),
+                follow_symlinks: true,
+                max_walk_depth: DEFAULT_MAX_WALK_DEPTH,
+                auto_create_folders: true,
+                custom_stop_words: Vec::new(),
+                uuid_paths: Arc::new(Mutex::new(HashMap::new())),
+                #[cfg(feature = "summary-cache")]
+                cache: Arc::new(Mutex::new(HashMap::new())),
+                use_cache: true,
+                recent_writes: Arc::new(Mutex::new(HashMap::new())),
+            }
         }
     }
     
-    /// Lists all notes in the directory
+    /// Gets the directory containing the notes
+    pub fn notes_dir(&self) -> &Path {
+        &self.notes_dir
+    }
+    
+    /// Sets whether note summaries are cached between listings
+    /// 
+    /// The cache only sees changes made through the NoteManager, so it should
+    /// only be enabled while external changes are watched and invalidate it.
     /// 
     /// # Parameters
-    /// * `sort` - Optional sort option to determine the order of notes
+    /// * `enabled` - Whether to cache summaries
     /// 
     /// # Returns
-    /// A list of note summaries
-    pub fn list_notes(&self, sort: Option<SortOption>) -> Result<Vec<NoteSummary>> {
-        let mut notes = Vec::new();
-        
-        #[cfg(target_os = "ios")]
-        {
-            // On iOS, we need to be more careful with file system access
-            // and handle the case where the directory might not be accessible yet
-            if !self.notes_dir.exists() {
-                return Ok(Vec::new());
-            }
-        }
-        
-        for entry in WalkDir::new(&self.notes_dir)
-            .follow_links(true)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            
-            // Process markdown and txt files
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "md" || ext == "txt") {
-                if let Ok(note) = self.get_note_summary(path) {
-                    notes.push(note);
-                }
-            }
-        }
-        
-        // Apply sorting based on the provided option
-        match sort.unwrap_or(SortOption::ModifiedNewest) {
-            // Use natural sorting for title comparisons
-            SortOption::TitleAsc => notes.sort_by(|a, b| compare(&a.title, &b.title)),
-            SortOption::TitleDesc => notes.sort_by(|a, b| compare(&b.title, &a.title)),
-            SortOption::CreatedNewest => notes.sort_by(|a, b| b.created.cmp(&a.created)),
-            SortOption::CreatedOldest => notes.sort_by(|a, b| a.created.cmp(&b.created)),
-            SortOption::ModifiedNewest => notes.sort_by(|a, b| b.modified.cmp(&a.modified)),
-            SortOption::ModifiedOldest => notes.sort_by(|a, b| a.modified.cmp(&b.modified)),
-        }
-        
-        Ok(notes)
+    /// The NoteManager with the updated setting
+    pub fn with_summary_cache(mut self, enabled: bool) -> Self {
+        self.use_cache = enabled;
+        self
     }
     
-    /// Gets a note by ID
+    /// Sets the maximum number of history snapshots kept per note
     /// 
     /// # Parameters
-    /// * `id` - ID of the note to retrieve
+    /// * `max_versions` - Maximum number of snapshots
     /// 
     /// # Returns
-    /// The note if found
-    pub fn get_note(&self, id: &str) -> Result<Note> {
-        let path = self.get_note_path(id)?;
-        self.read_note(&path)
+    /// The NoteManager with the updated limit
+    pub fn with_max_history_versions(mut self, max_versions: u32) -> Self {
+        self.max_history_versions = max_versions;
+        self
     }
     
-    /// Determines the note type based on file extension
+    /// Sets the folders to hide from listing and indexing
     /// 
     /// # Parameters
-    /// * `path` - Path to the note file
+    /// * `folders` - Names of folders to exclude at any depth
     /// 
     /// # Returns
-    /// The note type (Markdown or PlainText)
-    fn get_note_type(&self, path: &Path) -> NoteType {
-        if path.extension().map_or(false, |ext| ext == "md") {
-            NoteType::Markdown
-        } else {
-            NoteType::PlainText
-        }
+    /// The NoteManager with the updated exclusions
+    pub fn with_excluded_folders(mut self, folders: Vec<String>) -> Self {
+        self.excluded_folders = folders;
+        self
     }
     
-    /// Extracts tags from note content
+    /// Sets whether `.org` files are listed as notes
     /// 
     /// # Parameters
-    /// * `content` - Note content to extract tags from
+    /// * `include` - Whether to list Org-mode files
     /// 
     /// # Returns
-    /// Vector of extracted tags
-    fn extract_tags(&self, content: &str) -> Vec<String> {
-        let mut tags = Vec::new();
-        
-        for line in content.lines() {
-            // Split line into words and find those starting with #
-            for word in line.split_whitespace() {
-                if word.starts_with("#") && word.len() > 1 {
-                    // Remove the # and any trailing punctuation
-                    let tag = word.trim_start_matches('#')
-                              .trim_end_matches(|c: char| !c.is_alphanumeric())
-                              .to_string();
-                    if !tag.is_empty() && !tags.contains(&tag) {
-                        tags.push(tag);
-                    }
-                }
-            }
-        }
-        
-        tags
+    /// The NoteManager with the updated setting
+    pub fn with_org_files(mut self, include: bool) -> Self {
+        self.include_org_files = include;
+        self
     }
     
-    /// Reads a note from a file
+    /// Sets additional file extensions to list as plain text notes
     /// 
     /// # Parameters
-    /// * `path` - Path to the note file
+    /// * `extensions` - Lowercase extensions without the leading dot
     /// 
     /// # Returns
-    /// The parsed note
-    fn read_note(&self, path: &Path) -> Result<Note> {
-        let content = fs::read_to_string(path)
-            .context("Failed to read note file")?;
-        
-        let file_type = self.get_note_type(path);
-        
-        // Extract title based on file type
-        let title = match file_type {
-            NoteType::Markdown => content.lines()
-                .next()
-                .map(|line| line.trim_start_matches('#').trim().to_string())
-                .unwrap_or_else(|| "Untitled Note".to_string()),
-            NoteType::PlainText => path.file_stem()
-                .and_then(|stem| stem.to_str())
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "Untitled Note".to_string()),
-        };
-        
-        // Extract tags from content
-        let tags = self.extract_tags(&content);
-        
-        // Get file metadata
-        let metadata = path.metadata()
-            .context("Failed to read file metadata")?;
-        
-        let created = metadata.created()
-            .map(|time| DateTime::<Utc>::from(time))
-            .unwrap_or_else(|_| Utc::now());
-        
-        let modified = metadata.modified()
-            .map(|time| DateTime::<Utc>::from(time))
-            .unwrap_or_else(|_| Utc::now());
-        
-        // Generate ID from file path
-        let id = self.path_to_id(path)?;
-        
-        // Get relative path from notes directory
-        let relative_path = path.strip_prefix(&self.notes_dir)
-            .context("Path is not in notes directory")?
-            .to_string_lossy()
-            .to_string();
-        
-        Ok(Note {
-            id,
-            title,
-            content,
-            created,
-            modified,
-            tags,
-            file_type,
-            path: relative_path,
-        })
+    /// The NoteManager with the updated extensions
+    pub fn with_custom_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.custom_extensions = extensions;
+        self
     }
     
-    /// Gets a summary of a note without loading full content
+    /// Sets how the notes directory is scanned
+    /// 
+    /// Symbolic link cycles are detected and skipped; the depth limit also
+    /// guards against very deep link chains.
     /// 
     /// # Parameters
-    /// * `path` - Path to the note file
+    /// * `follow_symlinks` - Whether to follow symbolic links
+    /// * `max_depth` - Maximum folder depth below the notes directory
     /// 
     /// # Returns
-    /// A summary of the note
-    fn get_note_summary(&self, path: &Path) -> Result<NoteSummary> {
-        let file_type = self.get_note_type(path);
-        
-        // For title and tags, we only need to read a portion of the file
-        // This is more efficient for large files
-        let (title, tags) = match file_type {
-            NoteType::Markdown => {
-                // For markdown files, read the first few lines to extract title and tags
-                let file = fs::File::open(path)
-                    .context("Failed to open note file")?;
-                let reader = std::io::BufReader::new(file);
-                let mut lines = Vec::new();
-                let mut line_count = 0;
-                
-                // Read up to 50 lines or until EOF
-                for line in std::io::BufRead::lines(reader) {
-                    if let Ok(line) = line {
-                        lines.push(line);
-                        line_count += 1;
-                        if line_count >= 50 {
-                            break;
-                        }
-                    }
-                }
-                
-                // Extract title from the first line
-                let title = lines.first()
-                    .map(|line| line.trim_start_matches('#').trim().to_string())
-                    .unwrap_or_else(|| "Untitled Note".to_string());
-                
-                // Extract tags from the first few lines
-                let content = lines.join("\n");
-                let tags = self.extract_tags(&content);
-                
-                (title, tags)
-            },
-            NoteType::PlainText => {
-                // For plain text files, use filename as title
-                let title = path.file_stem()
-                    .and_then(|stem| stem.to_str())
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| "Untitled Note".to_string());
-                
-                // For tags, read the first few lines
-                let file = fs::File::open(path)
-                    .context("Failed to open note file")?;
-                let reader = std::io::BufReader::new(file);
-                let mut content = String::new();
-                let mut line_count = 0;
-                
-                // Read up to 50 lines or until EOF
-                for line in std::io::BufRead::lines(reader) {
-                    if let Ok(line) = line {
-                        content.push_str(&line);
-                        content.push('\n');
-                        line_count += 1;
-                        if line_count >= 50 {
-                            break;
-                        }
-                    }
-                }
-                
-                let tags = self.extract_tags(&content);
-                
-                (title, tags)
-            }
-        };
-        
-        // Get file metadata
-        let metadata = path.metadata()
-            .context("Failed to read file metadata")?;
-        
-        let created = metadata.created()
-            .map(|time| DateTime::<Utc>::from(time))
-            .unwrap_or_else(|_| Utc::now());
-        
-        let modified = metadata.modified()
-            .map(|time| DateTime::<Utc>::from(time))
-            .unwrap_or_else(|_| Utc::now());
-        
-        // Generate ID from file path
-        let id = self.path_to_id(path)?;
-        
-        Ok(NoteSummary {
-            id,
-            title,
-            created,
-            modified,
-            tags,
-            file_type,
-        })
+    /// The NoteManager with the updated settings
+    pub fn with_walk_options(mut self, follow_symlinks: bool, max_depth: u32) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self.max_walk_depth = max_depth;
+        self
     }
     
-    /// Converts a note ID to a file path
+    /// Sets whether missing destination folders are created when moving notes in bulk
     /// 
     /// # Parameters
-    /// * `id` - ID of the note
+    /// * `enabled` - Whether to create missing folders
     /// 
     /// # Returns
-    /// Path to the note file
-    fn get_note_path(&self, id: &str) -> Result<PathBuf> {
-        // Decode the ID back to a relative path
-        let relative_path = base64::engine::general_purpose::STANDARD
-            .decode(id)
-            .context("Failed to decode note ID")?;
-        
-        let relative_path = String::from_utf8(relative_path)
-            .context("Invalid UTF-8 in note ID")?;
+    /// The NoteManager with the updated setting
+    pub fn with_auto_create_folders(mut self, enabled: bool) -> Self {
+        self.auto_create_folders = enabled;
+        self
+    }
+    
+    /// Sets the words left out of word frequencies
+    /// 
+    /// # Parameters
+    /// * `words` - Words to ignore in addition to common English words
+    /// 
+    /// # Returns
+    /// The NoteManager with the updated stop words
+    pub fn with_custom_stop_words(mut self, words: Vec<String>) -> Self {
+        self.custom_stop_words = words;
+        self
+    }
+    
+    /// Sets how IDs are assigned to new notes
+    /// 
+    /// # Parameters
+    /// * `id_scheme` - ID scheme to use
+    /// 
+    /// # Returns
+    /// The NoteManager with the updated ID scheme
+    pub fn with_id_scheme(mut self, id_scheme: IdScheme) -> Self {
+        self.id_scheme = id_scheme;
+        self
+    }
+    
+    /// Rebuilds the cache mapping frontmatter UUIDs to note paths
+    /// 
+    /// Does nothing unless the UUID ID scheme is in use.
+    pub fn rebuild_id_cache(&self) {
+        if self.id_scheme != IdScheme::Uuid {
+            return;
+        }
         
-        let path = self.notes_dir.join(relative_path);
+        let uuid_paths: HashMap<String, PathBuf> = self
+            .frontmatter_id_paths()
+            .into_iter()
+            .collect();
         
-        if !path.exists() {
-            anyhow::bail!("Note not found: {}", id);
+        if let Ok(mut cache) = self.uuid_paths.lock() {
+            *cache = uuid_paths;
         }
-        
-        Ok(path)
     }
     
-    /// Updates the content of a note
+    /// Gets the IDs that change when switching to another ID scheme
+    /// 
+    /// Only Markdown notes with a frontmatter UUID have different IDs under
+    /// the two schemes.
     /// 
     /// # Parameters
-    /// * `id` - ID of the note to update
-    /// * `content` - New content for the note
+    /// * `id_scheme` - ID scheme that is about to be used
     /// 
     /// # Returns
-    /// The updated note
-    pub fn update_note_content(&self, id: &str, content: &str) -> Result<Note> {
-        // Get the file path from the ID
-        let path = self.get_note_path(id)?;
-        
-        // Write the new content to the file
-        fs::write(&path, content)
-            .context("Failed to write note content")?;
+    /// The new IDs keyed by the current IDs
+    pub fn id_scheme_changes(&self, id_scheme: IdScheme) -> HashMap<String, String> {
+        if id_scheme == self.id_scheme {
+            return HashMap::new();
+        }
         
-        // Return the updated note
-        self.read_note(&path)
+        self.frontmatter_id_paths()
+            .into_iter()
+            .filter_map(|(uuid, path)| {
+                let path_id = self.path_based_id(&path).ok()?;
+                Some(match id_scheme {
+                    IdScheme::Uuid => (path_id, uuid),
+                    IdScheme::PathBased => (uuid, path_id),
+                })
+            })
+            .collect()
     }
     
-    /// Renames a note file
+    /// Finds all Markdown notes with a UUID in their frontmatter
+    /// 
+    /// # Returns
+    /// Pairs of frontmatter UUID and note path
+    fn frontmatter_id_paths(&self) -> Vec<(String, PathBuf)> {
+        self.walk_notes_dir()
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && self.has_note_extension(entry.path())
+                    && self.get_note_type(entry.path()) == NoteType::Markdown
+            })
+            .filter_map(|entry| read_frontmatter_id(entry.path()).map(|id| (id, entry.into_path())))
+            .collect()
+    }
+    
+    /// Looks up the path of a note in the UUID cache
     /// 
     /// # Parameters
-    /// * `id` - ID of the note to rename
-    /// * `new_name` - New name for the note file (without extension)
+    /// * `id` - Frontmatter UUID of the note
     /// 
     /// # Returns
-    /// The updated note with new ID
-    pub fn rename_note(&self, id: &str, new_name: &str) -> Result<Note> {
-        // Get the current file path from the ID
-        let current_path = self.get_note_path(id)?;
-        
-        // Get the file extension
-        let extension = current_path.extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("txt");
-        
-        // Get the parent directory
-        let parent_dir = current_path.parent()
-            .unwrap_or_else(|| Path::new(""));
-        
-        // Create the new path with the new name and same extension
-        let new_path = parent_dir.join(format!("{}.{}", new_name, extension));
-        
-        // Get the current filename without extension
-        let current_name = current_path.file_stem()
-            .and_then(|stem| stem.to_str())
-            .unwrap_or("");
-        
-        // Check if the only difference is case (case-insensitive comparison)
-        let case_only_difference = current_name.to_lowercase() == new_name.to_lowercase() && current_name != new_name;
-        
-        // Check if the new path already exists and it's not just a case difference
-        if new_path.exists() && !case_only_difference {
-            anyhow::bail!("A file with this name already exists");
+    /// The cached path if it still exists
+    fn cached_uuid_path(&self, id: &str) -> Option<PathBuf> {
+        self.uuid_paths
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(id).cloned())
+            .filter(|path| path.exists())
+    }
+    
+    /// Lists all notes in the directory
+    /// 
+    /// # Parameters
+    /// * `sort` - Optional sort options, applied left to right so later options
+    ///   break ties of earlier ones; notes are sorted by modification date (newest
+    ///   first) if omitted
+    /// 
+    /// # Returns
+    /// A list of note summaries
+    pub fn list_notes(&self, sort: Option<Vec<SortOption>>) -> Result<Vec<NoteSummary>> {
+        #[cfg(target_os = "ios")]
+        {
+            // On iOS, we need to be more careful with file system access
+            // and handle the case where the directory might not be accessible yet
+            if !self.notes_dir.exists() {
+                return Ok(Vec::new());
+            }
         }
         
-        // If it's only a case difference, use a two-step rename process
-        if case_only_difference {
-            // Create a temporary path with a unique name
-            let timestamp = chrono::Utc::now().timestamp();
-            let temp_path = parent_dir.join(format!("temp_rename_{}_{}.{}", timestamp, new_name, extension));
-            
-            // Step 1: Rename to temporary path
-            fs::rename(&current_path, &temp_path)
-                .context("Failed to rename note file to temporary path")?;
-            
-            // Step 2: Rename from temporary path to new path
-            fs::rename(&temp_path, &new_path)
-                .context("Failed to rename note file from temporary path")?;
+        let mut notes = if let Some(cached) = self.cached_summaries() {
+            cached
         } else {
-            // Regular rename for non-case-only changes
-            fs::rename(&current_path, &new_path)
-                .context("Failed to rename note file")?;
-        }
+            // Unreadable files are skipped; list_notes_with_errors reports them
+            let (notes, _) = self.scan_note_summaries();
+            self.fill_cache(&notes);
+            notes
+        };
         
-        // Return the updated note
-        self.read_note(&new_path)
+        sort_summaries(&mut notes, sort.as_deref().unwrap_or_default());
+        
+        Ok(notes)
     }
     
-    /// Moves a note to a different path
+    /// Lists all notes in the directory along with the files that failed to load
+    /// 
+    /// Unlike `list_notes`, the notes directory is always read from disk.
     /// 
     /// # Parameters
-    /// * `id` - ID of the note to move
-    /// * `new_path` - New relative path for the note (including filename)
+    /// * `sort` - Optional sort options, applied left to right so later options
+    ///   break ties of earlier ones
     /// 
     /// # Returns
-    /// The updated note with new ID
-    pub fn move_note(&self, id: &str, new_relative_path: &str) -> Result<Note> {
-        // Get the current file path from the ID
-        let current_path = self.get_note_path(id)?;
-        
-        // Prevent directory traversal by normalizing the path and ensuring it remains inside notes_dir
-        let mut normalized = PathBuf::new();
-        for comp in Path::new(new_relative_path).components() {
-            match comp {
-                std::path::Component::ParentDir => {
-                    anyhow::bail!("Invalid target path");
-                }
-                std::path::Component::CurDir => {}
-                other => normalized.push(other.as_os_str()),
-            }
-        }
-        let new_path = self.notes_dir.join(normalized);
-
-        // Ensure the resulting path is still within the notes directory
-        if !new_path.starts_with(&self.notes_dir) {
-            anyhow::bail!("Target path is outside notes directory");
-        }
+    /// The summaries of all readable notes, and the path and error of every unreadable one
+    pub fn list_notes_with_errors(&self, sort: Option<Vec<SortOption>>) -> (Vec<NoteSummary>, Vec<(PathBuf, anyhow::Error)>) {
+        let (mut notes, errors) = self.scan_note_summaries();
         
-        // Ensure the parent directory exists
-        if let Some(parent) = new_path.parent() {
-            fs::create_dir_all(parent)
-                .context("Failed to create parent directories")?;
+        if errors.is_empty() {
+            self.fill_cache(&notes);
         }
         
-        // Check if the paths are the same except for case
-        let case_only_difference = current_path.to_string_lossy().to_lowercase() == 
-                                  new_path.to_string_lossy().to_lowercase() && 
-                                  current_path != new_path;
+        sort_summaries(&mut notes, sort.as_deref().unwrap_or_default());
         
-        // Check if the new path already exists and it's not just a case difference
-        if new_path.exists() && !case_only_difference {
-            anyhow::bail!("A file already exists at the target path");
-        }
+        (notes, errors)
+    }
+    
+    /// Reads the summary of every note file in the notes directory
+    /// 
+    /// # Returns
+    /// The summaries of readable notes, and the path and error of every unreadable one
+    fn scan_note_summaries(&self) -> (Vec<NoteSummary>, Vec<(PathBuf, anyhow::Error)>) {
+        let mut notes = Vec::new();
+        let mut errors = Vec::new();
         
-        // If it's only a case difference, use a two-step move process
-        if case_only_difference {
-            // Create a temporary path with a unique name
-            let timestamp = chrono::Utc::now().timestamp();
-            let file_name = new_path.file_name()
-                .and_then(|name| name.to_str())
-                .unwrap_or("note");
-            let parent_dir = new_path.parent()
-                .unwrap_or_else(|| Path::new(""));
-            let temp_path = parent_dir.join(format!("temp_move_{}_{}", timestamp, file_name));
-            
-            // Step 1: Move to temporary path
-            fs::rename(&current_path, &temp_path)
-                .context("Failed to move note file to temporary path")?;
+        for entry in self.walk_notes_dir() {
+            let path = entry.path();
             
-            // Step 2: Move from temporary path to new path
-            fs::rename(&temp_path, &new_path)
-                .context("Failed to move note file from temporary path")?;
-        } else {
-            // Regular move for non-case-only changes
-            fs::rename(&current_path, &new_path)
-                .context("Failed to move note file")?;
+            // Process note files
+            if path.is_file() && self.has_note_extension(path) {
+                match self.get_note_summary(path) {
+                    Ok(note) => notes.push(note),
+                    Err(e) => errors.push((path.to_path_buf(), e)),
+                }
+            }
         }
         
-        // Return the updated note
-        self.read_note(&new_path)
+        (notes, errors)
     }
     
-    /// Creates a new note file
+    /// Lists one page of notes
+    /// 
+    /// All notes are sorted before slicing so pages are stable between calls.
     /// 
     /// # Parameters
-    /// * `title` - Title of the note
-    /// * `content` - Initial content of the note
-    /// * `file_type` - Type of note (Markdown or PlainText)
-    /// * `pattern` - Optional naming pattern (e.g., "{number}-{title}")
+    /// * `page` - Page to return (1-indexed)
+    /// * `page_size` - Maximum number of notes per page
+    /// * `sort` - Optional sort options, applied left to right so later options
+    ///   break ties of earlier ones
     /// 
     /// # Returns
-    /// The newly created note
-    pub fn create_note(&self, title: &str, content: &str, file_type: NoteType, pattern: Option<&str>) -> Result<Note> {
-        // Generate filename based on pattern or use title directly
-        let filename = if let Some(pattern) = pattern {
-            self.generate_filename_from_pattern(title, pattern, &file_type)?
-        } else {
-            format!("{}.{}", title, self.get_extension_for_type(&file_type))
-        };
+    /// The notes on the page and the page position
+    pub fn list_notes_paginated(
+        &self,
+        page: usize,
+        page_size: usize,
+        sort: Option<Vec<SortOption>>,
+    ) -> Result<(Vec<NoteSummary>, PageInfo)> {
+        if page == 0 {
+            anyhow::bail!("Page numbers start at 1");
+        }
+        if page_size == 0 {
+            anyhow::bail!("Page size must be greater than 0");
+        }
         
-        // Create the full path
-        let file_path = self.notes_dir.join(&filename);
+        let notes = self.list_notes(sort)?;
+        let total_count = notes.len();
         
-        // Check if file already exists
-        if file_path.exists() {
-            anyhow::bail!("A note with this name already exists");
-        }
+        let page_notes = notes
+            .into_iter()
+            .skip((page - 1).saturating_mul(page_size))
+            .take(page_size)
+            .collect();
         
-        // Write content to file
-        fs::write(&file_path, content)
-            .context("Failed to write note file")?;
+        let page_info = PageInfo {
+            page,
+            page_size,
+            total_count,
+            total_pages: total_count.div_ceil(page_size),
+        };
         
-        // Read the newly created note
-        self.read_note(&file_path)
+        Ok((page_notes, page_info))
     }
     
-    /// Generates a filename based on a pattern
+    /// Lists all notes with a preview of their content
+    /// 
+    /// Notes that cannot be opened are logged and left out.
     /// 
     /// # Parameters
-    /// * `title` - Title of the note
-    /// * `pattern` - Naming pattern (e.g., "{number}-{title}")
-    /// * `file_type` - Type of note (Markdown or PlainText)
+    /// * `sort` - Optional sort options, applied left to right so later options
+    ///   break ties of earlier ones
+    /// * `preview_length` - Maximum number of characters in each preview
     /// 
     /// # Returns
-    /// The generated filename
-    fn generate_filename_from_pattern(&self, title: &str, pattern: &str, file_type: &NoteType) -> Result<String> {
-        let extension = self.get_extension_for_type(file_type);
+    /// A list of note summaries with `preview` populated
+    pub fn list_notes_with_preview(&self, sort: Option<Vec<SortOption>>, preview_length: usize) -> Result<Vec<NoteSummary>> {
+        let mut notes = self.list_notes(sort)?;
         
-        // If pattern contains {number}, find the highest number and increment
-        if pattern.contains("{number}") {
-            let highest_number = self.find_highest_number_in_notes(pattern)?;
-            let next_number = highest_number + 1;
-            
-            // Replace placeholders in pattern
-            let filename = pattern
-                .replace("{number}", &next_number.to_string())
-                .replace("{title}", title)
-                .replace("{extension}", extension);
-            
-            Ok(filename)
-        } else {
-            // Simple replacement without number logic
-            let filename = pattern
-                .replace("{title}", title)
-                .replace("{extension}", extension);
+        notes.retain_mut(|note| {
+            let file = match self
+                .get_note_path(&note.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| fs::File::open(path).context("Failed to open note file"))
+            {
+                Ok(file) => file,
+                Err(e) => {
+                    log::warn!("Skipping note {} in preview listing: {}", note.id, e);
+                    return false;
+                }
+            };
+            let lines = std::io::BufRead::lines(std::io::BufReader::new(file))
+                .map_while(|line| line.ok());
             
-            Ok(filename)
-        }
+            note.preview = Some(note_preview(lines, preview_length));
+            true
+        });
+        
+        Ok(notes)
     }
     
-    /// Finds the highest number used in existing note filenames that follow a pattern
+    /// Gets vault-wide statistics for a vault health overview
     /// 
-    /// # Parameters
-    /// * `pattern` - Naming pattern to match
+    /// Word and character counts are taken from the full content of every
+    /// note rather than the summaries, whose counts of very large notes are
+    /// estimated. Notes that cannot be read are logged and not counted.
     /// 
     /// # Returns
-    /// The highest number found, or 0 if none found
-    fn find_highest_number_in_notes(&self, pattern: &str) -> Result<u32> {
-        let mut highest_number = 0;
+    /// Size, word, tag, type and age statistics for the vault
+    pub fn get_vault_stats(&self) -> Result<VaultStats> {
+        let notes = self.list_notes(None)?;
         
-        // Create a regex pattern from the naming pattern
-        // This converts "{number}-{title}" to something like "(\d+)-.*"
-        let regex_pattern = pattern
-            .replace("{number}", r"(\d+)")
-            .replace("{title}", ".*")
-            .replace("{extension}", "");
+        let mut total_words = 0;
+        let mut total_chars = 0;
+        let mut total_reading_time = 0;
+        for note in &notes {
+            let bytes = match self
+                .get_note_path(&note.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| fs::read(path).context("Failed to read note file"))
+            {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    log::warn!("Skipping note {} in vault statistics: {}", note.id, e);
+                    continue;
+                }
+            };
+            let (word_count, char_count) = content_stats(&String::from_utf8_lossy(&bytes));
+            
+            total_words += word_count as u64;
+            total_chars += char_count as u64;
+            total_reading_time += reading_time_secs(word_count) as u64;
+        }
         
-        let regex = Regex::new(&regex_pattern)
-            .context("Failed to create regex from pattern")?;
+        let total_notes = notes.len();
+        let total_size: u64 = notes.iter().map(|note| note.size_bytes).sum();
         
-        // Scan all notes in the directory
-        for entry in WalkDir::new(&self.notes_dir)
-            .max_depth(1) // Only look at root directory
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            
-            if path.is_file() {
-                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                    if let Some(captures) = regex.captures(filename) {
-                        if let Some(number_match) = captures.get(1) {
-                            if let Ok(number) = number_match.as_str().parse::<u32>() {
-                                if number > highest_number {
-                                    highest_number = number;
-                                }
-                            }
-                        }
-                    }
-                }
+        let average = |total: u64| {
+            if total_notes > 0 {
+                total as f64 / total_notes as f64
+            } else {
+                0.0
             }
+        };
+        
+        let total_tags = notes.iter().map(|note| note.tags.len()).sum();
+        let unique_tags = notes
+            .iter()
+            .flat_map(|note| note.tags.iter())
+            .collect::<HashSet<_>>()
+            .len();
+        
+        let mut notes_by_type = HashMap::new();
+        for note in &notes {
+            *notes_by_type.entry(format!("{:?}", note.file_type)).or_insert(0) += 1;
         }
         
-        Ok(highest_number)
+        Ok(VaultStats {
+            total_notes,
+            total_words,
+            total_chars,
+            average_reading_time_secs: average(total_reading_time),
+            total_tags,
+            unique_tags,
+            notes_by_type,
+            oldest_note: notes.iter().map(|note| note.created).min(),
+            newest_note: notes.iter().map(|note| note.created).max(),
+            average_note_size_bytes: average(total_size),
+        })
     }
     
-    /// Gets the file extension for a note type
+    /// Walks the notes directory, skipping reserved and excluded directories
+    /// 
+    /// # Returns
+    /// An iterator over all readable directory entries
+    fn walk_notes_dir(&self) -> impl Iterator<Item = walkdir::DirEntry> {
+        let excluded_folders = self.excluded_folders.clone();
+        
+        // Entries of a symbolic link cycle are reported as errors and skipped
+        WalkDir::new(&self.notes_dir)
+            .follow_links(self.follow_symlinks)
+            .max_depth(self.max_walk_depth as usize)
+            .into_iter()
+            .filter_entry(move |entry| {
+                entry.depth() == 0
+                    || !(is_reserved_dir(entry) || is_excluded_dir(entry, &excluded_folders))
+            })
+            .filter_map(|e| e.ok())
+    }
+    
+    /// Gets usage statistics for every tag
     /// 
     /// # Parameters
-    /// * `file_type` - Type of note
+    /// * `min_count` - Tags used by fewer notes are left out
+    /// * `max_count` - Tags used by more notes are left out
     /// 
     /// # Returns
-    /// The file extension without the dot
-    fn get_extension_for_type(&self, file_type: &NoteType) -> &str {
-        match file_type {
-            NoteType::Markdown => "md",
-            NoteType::PlainText => "txt",
+    /// Statistics per tag, most used first (ties ordered by tag)
+    pub fn get_tag_stats(&self, min_count: usize, max_count: usize) -> Result<Vec<TagStats>> {
+        let mut stats: HashMap<String, TagStats> = HashMap::new();
+        
+        for note in self.list_notes(Some(vec![SortOption::TitleAsc]))? {
+            for tag in &note.tags {
+                let entry = stats.entry(tag.clone()).or_insert_with(|| TagStats {
+                    tag: tag.clone(),
+                    count: 0,
+                    note_ids: Vec::new(),
+                    note_titles: Vec::new(),
+                });
+                entry.count += 1;
+                entry.note_ids.push(note.id.clone());
+                entry.note_titles.push(note.title.clone());
+            }
         }
+        
+        let mut stats: Vec<TagStats> = stats
+            .into_values()
+            .filter(|stat| stat.count >= min_count && stat.count <= max_count)
+            .collect();
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        
+        Ok(stats)
     }
     
-    /// Converts a file path to a note ID
+    /// Gets the size of a note file
     /// 
     /// # Parameters
-    /// * `path` - Path to the note file
+    /// * `id` - ID of the note
     /// 
     /// # Returns
-    /// ID for the note
-    fn path_to_id(&self, path: &Path) -> Result<String> {
-        // Get relative path from notes directory
-        let relative_path = path.strip_prefix(&self.notes_dir)
-            .context("Path is not in notes directory")?;
+    /// Size of the note file in bytes
+    pub fn get_note_size(&self, id: &str) -> Result<u64> {
+        let path = self.get_note_path(id)?;
         
-        // Use base64-encoded relative path as ID
-        let id = base64::engine::general_purpose::STANDARD
-            .encode(relative_path.to_string_lossy().as_bytes());
+        let metadata = path.metadata()
+            .context("Failed to read file metadata")?;
         
-        Ok(id)
+        Ok(metadata.len())
     }
     
-    /// Finds a note by its title
+    /// Gets notes that have not been modified for a while
+    /// 
+    /// Archived notes are never considered stale, since the archive folder is
+    /// not listed.
     /// 
     /// # Parameters
-    /// * `title` - Title of the note to find
+    /// * `days_threshold` - Notes not modified for more than this many days are stale
     /// 
     /// # Returns
-    /// The note ID if found, None otherwise
-    pub fn find_note_by_title(&self, title: &str) -> Result<Option<String>> {
-        // List all notes
-        let notes = self.list_notes(None)?;
+    /// Summaries of the stale notes, oldest first
+    pub fn get_stale_notes(&self, days_threshold: u32) -> Result<Vec<NoteSummary>> {
+        let cutoff = Utc::now() - chrono::Duration::days(days_threshold as i64);
         
-        // Find the first note with matching title (case-insensitive)
-        for note in notes {
-            if note.title.to_lowercase() == title.to_lowercase() {
-                return Ok(Some(note.id));
+        let mut notes = self.list_notes(Some(vec![SortOption::ModifiedOldest]))?;
+        notes.retain(|note| note.modified < cutoff);
+        
+        Ok(notes)
+    }
+    
+    /// Finds notes by their number of tags
+    /// 
+    /// # Parameters
+    /// * `min_tags` - Notes with fewer tags are left out
+    /// * `max_tags` - Notes with more tags are left out
+    /// 
+    /// # Returns
+    /// Summaries of the matching notes; `0..=0` returns the untagged notes
+    pub fn find_notes_with_tag_count(&self, min_tags: usize, max_tags: usize) -> Result<Vec<NoteSummary>> {
+        self.find_notes_with_tag_count_sorted(min_tags, max_tags, None)
+    }
+    
+    /// Finds notes by their number of tags in a given order
+    /// 
+    /// # Parameters
+    /// * `min_tags` - Notes with fewer tags are left out
+    /// * `max_tags` - Notes with more tags are left out
+    /// * `sort` - Optional sort options, applied left to right so later options
+    ///   break ties of earlier ones
+    /// 
+    /// # Returns
+    /// Summaries of the matching notes
+    pub fn find_notes_with_tag_count_sorted(
+        &self,
+        min_tags: usize,
+        max_tags: usize,
+        sort: Option<Vec<SortOption>>,
+    ) -> Result<Vec<NoteSummary>> {
+        let mut notes = self.list_notes(sort)?;
+        notes.retain(|note| (min_tags..=max_tags).contains(&note.tags.len()));
+        Ok(notes)
+    }
+    
+    /// Finds notes without any tags
+    /// 
+    /// # Returns
+    /// Summaries of the untagged notes
+    pub fn find_notes_without_tags(&self) -> Result<Vec<NoteSummary>> {
+        self.find_notes_with_exactly_n_tags(0)
+    }
+    
+    /// Finds notes with an exact number of tags
+    /// 
+    /// # Parameters
+    /// * `n` - Number of tags
+    /// 
+    /// # Returns
+    /// Summaries of the matching notes
+    pub fn find_notes_with_exactly_n_tags(&self, n: usize) -> Result<Vec<NoteSummary>> {
+        self.find_notes_with_tag_count(n, n)
+    }
+    
+    /// Gets the notes modified after a point in time
+    /// 
+    /// # Parameters
+    /// * `since` - Only notes modified strictly after this time are returned
+    /// 
+    /// # Returns
+    /// Summaries of the modified notes
+    pub fn get_notes_modified_after(&self, since: DateTime<Utc>) -> Result<Vec<NoteSummary>> {
+        let mut notes = self.list_notes(None)?;
+        notes.retain(|note| note.modified > since);
+        Ok(notes)
+    }
+    
+    /// Gets the notes created after a point in time
+    /// 
+    /// # Parameters
+    /// * `since` - Only notes created strictly after this time are returned
+    /// 
+    /// # Returns
+    /// Summaries of the created notes
+    pub fn get_notes_created_after(&self, since: DateTime<Utc>) -> Result<Vec<NoteSummary>> {
+        let mut notes = self.list_notes(None)?;
+        notes.retain(|note| note.created > since);
+        Ok(notes)
+    }
+    
+    /// Gets the folder structure of the notes directory
+    /// 
+    /// Reserved and excluded folders are omitted. Empty folders are included
+    /// with a note count of zero.
+    /// 
+    /// # Returns
+    /// The root folder of the vault with all its subfolders
+    pub fn get_folder_tree(&self) -> Result<FolderNode> {
+        let mut note_counts: HashMap<PathBuf, usize> = HashMap::new();
+        let mut children: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        
+        for entry in self.walk_notes_dir() {
+            let relative_path = entry
+                .path()
+                .strip_prefix(&self.notes_dir)
+                .context("Path is not in notes directory")?
+                .to_path_buf();
+            let parent = relative_path.parent().map(Path::to_path_buf).unwrap_or_default();
+            
+            if entry.file_type().is_dir() {
+                note_counts.entry(relative_path.clone()).or_insert(0);
+                if entry.depth() > 0 {
+                    children.entry(parent).or_default().push(relative_path);
+                }
+            } else if self.has_note_extension(entry.path()) {
+                *note_counts.entry(parent).or_insert(0) += 1;
             }
         }
         
-        Ok(None)
+        let root_name = self
+            .notes_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        
+        Ok(build_folder_node(PathBuf::new(), root_name, &note_counts, &children))
     }
     
-    /// Finds all notes that link to a specific note
+    /// Gets a note by ID
     /// 
     /// # Parameters
-    /// * `note_title` - Title of the note to find backlinks for
+    /// * `id` - ID of the note to retrieve
     /// 
     /// # Returns
-    /// A list of note summaries that link to the specified note
-    pub fn find_backlinks(&self, note_title: &str) -> Result<Vec<NoteSummary>> {
-        // List all notes
-        let notes = self.list_notes(None)?;
-        let mut backlinks = Vec::new();
+    /// The note if found
+    pub fn get_note(&self, id: &str) -> Result<Note> {
+        let path = self.get_note_path(id)?;
+        self.read_note(&path)
+    }
+    
+    /// Gets the metadata of a note without loading its full content
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// The summary of the note
+    pub fn get_note_metadata(&self, id: &str) -> Result<NoteSummary> {
+        let path = self.get_note_path(id)?;
+        self.get_note_summary(&path)
+    }
+    
+    /// Checks whether a note exists without reading its content
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// True if the ID resolves to an existing file
+    pub fn check_note_exists(&self, id: &str) -> bool {
+        self.get_note_absolute_path(id)
+            .map_or(false, |path| path.is_file())
+    }
+    
+    /// Gets multiple notes by ID
+    /// 
+    /// Notes that cannot be loaded are skipped and logged instead of failing
+    /// the whole batch.
+    /// 
+    /// # Parameters
+    /// * `ids` - IDs of the notes to retrieve
+    /// 
+    /// # Returns
+    /// The successfully loaded notes, in the order of `ids`
+    pub fn batch_get_notes(&self, ids: &[&str]) -> Result<Vec<Note>> {
+        let mut notes = Vec::with_capacity(ids.len());
+        let mut errors: Vec<(String, String)> = Vec::new();
         
-        // Regular expression to find [[Note Title]] patterns
-        let link_pattern = format!(r"\[\[{}\]\]", regex::escape(note_title));
-        let regex = regex::Regex::new(&link_pattern)?;
+        for id in ids {
+            match self.get_note(id) {
+                Ok(note) => notes.push(note),
+                Err(e) => errors.push((id.to_string(), e.to_string())),
+            }
+        }
         
-        // Check each note for links to the specified note
-        for summary in notes {
-            // Get the path from the ID
-            if let Ok(path) = self.get_note_path(&summary.id) {
-                // Check if the file contains the link pattern
-                // We'll read the file in chunks to avoid loading the entire file
-                if self.file_contains_pattern(&path, &regex)? {
-                    backlinks.push(summary);
-                }
+        for (id, error) in &errors {
+            log::warn!("Failed to load note {}: {}", id, error);
+        }
+        
+        Ok(notes)
+    }
+    
+    /// Determines the note type based on file extension
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the note file
+    /// 
+    /// # Returns
+    /// The note type (Markdown, Org or PlainText)
+    fn get_note_type(&self, path: &Path) -> NoteType {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md") => NoteType::Markdown,
+            Some("org") => NoteType::Org,
+            _ => NoteType::PlainText,
+        }
+    }
+    
+    /// Checks if a file inside the notes directory is listed as a note
+    /// 
+    /// # Parameters
+    /// * `path` - Absolute path to the file
+    /// 
+    /// # Returns
+    /// True if the file has a note extension and is not inside a reserved or
+    /// excluded folder
+    pub fn is_listed_note_file(&self, path: &Path) -> bool {
+        self.has_note_extension(path) && self.is_listed_path(path)
+    }
+    
+    /// Checks whether a path inside the notes directory is included in listings
+    /// 
+    /// # Parameters
+    /// * `path` - Absolute path of the note
+    /// 
+    /// # Returns
+    /// False if the path is outside the notes directory or inside a reserved
+    /// or excluded folder
+    pub(super) fn is_listed_path(&self, path: &Path) -> bool {
+        let Ok(relative_path) = path.strip_prefix(&self.notes_dir) else {
+            return false;
+        };
+        
+        // The last component is the file name, only folders are checked
+        let folders = relative_path.parent().map_or(Vec::new(), |parent| {
+            parent.components().collect::<Vec<_>>()
+        });
+        
+        !folders.iter().any(|component| match component {
+            std::path::Component::Normal(name) => name.to_str().map_or(false, |name| {
+                RESERVED_DIRS.contains(&name) || is_excluded_name(name, &self.excluded_folders)
+            }),
+            _ => false,
+        })
+    }
+    
+    /// Checks if a file has an extension listed as a note with the current settings
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the file
+    /// 
+    /// # Returns
+    /// True for Markdown and text files, for Org files if enabled, and for
+    /// files with a custom extension
+    fn has_note_extension(&self, path: &Path) -> bool {
+        if is_note_file(path) {
+            return true;
+        }
+        
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            return false;
+        };
+        
+        (self.include_org_files && extension == "org")
+            || self
+                .custom_extensions
+                .iter()
+                .any(|custom| custom.eq_ignore_ascii_case(extension))
+    }
+    
+    /// Extracts tags from note content
+    /// 
+    /// Tags are taken from a `tags:` key in YAML frontmatter and from inline
+    /// `#tag` words in the body. Tags present in both are only returned once.
+    /// 
+    /// # Parameters
+    /// * `content` - Note content to extract tags from
+    /// 
+    /// # Returns
+    /// Vector of extracted tags
+    fn extract_tags(&self, content: &str) -> Vec<String> {
+        let (frontmatter, body) = split_frontmatter(content);
+        
+        let mut tags = Vec::new();
+        
+        for tag in frontmatter.map(frontmatter_tags).unwrap_or_default() {
+            if !tags.contains(&tag) {
+                tags.push(tag);
             }
         }
         
-        Ok(backlinks)
+        for tag in inline_tags(body) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        
+        tags
     }
     
-    /// Checks if a file contains a specific regex pattern
+    /// Extracts the tags of a note
     /// 
     /// # Parameters
-    /// * `path` - Path to the file to check
-    /// * `pattern` - Regex pattern to search for
+    /// * `file_type` - Type of the note
+    /// * `content` - Content of the note, or its first lines
     /// 
     /// # Returns
-    /// True if the file contains the pattern, false otherwise
-    fn file_contains_pattern(&self, path: &Path, pattern: &Regex) -> Result<bool> {
-        // Use a line-by-line approach which is safer for UTF-8 text
+    /// The tags of the note
+    fn note_tags(&self, file_type: &NoteType, content: &str) -> Vec<String> {
+        match file_type {
+            NoteType::Org => org_tags(content),
+            _ => self.extract_tags(content),
+        }
+    }
+    
+    /// Reads a note from a file
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the note file
+    /// 
+    /// # Returns
+    /// The parsed note
+    fn read_note(&self, path: &Path) -> Result<Note> {
+        let content = fs::read_to_string(path)
+            .context("Failed to read note file")?;
+        
+        let file_type = self.get_note_type(path);
+        
+        let title = note_title(&file_type, path, &content);
+        let tags = self.note_tags(&file_type, &content);
+        
+        // Get file metadata
+        let metadata = path.metadata()
+            .context("Failed to read file metadata")?;
+        
+        let created = metadata.created()
+            .map(|time| DateTime::<Utc>::from(time))
+            .unwrap_or_else(|_| Utc::now());
+        
+        let modified = metadata.modified()
+            .map(|time| DateTime::<Utc>::from(time))
+            .unwrap_or_else(|_| Utc::now());
+        
+        // Generate ID from file path
+        let id = self.path_to_id(path)?;
+        
+        // Get relative path from notes directory
+        let relative_path = path.strip_prefix(&self.notes_dir)
+            .context("Path is not in notes directory")?
+            .to_string_lossy()
+            .to_string();
+        
+        Ok(Note {
+            id,
+            title,
+            content,
+            created,
+            modified,
+            tags,
+            file_type,
+            path: relative_path,
+        })
+    }
+    
+    /// Gets a summary of a note without loading full content
+    /// 
+    /// At most the first 64 KiB of the file are read. Word and character
+    /// counts of longer files are estimated from their size.
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the note file
+    /// 
+    /// # Returns
+    /// A summary of the note
+    fn get_note_summary(&self, path: &Path) -> Result<NoteSummary> {
+        let file_type = self.get_note_type(path);
+        
+        // Only the beginning of the file is read, so large files stay cheap
         let file = fs::File::open(path)
             .context("Failed to open note file")?;
-        let reader = std::io::BufReader::new(file);
+        let metadata = file.metadata()
+            .context("Failed to read file metadata")?;
         
-        // We'll read the file line by line, but keep a buffer of recent lines
-        // to handle patterns that might span multiple lines
-        const BUFFER_LINES: usize = 5; // Keep last 5 lines in buffer
-        let mut line_buffer = Vec::with_capacity(BUFFER_LINES);
+        let mut bytes = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::Read::take(file, SUMMARY_READ_LIMIT), &mut bytes)
+            .context("Failed to read note file")?;
+        let text = String::from_utf8_lossy(&bytes);
         
-        // Process each line
-        for line_result in std::io::BufRead::lines(reader) {
-            let line = line_result.context("Failed to read line from file")?;
-            
-            // Add the new line to our buffer
-            line_buffer.push(line);
-            
-            // If buffer is larger than our desired size, remove oldest line
-            if line_buffer.len() > BUFFER_LINES {
-                line_buffer.remove(0);
-            }
-            
-            // Join the buffer lines and check for pattern
-            let text = line_buffer.join("\n");
-            if pattern.is_match(&text) {
-                return Ok(true);
-            }
+        // Statistics of a file longer than the limit are extrapolated from its size
+        let (mut word_count, mut char_count) = content_stats(&text);
+        if metadata.len() > bytes.len() as u64 && !bytes.is_empty() {
+            let scale = |count: u32| {
+                u32::try_from(count as u64 * metadata.len() / bytes.len() as u64).unwrap_or(u32::MAX)
+            };
+            word_count = scale(word_count);
+            char_count = scale(char_count);
         }
         
-        Ok(false)
+        // Title and tags are taken from the first 50 lines
+        let head = text.lines().take(50).collect::<Vec<_>>().join("\n");
+        let title = note_title(&file_type, path, &head);
+        let tags = self.note_tags(&file_type, &head);
+        
+        let created = metadata.created()
+            .map(|time| DateTime::<Utc>::from(time))
+            .unwrap_or_else(|_| Utc::now());
+        
+        let modified = metadata.modified()
+            .map(|time| DateTime::<Utc>::from(time))
+            .unwrap_or_else(|_| Utc::now());
+        
+        // Generate ID from file path
+        let id = self.path_to_id(path)?;
+        
+        Ok(NoteSummary {
+            id,
+            title,
+            created,
+            modified,
+            tags,
+            file_type,
+            word_count,
+            char_count,
+            reading_time_secs: reading_time_secs(word_count),
+            size_bytes: metadata.len(),
+            preview: None,
+        })
+    }
+    
+    /// Converts a note ID to a file path
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// Path to the note file, or `NoteError::NotFound` if it does not exist
+    fn get_note_path(&self, id: &str) -> std::result::Result<PathBuf, NoteError> {
+        let path = self.get_note_absolute_path(id)?;
+        
+        if !path.exists() {
+            return Err(NoteError::NotFound(id.to_string()));
+        }
+        
+        Ok(path)
+    }
+    
+    /// Converts a note ID to an absolute file path without checking that the file exists
+    /// 
+    /// Notes with a frontmatter UUID can only be resolved while they exist.
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// Absolute path of the note file
+    pub fn get_note_absolute_path(&self, id: &str) -> std::result::Result<PathBuf, NoteError> {
+        if self.id_scheme == IdScheme::Uuid && uuid::Uuid::parse_str(id).is_ok() {
+            if let Some(path) = self.cached_uuid_path(id) {
+                return Ok(path);
+            }
+            
+            // The note may have been created or moved outside the app
+            self.rebuild_id_cache();
+            
+            return self
+                .cached_uuid_path(id)
+                .ok_or_else(|| NoteError::NotFound(id.to_string()));
+        }
+        
+        // Decode the ID back to a relative path
+        let relative_path = base64::engine::general_purpose::STANDARD
+            .decode(id)
+            .map_err(|_| NoteError::InvalidId("Failed to decode note ID".into()))?;
+        
+        let relative_path = String::from_utf8(relative_path)
+            .map_err(|_| NoteError::InvalidId("Invalid UTF-8 in note ID".into()))?;
+        
+        // IDs come from the frontend, so they must not lead out of the notes directory
+        let escapes = Path::new(&relative_path).components().any(|component| {
+            !matches!(component, std::path::Component::Normal(_) | std::path::Component::CurDir)
+        });
+        if escapes {
+            return Err(NoteError::PathEscape);
+        }
+        
+        Ok(self.notes_dir.join(relative_path))
+    }
+    
+    /// Updates the content of a note
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to update
+    /// * `content` - New content for the note
+    /// 
+    /// # Returns
+    /// The updated note
+    pub fn update_note_content(&self, id: &str, content: &str) -> Result<Note> {
+        // Get the file path from the ID
+        let path = self.get_note_path(id)?;
+        
+        // Keep a snapshot of the previous content so the change can be undone
+        let unchanged = fs::read_to_string(&path).map_or(false, |current| current == content);
+        if !unchanged {
+            self.save_version(id)?;
+        }
+        
+        // Write the new content to the file
+        fs::write(&path, content)
+            .context("Failed to write note content")?;
+        self.cache_note(None, &path);
+        
+        // Return the updated note
+        self.read_note(&path)
+    }
+    
+    /// Renames a note file
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to rename
+    /// * `new_name` - New name for the note file (without extension)
+    /// * `update_markdown_title` - Whether to also replace the `# Heading` that
+    ///   Markdown notes take their title from
+    /// 
+    /// # Returns
+    /// The updated note with new ID
+    pub fn rename_note(&self, id: &str, new_name: &str, update_markdown_title: bool) -> Result<Note> {
+        // Get the current file path from the ID
+        let current_path = self.get_note_path(id)?;
+        
+        // Get the file extension
+        let extension = current_path.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("txt");
+        
+        // Get the parent directory
+        let parent_dir = current_path.parent()
+            .unwrap_or_else(|| Path::new(""));
+        
+        // Create the new path with the new name and same extension
+        let new_path = parent_dir.join(format!("{}.{}", new_name, extension));
+        
+        // Get the current filename without extension
+        let current_name = current_path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("");
+        
+        // Check if the only difference is case (case-insensitive comparison)
+        let case_only_difference = current_name.to_lowercase() == new_name.to_lowercase() && current_name != new_name;
+        
+        // Check if the new path already exists and it's not just a case difference
+        if new_path.exists() && !case_only_difference {
+            anyhow::bail!("A file with this name already exists");
+        }
+        
+        // If it's only a case difference, use a two-step rename process
+        if case_only_difference {
+            // Create a temporary path with a unique name
+            let timestamp = chrono::Utc::now().timestamp();
+            let temp_path = parent_dir.join(format!("temp_rename_{}_{}.{}", timestamp, new_name, extension));
+            
+            // Step 1: Rename to temporary path
+            fs::rename(&current_path, &temp_path)
+                .context("Failed to rename note file to temporary path")?;
+            
+            // Step 2: Rename from temporary path to new path
+            fs::rename(&temp_path, &new_path)
+                .context("Failed to rename note file from temporary path")?;
+        } else {
+            // Regular rename for non-case-only changes
+            fs::rename(&current_path, &new_path)
+                .context("Failed to rename note file")?;
+        }
+        
+        // Keep the title heading in sync with the new file name
+        if update_markdown_title && self.get_note_type(&new_path) == NoteType::Markdown {
+            let content = fs::read_to_string(&new_path)
+                .context("Failed to read note content")?;
+            
+            if let Some(updated) = replace_markdown_heading(&content, new_name) {
+                fs::write(&new_path, updated)
+                    .context("Failed to update note heading")?;
+            }
+        }
+        
+        self.cache_note(Some(id), &new_path);
+        
+        // Return the updated note
+        self.read_note(&new_path)
+    }
+    
+    /// Moves a note to a different path
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to move
+    /// * `new_path` - New relative path for the note (including filename)
+    /// 
+    /// # Returns
+    /// The updated note with new ID
+    pub fn move_note(&self, id: &str, new_relative_path: &str) -> Result<Note> {
+        // Get the current file path from the ID
+        let current_path = self.get_note_path(id)?;
+        
+        let new_path = self.resolve_relative_path(new_relative_path)?;
+        
+        // Ensure the parent directory exists
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create parent directories")?;
+        }
+        
+        // Check if the paths are the same except for case
+        let case_only_difference = current_path.to_string_lossy().to_lowercase() == 
+                                  new_path.to_string_lossy().to_lowercase() && 
+                                  current_path != new_path;
+        
+        // Check if the new path already exists and it's not just a case difference
+        if new_path.exists() && !case_only_difference {
+            anyhow::bail!("A file already exists at the target path");
+        }
+        
+        // If it's only a case difference, use a two-step move process
+        if case_only_difference {
+            // Create a temporary path with a unique name
+            let timestamp = chrono::Utc::now().timestamp();
+            let file_name = new_path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("note");
+            let parent_dir = new_path.parent()
+                .unwrap_or_else(|| Path::new(""));
+            let temp_path = parent_dir.join(format!("temp_move_{}_{}", timestamp, file_name));
+            
+            // Step 1: Move to temporary path
+            fs::rename(&current_path, &temp_path)
+                .context("Failed to move note file to temporary path")?;
+            
+            // Step 2: Move from temporary path to new path
+            fs::rename(&temp_path, &new_path)
+                .context("Failed to move note file from temporary path")?;
+        } else {
+            // Regular move for non-case-only changes
+            fs::rename(&current_path, &new_path)
+                .context("Failed to move note file")?;
+        }
+        self.cache_note(Some(id), &new_path);
+        
+        // Return the updated note
+        self.read_note(&new_path)
+    }
+    
+    /// Resolves a path relative to the notes directory
+    /// 
+    /// Prevents directory traversal by rejecting `..` segments and ensuring
+    /// the resulting path remains inside the notes directory.
+    /// 
+    /// # Parameters
+    /// * `relative_path` - Path relative to the notes directory
+    /// 
+    /// # Returns
+    /// The absolute path inside the notes directory
+    pub(crate) fn resolve_relative_path(&self, relative_path: &str) -> Result<PathBuf> {
+        let mut normalized = PathBuf::new();
+        for comp in Path::new(relative_path).components() {
+            match comp {
+                std::path::Component::ParentDir => {
+                    anyhow::bail!("Invalid target path");
+                }
+                std::path::Component::CurDir => {}
+                other => normalized.push(other.as_os_str()),
+            }
+        }
+        let path = self.notes_dir.join(normalized);
+
+        // Ensure the resulting path is still within the notes directory
+        if !path.starts_with(&self.notes_dir) {
+            anyhow::bail!("Target path is outside notes directory");
+        }
+        
+        Ok(path)
+    }
+    
+    /// Gets the note at a path, creating it if it does not exist yet
+    /// 
+    /// Missing parent directories are created automatically.
+    /// 
+    /// # Parameters
+    /// * `relative_path` - Path of the note relative to the notes directory
+    /// * `content` - Content to use if the note has to be created
+    /// 
+    /// # Returns
+    /// The existing or newly created note
+    pub fn get_or_create_note_at(&self, relative_path: &str, content: &str) -> Result<Note> {
+        let path = self.resolve_relative_path(relative_path)?;
+        
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .context("Failed to create parent directories")?;
+            }
+            
+            fs::write(&path, content)
+                .context("Failed to write note file")?;
+            self.cache_note(None, &path);
+        }
+        
+        self.read_note(&path)
+    }
+    
+    /// Imports notes from an external directory
+    /// 
+    /// Copies every `.md` and `.txt` file from `source_dir` into the notes
+    /// directory, preserving the subdirectory structure. Other files are ignored.
+    /// 
+    /// # Parameters
+    /// * `source_dir` - Directory to import notes from
+    /// * `overwrite` - Whether to overwrite notes that already exist
+    /// 
+    /// # Returns
+    /// A report of imported, skipped and failed files
+    pub fn import_notes_from_directory(&self, source_dir: &Path, overwrite: bool) -> Result<ImportReport> {
+        if !source_dir.is_dir() {
+            anyhow::bail!("Source path is not a directory");
+        }
+        
+        if source_dir.starts_with(&self.notes_dir) || self.notes_dir.starts_with(source_dir) {
+            anyhow::bail!("Source directory overlaps the notes directory");
+        }
+        
+        let mut report = ImportReport::default();
+        
+        for entry in WalkDir::new(source_dir)
+            .follow_links(self.follow_symlinks)
+            .max_depth(self.max_walk_depth as usize)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_reserved_dir(entry))
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            
+            if !path.is_file() || !self.has_note_extension(path) {
+                continue;
+            }
+            
+            let relative_path = match path.strip_prefix(source_dir) {
+                Ok(relative_path) => relative_path.to_string_lossy().to_string(),
+                Err(_) => continue,
+            };
+            
+            // Validate the target so that nothing is written outside the notes directory
+            let target_path = match self.resolve_relative_path(&relative_path) {
+                Ok(target_path) => target_path,
+                Err(e) => {
+                    report.errors.push(format!("{}: {}", relative_path, e));
+                    continue;
+                }
+            };
+            
+            if target_path.exists() && !overwrite {
+                report.skipped += 1;
+                continue;
+            }
+            
+            let copy_result = target_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::copy(path, &target_path));
+            
+            match copy_result {
+                Ok(_) => report.imported += 1,
+                Err(e) => report.errors.push(format!("{}: {}", relative_path, e)),
+            }
+        }
+        
+        if report.imported > 0 {
+            self.invalidate_cache();
+        }
+        
+        Ok(report)
+    }
+    
+    /// Creates a new note file
+    /// 
+    /// # Parameters
+    /// * `title` - Title of the note
+    /// * `content` - Initial content of the note
+    /// * `file_type` - Type of note (Markdown or PlainText)
+    /// * `pattern` - Optional naming pattern (e.g., "{number}-{title}")
+    /// 
+    /// # Returns
+    /// The newly created note
+    pub fn create_note(&self, title: &str, content: &str, file_type: NoteType, pattern: Option<&str>) -> Result<Note> {
+        let file_title = sanitize_title_for_filename(title);
+        
+        // Only Markdown has frontmatter to store a UUID in
+        let content = match (self.id_scheme, &file_type) {
+            (IdScheme::Uuid, NoteType::Markdown) => {
+                with_frontmatter_id(content, &uuid::Uuid::new_v4().to_string())
+            }
+            _ => content.to_string(),
+        };
+        
+        // Generate filename based on pattern or use title directly
+        let filename = if let Some(pattern) = pattern {
+            self.generate_filename_from_pattern(&file_title, pattern, &file_type)?
+        } else {
+            format!("{}.{}", file_title, self.get_extension_for_type(&file_type))
+        };
+        
+        // Create the full path
+        let file_path = self.notes_dir.join(&filename);
+        
+        // Check if file already exists
+        if file_path.exists() {
+            let suggested_name = match pattern {
+                Some(pattern) => self.find_available_title(&file_title, pattern, &file_type)?,
+                None => {
+                    let extension = self.get_extension_for_type(&file_type);
+                    file_stem_of(&self.find_available_filename(&file_title, extension))
+                }
+            };
+            
+            return Err(NoteExistsError { suggested_name }.into());
+        }
+        
+        // Write content to file
+        fs::write(&file_path, &content)
+            .context("Failed to write note file")?;
+        self.cache_note(None, &file_path);
+        
+        // Read the newly created note
+        self.read_note(&file_path)
+    }
+    
+    /// Finds a file name in the notes directory that is not taken yet
+    /// 
+    /// Appends `_2`, `_3`, ... to the base name until the name is free.
+    /// 
+    /// # Parameters
+    /// * `base` - File name without extension
+    /// * `ext` - File extension without the dot
+    /// 
+    /// # Returns
+    /// The free file name, including the extension
+    pub fn find_available_filename(&self, base: &str, ext: &str) -> String {
+        let with_extension = |name: String| {
+            if ext.is_empty() {
+                name
+            } else {
+                format!("{}.{}", name, ext)
+            }
+        };
+        
+        let mut filename = with_extension(base.to_string());
+        let mut suffix = 2;
+        
+        while self.notes_dir.join(&filename).exists() {
+            filename = with_extension(format!("{}_{}", base, suffix));
+            suffix += 1;
+        }
+        
+        filename
+    }
+    
+    /// Suggests a title for a new note whose file name does not collide
+    /// 
+    /// # Parameters
+    /// * `title` - Desired title of the note
+    /// * `file_type` - Type of the note
+    /// 
+    /// # Returns
+    /// The title as it will be used in the file name, with a suffix if taken
+    pub fn suggest_note_filename(&self, title: &str, file_type: &NoteType) -> String {
+        let base = sanitize_title_for_filename(title);
+        let extension = self.get_extension_for_type(file_type);
+        
+        file_stem_of(&self.find_available_filename(&base, extension))
+    }
+    
+    /// Finds a title for which a naming pattern gives a file name that is not taken yet
+    /// 
+    /// Appends `_2`, `_3`, ... to the title until the rendered name is free.
+    /// Patterns without `{title}` cannot be changed through the title, so the
+    /// title is returned as is.
+    /// 
+    /// # Parameters
+    /// * `title` - Sanitized title of the note
+    /// * `pattern` - Naming pattern (e.g., "{date}-{title}")
+    /// * `file_type` - Type of note
+    /// 
+    /// # Returns
+    /// The free title
+    fn find_available_title(&self, title: &str, pattern: &str, file_type: &NoteType) -> Result<String> {
+        if !pattern.contains("{title}") {
+            return Ok(title.to_string());
+        }
+        
+        let mut candidate = title.to_string();
+        let mut suffix = 2;
+        
+        while self
+            .notes_dir
+            .join(self.generate_filename_from_pattern(&candidate, pattern, file_type)?)
+            .exists()
+        {
+            candidate = format!("{}_{}", title, suffix);
+            suffix += 1;
+        }
+        
+        Ok(candidate)
+    }
+    
+    /// Generates a filename based on a pattern
+    /// 
+    /// # Parameters
+    /// * `title` - Title of the note
+    /// * `pattern` - Naming pattern (e.g., "{number}-{title}")
+    /// * `file_type` - Type of note (Markdown or PlainText)
+    /// 
+    /// # Returns
+    /// The generated filename
+    fn generate_filename_from_pattern(&self, title: &str, pattern: &str, file_type: &NoteType) -> Result<String> {
+        let extension = self.get_extension_for_type(file_type);
+        
+        // Dates are in UTC: {date} is YYYY-MM-DD, {datetime} is YYYYMMDD_HHMM
+        let now = Utc::now();
+        let pattern = pattern
+            .replace("{date}", &now.format("%Y-%m-%d").to_string())
+            .replace("{datetime}", &now.format("%Y%m%d_%H%M").to_string());
+        
+        // If pattern contains {number}, find the highest number and increment
+        if pattern.contains("{number}") {
+            let highest_number = self.find_highest_number_in_notes(&pattern)?;
+            let next_number = highest_number + 1;
+            
+            // Replace placeholders in pattern
+            let filename = pattern
+                .replace("{number}", &next_number.to_string())
+                .replace("{title}", title)
+                .replace("{extension}", extension);
+            
+            Ok(filename)
+        } else {
+            // Simple replacement without number logic
+            let filename = pattern
+                .replace("{title}", title)
+                .replace("{extension}", extension);
+            
+            Ok(filename)
+        }
+    }
+    
+    /// Finds the highest number used in existing note filenames that follow a pattern
+    /// 
+    /// # Parameters
+    /// * `pattern` - Naming pattern to match
+    /// 
+    /// # Returns
+    /// The highest number found, or 0 if none found
+    fn find_highest_number_in_notes(&self, pattern: &str) -> Result<u32> {
+        let mut highest_number = 0;
+        
+        // Create a regex pattern from the naming pattern
+        // This converts "{number}-{title}" to something like "(\d+)-.*"
+        let regex_pattern = pattern
+            .replace("{number}", r"(\d+)")
+            .replace("{title}", ".*")
+            .replace("{extension}", "");
+        
+        let regex = Regex::new(&regex_pattern)
+            .context("Failed to create regex from pattern")?;
+        
+        // Scan all notes in the directory
+        for entry in WalkDir::new(&self.notes_dir)
+            .max_depth(1) // Only look at root directory
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            
+            if path.is_file() {
+                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                    if let Some(captures) = regex.captures(filename) {
+                        if let Some(number_match) = captures.get(1) {
+                            if let Ok(number) = number_match.as_str().parse::<u32>() {
+                                if number > highest_number {
+                                    highest_number = number;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        Ok(highest_number)
+    }
+    
+    /// Gets the file extension for a note type
+    /// 
+    /// # Parameters
+    /// * `file_type` - Type of note
+    /// 
+    /// # Returns
+    /// The file extension without the dot
+    fn get_extension_for_type(&self, file_type: &NoteType) -> &str {
+        match file_type {
+            NoteType::Markdown => "md",
+            NoteType::PlainText => "txt",
+            NoteType::Org => "org",
+        }
+    }
+    
+    /// Converts a file path to a note ID
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the note file
+    /// 
+    /// # Returns
+    /// ID for the note
+    fn path_to_id(&self, path: &Path) -> Result<String> {
+        // Markdown notes with a frontmatter UUID keep it as their ID
+        if self.id_scheme == IdScheme::Uuid && self.get_note_type(path) == NoteType::Markdown {
+            if let Some(id) = read_frontmatter_id(path) {
+                if let Ok(mut cache) = self.uuid_paths.lock() {
+                    cache.insert(id.clone(), path.to_path_buf());
+                }
+                return Ok(id);
+            }
+        }
+        
+        self.path_based_id(path)
+    }
+    
+    /// Converts a file path to the ID it has under the path-based ID scheme
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the note file
+    /// 
+    /// # Returns
+    /// Base64-encoded path relative to the notes directory
+    fn path_based_id(&self, path: &Path) -> Result<String> {
+        // Get relative path from notes directory
+        let relative_path = path.strip_prefix(&self.notes_dir)
+            .context("Path is not in notes directory")?;
+        
+        // Use base64-encoded relative path as ID
+        let id = base64::engine::general_purpose::STANDARD
+            .encode(relative_path.to_string_lossy().as_bytes());
+        
+        Ok(id)
+    }
+    
+    /// Finds a note by its title
+    /// 
+    /// Titles are not unique across folders. When several notes share the
+    /// title, an arbitrary one of them is returned; use
+    /// `find_notes_by_title_all` to get every match.
+    /// 
+    /// # Parameters
+    /// * `title` - Title of the note to find
+    /// 
+    /// # Returns
+    /// The note ID if found, None otherwise
+    pub fn find_note_by_title(&self, title: &str) -> Result<Option<String>> {
+        // List all notes
+        let notes = self.list_notes(None)?;
+        
+        // Find the first note with matching title (case-insensitive)
+        for note in notes {
+            if note.title.to_lowercase() == title.to_lowercase() {
+                return Ok(Some(note.id));
+            }
+        }
+        
+        Ok(None)
+    }
+    
+    /// Finds all notes with a title
+    /// 
+    /// # Parameters
+    /// * `title` - Title of the notes to find (case-insensitive)
+    /// 
+    /// # Returns
+    /// Summaries of all notes with a matching title
+    pub fn find_notes_by_title_all(&self, title: &str) -> Result<Vec<NoteSummary>> {
+        let title = title.to_lowercase();
+        
+        let notes = self
+            .list_notes(None)?
+            .into_iter()
+            .filter(|note| note.title.to_lowercase() == title)
+            .collect();
+        
+        Ok(notes)
+    }
+    
+    /// Finds notes whose content contains a substring
+    /// 
+    /// Reads every note line by line without using the search index, so it
+    /// also works while the index is missing or being rebuilt. The substring
+    /// must be contained in a single line.
+    /// 
+    /// # Parameters
+    /// * `substring` - Text to look for
+    /// * `case_sensitive` - Whether letter case must match
+    /// * `limit` - Maximum number of notes to return
+    /// 
+    /// # Returns
+    /// Summaries of the matching notes in listing order
+    pub fn find_notes_by_content_contains(
+        &self,
+        substring: &str,
+        case_sensitive: bool,
+        limit: usize,
+    ) -> Result<Vec<NoteSummary>> {
+        if substring.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        
+        let needle = if case_sensitive {
+            substring.to_string()
+        } else {
+            substring.to_lowercase()
+        };
+        
+        let mut matches = Vec::new();
+        
+        for summary in self.list_notes(None)? {
+            let path = self.get_note_absolute_path(&summary.id)?;
+            
+            if file_contains_line(&path, &needle, case_sensitive) {
+                matches.push(summary);
+                if matches.len() >= limit {
+                    break;
+                }
+            }
+        }
+        
+        Ok(matches)
+    }
+    
+    /// Finds all notes that link to a specific note
+    /// 
+    /// # Parameters
+    /// * `note_title` - Title of the note to find backlinks for
+    /// 
+    /// # Returns
+    /// A list of note summaries that link to the specified note
+    pub fn find_backlinks(&self, note_title: &str) -> Result<Vec<NoteSummary>> {
+        // List all notes
+        let notes = self.list_notes(None)?;
+        let mut backlinks = Vec::new();
+        
+        // Regular expression to find [[Note Title]] patterns
+        let link_pattern = format!(r"\[\[{}\]\]", regex::escape(note_title));
+        let regex = regex::Regex::new(&link_pattern)?;
+        
+        // Check each note for links to the specified note
+        for summary in notes {
+            // Skip notes deleted since the listing was built
+            if !self.check_note_exists(&summary.id) {
+                continue;
+            }
+            
+            let path = self.get_note_absolute_path(&summary.id)?;
+            
+            // Check if the file contains the link pattern
+            // We'll read the file in chunks to avoid loading the entire file
+            if self.file_contains_pattern(&path, &regex)? {
+                backlinks.push(summary);
+            }
+        }
+        
+        Ok(backlinks)
+    }
+    
+    /// Replaces links to a note's old title with links to its new title
+    /// 
+    /// # Parameters
+    /// * `old_title` - The original title of the note
+    /// * `new_title` - The new title of the note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn update_backlinks(&self, old_title: &str, new_title: &str) -> Result<()> {
+        // Links to a title shared by several notes resolve to an arbitrary one of them
+        let matches = self.find_notes_by_title_all(new_title)?;
+        if matches.len() > 1 {
+            log::warn!(
+                "Links to [[{}]] are ambiguous: {} notes have this title",
+                new_title,
+                matches.len()
+            );
+        }
+        
+        // Find all notes that link to the old title
+        let backlinks = self.find_backlinks(old_title)?;
+        
+        // Update each backlink
+        for backlink in backlinks {
+            // Get the full note content
+            let backlink_note = self.get_note(&backlink.id)?;
+            
+            // Replace [[Old Title]] with [[New Title]] in the content
+            let updated_content = backlink_note.content.replace(
+                &format!("[[{}]]", old_title),
+                &format!("[[{}]]", new_title)
+            );
+            
+            // Save the updated content
+            self.update_note_content(&backlink.id, &updated_content)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Builds the graph of wikilinks between all notes
+    /// 
+    /// Links whose target cannot be resolved to a note are omitted from the edges,
+    /// but the notes containing them are still included as nodes. Notes that
+    /// cannot be read are included without outgoing edges.
+    /// 
+    /// # Returns
+    /// All notes as nodes and the resolved links as directed edges
+    pub fn get_link_graph(&self) -> Result<GraphData> {
+        let notes = self.list_notes(None)?;
+        
+        // Resolve titles like find_note_by_title: case-insensitive, first match wins
+        let mut title_to_id: HashMap<String, String> = HashMap::new();
+        for note in &notes {
+            title_to_id
+                .entry(note.title.to_lowercase())
+                .or_insert_with(|| note.id.clone());
+        }
+        
+        let mut nodes = Vec::with_capacity(notes.len());
+        let mut edges = Vec::new();
+        
+        for summary in notes {
+            let content = self
+                .get_note_path(&summary.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| fs::read_to_string(path).context("Failed to read note file"))
+                .unwrap_or_else(|e| {
+                    log::warn!("Skipping links of note {} in the link graph: {}", summary.id, e);
+                    String::new()
+                });
+            
+            // Only keep one edge per source and target pair
+            let mut linked_ids = HashSet::new();
+            for target in extract_wikilink_targets(&content) {
+                if let Some(target_id) = title_to_id.get(&target.to_lowercase()) {
+                    if linked_ids.insert(target_id.clone()) {
+                        edges.push(GraphEdge {
+                            source_id: summary.id.clone(),
+                            target_id: target_id.clone(),
+                        });
+                    }
+                }
+            }
+            
+            nodes.push(GraphNode {
+                id: summary.id,
+                title: summary.title,
+                tags: summary.tags,
+            });
+        }
+        
+        Ok(GraphData { nodes, edges })
+    }
+    
+    /// Finds wikilinks that do not resolve to any note
+    /// 
+    /// Targets are resolved case-insensitively by title, like `find_note_by_title`,
+    /// or by path without extension for `folder/Note` targets. Heading and block
+    /// references (`#...`) are ignored. Notes that cannot be read are skipped.
+    /// 
+    /// # Parameters
+    /// * `ids` - IDs of the notes to check, or None to check all notes
+    /// 
+    /// # Returns
+    /// The broken links, sorted by source note title and then target
+    pub fn validate_wikilinks(&self, ids: Option<&[&str]>) -> Result<Vec<BrokenLink>> {
+        let notes = self.list_notes(None)?;
+        
+        let titles: HashSet<String> = notes
+            .iter()
+            .map(|note| note.title.to_lowercase())
+            .collect();
+        
+        let paths: HashSet<String> = notes
+            .iter()
+            .filter_map(|note| self.get_note_path(&note.id).ok())
+            .filter_map(|path| {
+                let relative_path = path.strip_prefix(&self.notes_dir).ok()?.with_extension("");
+                Some(relative_path.to_string_lossy().replace('\\', "/").to_lowercase())
+            })
+            .collect();
+        
+        let resolves = |target: &str| {
+            let target = wikilink_target_name(target).to_lowercase();
+            target.is_empty()
+                || titles.contains(&target)
+                || (target.contains('/') && paths.contains(target.trim_start_matches('/')))
+        };
+        
+        let sources: Vec<NoteSummary> = match ids {
+            Some(ids) => notes
+                .into_iter()
+                .filter(|note| ids.contains(&note.id.as_str()))
+                .collect(),
+            None => notes,
+        };
+        
+        let mut broken_links = Vec::new();
+        
+        for source in sources {
+            let content = match self
+                .get_note_path(&source.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| fs::read_to_string(path).context("Failed to read note file"))
+            {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!("Skipping note {} while validating links: {}", source.id, e);
+                    continue;
+                }
+            };
+            
+            for (index, line) in content.lines().enumerate() {
+                for target in extract_wikilink_targets(line) {
+                    if !resolves(&target) {
+                        broken_links.push(BrokenLink {
+                            source_note_id: source.id.clone(),
+                            source_note_title: source.title.clone(),
+                            broken_target: target,
+                            line_number: u32::try_from(index + 1).ok(),
+                        });
+                    }
+                }
+            }
+        }
+        
+        broken_links.sort_by(|a, b| {
+            compare(&a.source_note_title, &b.source_note_title)
+                .then_with(|| a.broken_target.cmp(&b.broken_target))
+        });
+        
+        Ok(broken_links)
+    }
+    
+    /// Extracts the targets of all wikilinks in a note
+    /// 
+    /// Both `[[Target]]` and `[[Target|Alias]]` forms are recognized.
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// 
+    /// # Returns
+    /// The link targets in the order they appear
+    pub fn extract_wikilinks(&self, id: &str) -> Result<Vec<String>> {
+        let path = self.get_note_path(id)?;
+        let content = fs::read_to_string(&path)
+            .context("Failed to read note file")?;
+        
+        Ok(extract_wikilink_targets(&content))
+    }
+    
+    /// Links two notes to each other with wikilinks
+    /// 
+    /// `[[TargetTitle]]` is appended on a new line at the end of the source
+    /// note and `[[SourceTitle]]` at the end of the target note. A note that
+    /// already links to the other one, with or without an alias, is left
+    /// unchanged.
+    /// 
+    /// # Parameters
+    /// * `source_id` - ID of the note to link from
+    /// * `target_id` - ID of the note to link to
+    /// * `link_text` - Optional alias shown for the link in the source note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn link_notes(&self, source_id: &str, target_id: &str, link_text: Option<&str>) -> Result<()> {
+        let source = self.get_note(source_id)?;
+        let target = self.get_note(target_id)?;
+        
+        if source.id == target.id {
+            anyhow::bail!("A note cannot be linked to itself");
+        }
+        
+        let target_link = match link_text.map(str::trim).filter(|text| !text.is_empty()) {
+            Some(text) => format!("[[{}|{}]]", target.title, text),
+            None => format!("[[{}]]", target.title),
+        };
+        self.append_link(&source.id, &target.title, &target_link)?;
+        
+        let source_link = format!("[[{}]]", source.title);
+        self.append_link(&target.id, &source.title, &source_link)?;
+        
+        Ok(())
+    }
+    
+    /// Appends a wikilink to a note unless the note already links to its target
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to append the link to
+    /// * `target_title` - Title the link points to
+    /// * `link` - The wikilink to append
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    fn append_link(&self, id: &str, target_title: &str, link: &str) -> Result<()> {
+        let path = self.get_note_path(id)?;
+        
+        if self.file_contains_pattern(&path, &wikilink_regex(target_title)?)? {
+            return Ok(());
+        }
+        
+        let mut content = fs::read_to_string(&path)
+            .context("Failed to read note file")?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(link);
+        content.push('\n');
+        
+        self.update_note_content(id, &content)?;
+        
+        Ok(())
+    }
+    
+    /// Removes the wikilinks between two notes
+    /// 
+    /// Links are removed with or without an alias. Lines that only contained
+    /// removed links are dropped. Notes without such links are left unchanged.
+    /// 
+    /// # Parameters
+    /// * `source_id` - ID of the note to remove the links from
+    /// * `target_id` - ID of the note the links point to
+    /// * `bidirectional` - Whether to also remove links from the target note to the source note
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn unlink_notes(&self, source_id: &str, target_id: &str, bidirectional: bool) -> Result<()> {
+        let source = self.get_note(source_id)?;
+        let target = self.get_note(target_id)?;
+        
+        self.remove_links(&source, &target.title)?;
+        
+        if bidirectional {
+            self.remove_links(&target, &source.title)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Removes all wikilinks to a title from a note
+    /// 
+    /// # Parameters
+    /// * `note` - The note to remove the links from
+    /// * `target_title` - Title the links point to
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    fn remove_links(&self, note: &Note, target_title: &str) -> Result<()> {
+        let regex = wikilink_regex(target_title)?;
+        if !regex.is_match(&note.content) {
+            return Ok(());
+        }
+        
+        let mut lines = Vec::new();
+        for line in note.content.split('\n') {
+            if !regex.is_match(line) {
+                lines.push(line.to_string());
+                continue;
+            }
+            
+            let kept = regex.replace_all(line, "");
+            if !kept.trim().is_empty() {
+                lines.push(kept.trim_end().to_string());
+            }
+        }
+        
+        self.update_note_content(&note.id, &lines.join("\n"))?;
+        
+        Ok(())
+    }
+    
+    /// Suggests notes whose titles match a partial wikilink, for autocompletion
+    /// 
+    /// Case-insensitive prefix matches come first; substring matches are only
+    /// added if there are fewer than `limit` prefix matches.
+    /// 
+    /// # Parameters
+    /// * `partial` - Partially typed link target
+    /// * `limit` - Maximum number of suggestions to return
+    /// 
+    /// # Returns
+    /// Matching note summaries
+    pub fn suggest_wikilinks(&self, partial: &str, limit: usize) -> Result<Vec<NoteSummary>> {
+        self.search_titles(partial.trim(), limit)
+    }
+    
+    /// Finds notes whose titles match a search string
+    /// 
+    /// Matching happens in memory without the search index, which makes it
+    /// cheap enough for filtering as the user types. Case-insensitive prefix
+    /// matches come first, followed by substring matches, each in natural
+    /// title order.
+    /// 
+    /// # Parameters
+    /// * `prefix` - Search string to match titles against
+    /// * `limit` - Maximum number of notes to return
+    /// 
+    /// # Returns
+    /// Matching note summaries
+    pub fn search_titles(&self, prefix: &str, limit: usize) -> Result<Vec<NoteSummary>> {
+        let prefix = prefix.to_lowercase();
+        
+        let mut notes: Vec<(String, NoteSummary)> = self
+            .list_notes(None)?
+            .into_iter()
+            .map(|note| (note.title.to_lowercase(), note))
+            .filter(|(title, _)| title.contains(&prefix))
+            .collect();
+        
+        notes.sort_by(|(a, _), (b, _)| {
+            b.starts_with(&prefix)
+                .cmp(&a.starts_with(&prefix))
+                .then_with(|| compare(a, b))
+        });
+        
+        Ok(notes.into_iter().take(limit).map(|(_, note)| note).collect())
+    }
+    
+    /// Finds the notes most related to a note
+    /// 
+    /// Similarity is based on shared tags, shared wikilink targets and
+    /// whether both notes live in the same folder. Tags of all notes are read
+    /// from their full content. Notes that cannot be read are skipped.
+    /// 
+    /// # Parameters
+    /// * `id` - ID of the note to find related notes for
+    /// * `limit` - Maximum number of related notes to return
+    /// 
+    /// # Returns
+    /// Related notes with their scores, most related first
+    pub fn get_related_notes(&self, id: &str, limit: usize) -> Result<Vec<(NoteSummary, f32)>> {
+        let source = self.get_note(id)?;
+        let source_links = normalized_link_targets(&source.content);
+        let source_dir = Path::new(&source.path).parent().map(Path::to_path_buf);
+        
+        let mut related = Vec::new();
+        
+        for summary in self.list_notes(None)? {
+            if summary.id == source.id {
+                continue;
+            }
+            
+            let read = self
+                .get_note_path(&summary.id)
+                .map_err(anyhow::Error::from)
+                .and_then(|path| Ok((fs::read_to_string(&path).context("Failed to read note file")?, path)));
+            let (content, path) = match read {
+                Ok(read) => read,
+                Err(e) => {
+                    log::warn!("Skipping note {} while finding related notes: {}", summary.id, e);
+                    continue;
+                }
+            };
+            
+            // Summary tags only come from the first lines, the source tags from the whole note
+            let tags = self.note_tags(&summary.file_type, &content);
+            let links = normalized_link_targets(&content);
+            
+            let same_dir = path
+                .strip_prefix(&self.notes_dir)
+                .ok()
+                .map(|relative| relative.parent().map(Path::to_path_buf))
+                .map_or(false, |dir| dir == source_dir);
+            
+            let score = related_score(&source.tags, &tags, &source_links, &links, same_dir);
+            if score > 0.0 {
+                related.push((summary, score));
+            }
+        }
+        
+        related.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        related.truncate(limit);
+        
+        Ok(related)
+    }
+    
+    /// Finds notes with a line matching a regular expression
+    /// 
+    /// Files are read line by line, so patterns never match across lines.
+    /// 
+    /// # Parameters
+    /// * `pattern` - Regular expression to search for
+    /// * `max_results` - Maximum number of notes to return
+    /// 
+    /// # Returns
+    /// Summaries of the first matching notes, or an error if the pattern is invalid
+    pub fn find_notes_matching_regex(&self, pattern: &str, max_results: usize) -> Result<Vec<NoteSummary>> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| anyhow::anyhow!("Invalid regular expression: {}", e))?;
+        
+        let mut matches = Vec::new();
+        
+        for summary in self.list_notes(None)? {
+            if matches.len() >= max_results {
+                break;
+            }
+            
+            let path = self.get_note_path(&summary.id)?;
+            let file = match fs::File::open(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    log::warn!("Skipping unreadable note {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            
+            let is_match = std::io::BufRead::lines(std::io::BufReader::new(file))
+                .map_while(|line| line.ok())
+                .any(|line| regex.is_match(&line));
+            
+            if is_match {
+                matches.push(summary);
+            }
+        }
+        
+        Ok(matches)
+    }
+    
+    /// Checks if a file contains a specific regex pattern
+    /// 
+    /// # Parameters
+    /// * `path` - Path to the file to check
+    /// * `pattern` - Regex pattern to search for
+    /// 
+    /// # Returns
+    /// True if the file contains the pattern, false otherwise
+    fn file_contains_pattern(&self, path: &Path, pattern: &Regex) -> Result<bool> {
+        // Use a line-by-line approach which is safer for UTF-8 text
+        let file = fs::File::open(path)
+            .context("Failed to open note file")?;
+        let reader = std::io::BufReader::new(file);
+        
+        // We'll read the file line by line, but keep a buffer of recent lines
+        // to handle patterns that might span multiple lines
+        const BUFFER_LINES: usize = 5; // Keep last 5 lines in buffer
+        let mut line_buffer = Vec::with_capacity(BUFFER_LINES);
+        
+        // Process each line
+        for line_result in std::io::BufRead::lines(reader) {
+            let line = line_result.context("Failed to read line from file")?;
+            
+            // Add the new line to our buffer
+            line_buffer.push(line);
+            
+            // If buffer is larger than our desired size, remove oldest line
+            if line_buffer.len() > BUFFER_LINES {
+                line_buffer.remove(0);
+            }
+            
+            // Join the buffer lines and check for pattern
+            let text = line_buffer.join("\n");
+            if pattern.is_match(&text) {
+                return Ok(true);
+            }
+        }
+        
+        Ok(false)
+    }
+}
+
+/// Turns a note title into a name that is valid as a file name on all platforms
+///
+/// Path separators and characters reserved on Windows are replaced with `-`,
+/// and leading or trailing dots and spaces are removed. Unicode letters and
+/// digits are kept as-is.
+///
+/// # Parameters
+/// * `title` - Title of the note
+///
+/// # Returns
+/// The sanitized file name, without extension
+pub fn sanitize_title_for_filename(title: &str) -> String {
+    let sanitized: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+
+    let sanitized = sanitized.trim_matches(|c| c == '.' || c == ' ');
+
+    if sanitized.is_empty() {
+        "Untitled".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+/// Checks whether a directory can be used as the notes directory
+///
+/// # Parameters
+/// * `path` - Path of the directory to check
+///
+/// # Returns
+/// The results of the checks; a missing directory fails all checks
+pub fn validate_notes_dir(path: &Path) -> Result<DirValidation> {
+    if !path.is_dir() {
+        return Ok(DirValidation {
+            is_dir: false,
+            can_read: false,
+            can_write: false,
+            estimated_note_count: 0,
+            estimated_size_bytes: 0,
+            is_cloud_synced: false,
+        });
+    }
+
+    let can_read = fs::read_dir(path).is_ok();
+
+    // The temporary file is removed when dropped
+    let can_write = tempfile::Builder::new()
+        .prefix(".notter-write-check")
+        .tempfile_in(path)
+        .is_ok();
+
+    let mut estimated_note_count = 0;
+    let mut estimated_size_bytes = 0;
+
+    if can_read {
+        let note_files = WalkDir::new(path)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_reserved_dir(entry))
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file() && is_note_file(entry.path()));
+
+        for entry in note_files {
+            estimated_note_count += 1;
+            estimated_size_bytes += entry.metadata().map_or(0, |metadata| metadata.len());
+        }
+    }
+
+    Ok(DirValidation {
+        is_dir: true,
+        can_read,
+        can_write,
+        estimated_note_count,
+        estimated_size_bytes,
+        is_cloud_synced: is_cloud_synced(path),
+    })
+}
+
+/// Checks whether a directory appears to be inside a cloud-synced folder
+///
+/// Dropbox is detected by its `.dropbox` marker in the directory or any
+/// ancestor, OneDrive and iCloud Drive by their folder names, and iCloud
+/// additionally by `.icloud` placeholder files for evicted files.
+fn is_cloud_synced(path: &Path) -> bool {
+    let has_dropbox_marker = path.ancestors().any(|dir| dir.join(".dropbox").exists());
+
+    let has_synced_folder_name = path.components().any(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        name.starts_with("OneDrive") || name == "Mobile Documents" || name == "iCloud Drive"
+    });
+
+    let has_icloud_placeholders = fs::read_dir(path).map_or(false, |entries| {
+        entries
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.path().extension().map_or(false, |ext| ext == "icloud"))
+    });
+
+    has_dropbox_marker || has_synced_folder_name || has_icloud_placeholders
+}
+
+/// Builds a folder node and its subfolders from per-folder note counts
+///
+/// # Parameters
+/// * `path` - Path of the folder relative to the notes directory
+/// * `name` - Display name of the folder
+/// * `note_counts` - Number of notes directly inside each folder
+/// * `children` - Subfolders of each folder
+fn build_folder_node(
+    path: PathBuf,
+    name: String,
+    note_counts: &HashMap<PathBuf, usize>,
+    children: &HashMap<PathBuf, Vec<PathBuf>>,
+) -> FolderNode {
+    let mut child_nodes: Vec<FolderNode> = children
+        .get(&path)
+        .into_iter()
+        .flatten()
+        .map(|child| {
+            let child_name = child
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            build_folder_node(child.clone(), child_name, note_counts, children)
+        })
+        .collect();
+    child_nodes.sort_by(|a, b| compare(&a.name, &b.name));
+    
+    FolderNode {
+        name,
+        path: path.to_string_lossy().to_string(),
+        note_count: note_counts.get(&path).copied().unwrap_or(0),
+        children: child_nodes,
+    }
+}
+
+/// Sorts note summaries
+///
+/// # Parameters
+/// * `notes` - The summaries to sort
+/// * `sort` - Sort options applied left to right, each breaking the ties of
+///   the previous ones; the default sort is used if empty
+fn sort_summaries(notes: &mut [NoteSummary], sort: &[SortOption]) {
+    if sort.is_empty() {
+        notes.sort_by(|a, b| compare_summaries(a, b, &SortOption::default()));
+        return;
+    }
+    
+    notes.sort_by(|a, b| {
+        sort.iter().fold(std::cmp::Ordering::Equal, |ordering, option| {
+            ordering.then_with(|| compare_summaries(a, b, option))
+        })
+    });
+}
+
+/// Compares two note summaries by a single sort option
+fn compare_summaries(a: &NoteSummary, b: &NoteSummary, sort: &SortOption) -> std::cmp::Ordering {
+    match sort {
+        // Use natural sorting for title comparisons
+        SortOption::TitleAsc => compare(&a.title, &b.title),
+        SortOption::TitleDesc => compare(&b.title, &a.title),
+        SortOption::CreatedNewest => b.created.cmp(&a.created),
+        SortOption::CreatedOldest => a.created.cmp(&b.created),
+        SortOption::ModifiedNewest => b.modified.cmp(&a.modified),
+        SortOption::ModifiedOldest => a.modified.cmp(&b.modified),
+        SortOption::WordCountAsc => a.word_count.cmp(&b.word_count),
+        SortOption::WordCountDesc => b.word_count.cmp(&a.word_count),
+        SortOption::SizeAsc => a.size_bytes.cmp(&b.size_bytes),
+        SortOption::SizeDesc => b.size_bytes.cmp(&a.size_bytes),
+    }
+}
+
+/// Checks whether a line of a file contains a substring
+///
+/// # Parameters
+/// * `path` - Path of the file
+/// * `needle` - Substring to look for, already lowercased if not case-sensitive
+/// * `case_sensitive` - Whether letter case must match
+///
+/// # Returns
+/// False if no line contains the substring or the file cannot be read
+fn file_contains_line(path: &Path, needle: &str, case_sensitive: bool) -> bool {
+    let Ok(file) = fs::File::open(path) else {
+        // Notes may be deleted after a listing was built
+        return false;
+    };
+
+    std::io::BufRead::lines(std::io::BufReader::new(file))
+        .map_while(|line| line.ok())
+        .any(|line| {
+            if case_sensitive {
+                line.contains(needle)
+            } else {
+                line.to_lowercase().contains(needle)
+            }
+        })
+}
+
+/// Checks if a path has a supported note file extension
+fn is_note_file(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "md" || ext == "txt")
+}
+
+/// Checks if a directory entry is one of the reserved application directories
+fn is_reserved_dir(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && entry
+            .file_name()
+            .to_str()
+            .map_or(false, |name| RESERVED_DIRS.contains(&name))
+}
+
+/// Checks if a directory entry is one of the user-excluded folders
+fn is_excluded_dir(entry: &walkdir::DirEntry, excluded_folders: &[String]) -> bool {
+    if !entry.file_type().is_dir() {
+        return false;
+    }
+
+    entry
+        .file_name()
+        .to_str()
+        .map_or(false, |name| is_excluded_name(name, excluded_folders))
+}
+
+/// Checks if a folder name is one of the user-excluded folders
+///
+/// Folder names are compared case-insensitively on macOS and Windows, whose
+/// file systems are case-insensitive by default.
+fn is_excluded_name(name: &str, excluded_folders: &[String]) -> bool {
+    excluded_folders.iter().any(|folder| {
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            folder.to_lowercase() == name.to_lowercase()
+        } else {
+            folder == name
+        }
+    })
+}
+
+/// Splits YAML frontmatter from the rest of note content
+///
+/// Frontmatter must start on the first line with `---` and end with a line
+/// containing only `---`.
+///
+/// # Returns
+/// The frontmatter without its delimiters, if present, and the remaining content
+pub(crate) fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    // No closing delimiter, so this is not frontmatter
+    (None, content)
+}
+
+/// Gets the part of a file name before its extension
+fn file_stem_of(filename: &str) -> String {
+    Path::new(filename)
+        .file_stem()
+        .map_or(String::new(), |stem| stem.to_string_lossy().to_string())
+}
+
+/// Gets the title of a note
+///
+/// * Markdown: the first line after any frontmatter
+/// * Plain text: the file name
+/// * Org: the `#+TITLE:` directive, or the file name
+fn note_title(file_type: &NoteType, path: &Path, content: &str) -> String {
+    let file_stem = || path.file_stem().and_then(|stem| stem.to_str()).map(|s| s.to_string());
+    
+    let title = match file_type {
+        NoteType::Markdown => markdown_title(content),
+        NoteType::PlainText => file_stem(),
+        NoteType::Org => org_title(content).or_else(file_stem),
+    };
+    
+    title.unwrap_or_else(|| "Untitled Note".to_string())
+}
+
+/// Gets the title of Markdown content from its first line after any frontmatter
+fn markdown_title(content: &str) -> Option<String> {
+    let (_, body) = split_frontmatter(content);
+
+    body.lines()
+        .next()
+        .map(|line| line.trim_start_matches('#').trim().to_string())
+}
+
+/// Gets the title of Org-mode content from its `#+TITLE:` directive
+fn org_title(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        org_keyword_value(line, "TITLE")
+            .filter(|title| !title.is_empty())
+            .map(|title| title.to_string())
+    })
+}
+
+/// Extracts the tags of Org-mode content
+///
+/// Tags are taken from the `#+FILETAGS:` directive and from the `:tag:`
+/// lists at the end of headings. Each tag is only returned once.
+fn org_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let tag_list = if let Some(value) = org_keyword_value(line, "FILETAGS") {
+            value
+        } else if line.starts_with('*') && line.trim_start_matches('*').starts_with(' ') {
+            match line.split_whitespace().last() {
+                Some(last) if last.len() > 1 && last.starts_with(':') && last.ends_with(':') => last,
+                _ => continue,
+            }
+        } else {
+            continue;
+        };
+
+        for tag in tag_list.split(|c: char| c == ':' || c.is_whitespace()) {
+            if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+
+    tags
+}
+
+/// Gets the value of an Org-mode `#+KEYWORD:` line (the keyword is case-insensitive)
+fn org_keyword_value<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line.trim_start().strip_prefix("#+")?;
+    let (name, value) = rest.split_once(':')?;
+
+    name.eq_ignore_ascii_case(keyword).then(|| value.trim())
+}
+
+/// Replaces the title heading of Markdown content
+///
+/// The title heading is the first line after any frontmatter, if it is a
+/// level-one `# Heading`.
+///
+/// # Parameters
+/// * `content` - Markdown content
+/// * `title` - New title
+///
+/// # Returns
+/// The updated content, or None if the content does not start with a title heading
+fn replace_markdown_heading(content: &str, title: &str) -> Option<String> {
+    let (_, body) = split_frontmatter(content);
+    let frontmatter = &content[..content.len() - body.len()];
+
+    let (first_line, rest) = match body.split_once('\n') {
+        Some((first_line, rest)) => (first_line, Some(rest)),
+        None => (body, None),
+    };
+
+    let heading = first_line.trim_end_matches('\r');
+    if heading != "#" && !heading.starts_with("# ") {
+        return None;
+    }
+    let line_ending = &first_line[heading.len()..];
+
+    let mut updated = format!("{}# {}{}", frontmatter, title, line_ending);
+    if let Some(rest) = rest {
+        updated.push('\n');
+        updated.push_str(rest);
+    }
+
+    Some(updated)
+}
+
+/// Gets the value of a top-level key from YAML frontmatter
+fn frontmatter_value<'a>(frontmatter: &'a str, key: &str) -> Option<&'a str> {
+    frontmatter.lines().find_map(|line| {
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\''))
+    })
+}
+
+/// Reads the UUID stored as `id` in a note's frontmatter
+///
+/// Only the lines up to the end of the frontmatter are read.
+///
+/// # Parameters
+/// * `path` - Path to the note file
+///
+/// # Returns
+/// The UUID, if the note has frontmatter with a valid `id`
+fn read_frontmatter_id(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    let mut lines = std::io::BufRead::lines(std::io::BufReader::new(file));
+
+    if lines.next()?.ok()?.trim_end() != "---" {
+        return None;
+    }
+
+    for line in lines {
+        let line = line.ok()?;
+        if line.trim_end() == "---" {
+            return None;
+        }
+
+        if let Some(value) = frontmatter_value(&line, "id") {
+            return uuid::Uuid::parse_str(value).ok().map(|id| id.to_string());
+        }
+    }
+
+    None
+}
+
+/// Adds an `id` to the frontmatter of note content
+///
+/// Content without frontmatter gets a new frontmatter block; an existing `id`
+/// is left untouched.
+///
+/// # Parameters
+/// * `content` - Note content
+/// * `id` - ID to add
+///
+/// # Returns
+/// The content with the ID in its frontmatter
+fn with_frontmatter_id(content: &str, id: &str) -> String {
+    match split_frontmatter(content) {
+        (Some(frontmatter), _) if frontmatter_value(frontmatter, "id").is_some() => content.to_string(),
+        (Some(_), _) => format!("---\nid: {}\n{}", id, content.split_once('\n').map_or("", |(_, rest)| rest)),
+        (None, _) => format!("---\nid: {}\n---\n{}", id, content),
+    }
+}
+
+/// Extracts the values of the `tags` key from YAML frontmatter
+///
+/// Supports flow lists (`tags: [a, b]`), block lists (`- a` on the following
+/// lines) and comma-separated scalars (`tags: a, b`), without a full YAML parse.
+pub(crate) fn frontmatter_tags(frontmatter: &str) -> Vec<String> {
+    let clean = |value: &str| {
+        value
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .trim_start_matches('#')
+            .to_string()
+    };
+
+    let mut lines = frontmatter.lines().peekable();
+    let mut tags = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let Some(value) = line.strip_prefix("tags:") else {
+            continue;
+        };
+        let value = value.trim();
+
+        if value.is_empty() {
+            // Block list on the following indented lines
+            while let Some(item) = lines.peek().and_then(|next| next.trim_start().strip_prefix('-')) {
+                tags.push(clean(item));
+                lines.next();
+            }
+        } else {
+            let value = value
+                .strip_prefix('[')
+                .and_then(|inner| inner.strip_suffix(']'))
+                .unwrap_or(value);
+            tags.extend(value.split(',').map(clean));
+        }
+    }
+
+    tags.retain(|tag| !tag.is_empty());
+    tags
+}
+
+/// Counts the words in a piece of text
+///
+/// Words are split on Unicode whitespace; tokens made only of markup
+/// characters (such as `#`, `-` or `**`) are not counted.
+fn count_words(text: &str) -> u32 {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+        .count() as u32
+}
+
+/// Builds a plain-text preview from the lines of a note
+///
+/// YAML frontmatter, headings and blank lines are skipped, whitespace is
+/// collapsed and the preview is cut at the last word boundary within
+/// `length` characters. Only as many lines as needed are consumed.
+///
+/// # Parameters
+/// * `lines` - Lines of the note
+/// * `length` - Maximum number of characters in the preview
+///
+/// # Returns
+/// The preview text
+fn note_preview(lines: impl Iterator<Item = String>, length: usize) -> String {
+    let mut preview = String::new();
+    let mut preview_chars = 0;
+    let mut in_frontmatter = false;
+
+    for (index, line) in lines.enumerate() {
+        let line = line.trim();
+
+        if index == 0 && line == "---" {
+            in_frontmatter = true;
+            continue;
+        }
+        if in_frontmatter {
+            in_frontmatter = line != "---";
+            continue;
+        }
+
+        let is_heading = line.starts_with('#')
+            && line.trim_start_matches('#').chars().next().map_or(true, char::is_whitespace);
+        if is_heading {
+            continue;
+        }
+
+        for word in line.split_whitespace() {
+            let word_chars = word.chars().count();
+            let separator = usize::from(!preview.is_empty());
+
+            if preview_chars + separator + word_chars > length {
+                // A single word longer than the preview is cut instead of dropped
+                if preview.is_empty() {
+                    preview = word.chars().take(length).collect();
+                }
+                return preview;
+            }
+
+            if separator == 1 {
+                preview.push(' ');
+            }
+            preview.push_str(word);
+            preview_chars += separator + word_chars;
+        }
+    }
+
+    preview
+}
+
+/// Counts the words and characters of note content
+///
+/// # Parameters
+/// * `content` - Content of the note
+///
+/// # Returns
+/// The number of words and the number of characters, excluding line breaks
+pub(crate) fn content_stats(content: &str) -> (u32, u32) {
+    content.lines().fold((0, 0), |(words, chars), line| {
+        (
+            words.saturating_add(count_words(line)),
+            chars.saturating_add(line.chars().count() as u32),
+        )
+    })
+}
+
+/// Estimates the reading time for a number of words
+pub(crate) fn reading_time_secs(word_count: u32) -> u32 {
+    (word_count as u64 * 60).div_ceil(WORDS_PER_MINUTE as u64) as u32
+}
+
+/// Extracts the targets of all `[[wikilinks]]` in note content
+///
+/// For aliased links (`[[Target|Alias]]`) only the target is returned.
+/// Nested brackets resolve to the innermost link.
+fn extract_wikilink_targets(content: &str) -> Vec<String> {
+    static WIKILINK_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = WIKILINK_REGEX
+        .get_or_init(|| Regex::new(r"\[\[([^\[\]]+)\]\]").expect("Invalid wikilink regex"));
+    
+    regex
+        .captures_iter(content)
+        .filter_map(|captures| captures.get(1))
+        .map(|link| link.as_str().split('|').next().unwrap_or("").trim().to_string())
+        .filter(|target| !target.is_empty())
+        .collect()
+}
+
+/// Gets the note name of a wikilink target, without a `#heading` or `#^block` reference
+fn wikilink_target_name(target: &str) -> &str {
+    target.split('#').next().unwrap_or("").trim()
+}
+
+/// Builds a regex matching the wikilinks to a title and any whitespace before them
+///
+/// Titles are matched case-insensitively, with or without an alias.
+fn wikilink_regex(title: &str) -> Result<Regex> {
+    let pattern = format!(r"(?i)[ \t]*\[\[\s*{}\s*(\|[^\]]*)?\]\]", regex::escape(title));
+    Ok(Regex::new(&pattern)?)
+}
+
+/// Extracts the unique, lowercased wikilink targets of note content
+pub(crate) fn normalized_link_targets(content: &str) -> HashSet<String> {
+    extract_wikilink_targets(content)
+        .into_iter()
+        .map(|target| target.to_lowercase())
+        .collect()
+}
+
+/// Computes the similarity score between two notes
+///
+/// * +1.0 per shared tag, normalized by the number of distinct tags of both notes
+/// * +0.5 per shared wikilink target
+/// * +0.3 if both notes are in the same folder
+fn related_score(
+    tags_a: &[String],
+    tags_b: &[String],
+    links_a: &HashSet<String>,
+    links_b: &HashSet<String>,
+    same_dir: bool,
+) -> f32 {
+    let mut score = 0.0;
+    
+    let all_tags: HashSet<&String> = tags_a.iter().chain(tags_b.iter()).collect();
+    if !all_tags.is_empty() {
+        let shared_tags = tags_a.iter().filter(|tag| tags_b.contains(tag)).count();
+        score += shared_tags as f32 / all_tags.len() as f32;
+    }
+    
+    score += 0.5 * links_a.intersection(links_b).count() as f32;
+    
+    if same_dir {
+        score += 0.3;
+    }
+    
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn links(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_related_score_tags_are_normalized() {
+        let score = related_score(&tags(&["rust", "tauri"]), &tags(&["rust", "notes"]), &links(&[]), &links(&[]), false);
+        // One shared tag out of three distinct tags
+        assert!((score - 1.0 / 3.0).abs() < f32::EPSILON);
+
+        let score = related_score(&tags(&["rust"]), &tags(&["rust"]), &links(&[]), &links(&[]), false);
+        assert!((score - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_related_score_links_and_folder() {
+        let score = related_score(&[], &[], &links(&["a", "b", "c"]), &links(&["b", "c"]), true);
+        assert!((score - 1.3).abs() < 1e-6);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_notes_with_preview_skips_unreadable_notes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("A", "first words", NoteType::PlainText, None).unwrap();
+        manager.create_note("B", "b", NoteType::PlainText, None).unwrap();
+        manager.list_notes(None).unwrap();
+
+        // The note becomes unreadable after it was listed
+        let locked = temp_dir.path().join("B.txt");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        let notes = manager.list_notes_with_preview(None, 100);
+        let readable = fs::File::open(&locked).is_ok();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let notes = notes.unwrap();
+        if readable {
+            // Permissions are not enforced, e.g. when running as root
+            return;
+        }
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].preview.as_deref(), Some("first words"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_link_graph_skips_unreadable_notes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("A", "links to [[B]]", NoteType::PlainText, None).unwrap();
+        let b = manager.create_note("B", "b", NoteType::PlainText, None).unwrap();
+        manager.create_note("C", "links to [[A]]", NoteType::PlainText, None).unwrap();
+        manager.list_notes(None).unwrap();
+
+        // The note becomes unreadable after it was listed
+        let locked = temp_dir.path().join("C.txt");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        let graph = manager.get_link_graph();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let graph = graph.unwrap();
+        assert!(graph
+            .edges
+            .iter()
+            .any(|edge| edge.source_id == a.id && edge.target_id == b.id));
+    }
+
+    #[test]
+    fn test_get_related_notes_reads_tags_from_full_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        fs::create_dir(temp_dir.path().join("a")).unwrap();
+        fs::create_dir(temp_dir.path().join("b")).unwrap();
+        fs::write(temp_dir.path().join("a").join("Source.md"), "# Source\n#rust\n").unwrap();
+        let filler = "line\n".repeat(60);
+        fs::write(temp_dir.path().join("b").join("Late.md"), format!("# Late\n{}#rust\n", filler)).unwrap();
+        fs::write(temp_dir.path().join("b").join("Other.md"), "# Other\n#cooking\n").unwrap();
+
+        let source_id = manager.find_note_by_title("Source").unwrap().unwrap();
+        let related = manager.get_related_notes(&source_id, 10).unwrap();
+
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].0.title, "Late");
+        assert!((related[0].1 - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_related_score_unrelated_notes() {
+        let score = related_score(&tags(&["rust"]), &tags(&["cooking"]), &links(&["a"]), &links(&["b"]), false);
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn test_extract_tags_frontmatter_only() {
+        let manager = NoteManager::new(PathBuf::from("notes"));
+
+        let flow = manager.extract_tags("---\ntags: [rust, programming]\n---\n# Note\n");
+        assert_eq!(flow, tags(&["rust", "programming"]));
+
+        let block = manager.extract_tags("---\ntitle: Note\ntags:\n  - rust\n  - \"programming\"\n---\nBody\n");
+        assert_eq!(block, tags(&["rust", "programming"]));
+    }
+
+    #[test]
+    fn test_extract_tags_inline_only() {
+        let manager = NoteManager::new(PathBuf::from("notes"));
+
+        let extracted = manager.extract_tags("# Note\n\nLearning #rust and #programming.\n");
+        assert_eq!(extracted, tags(&["rust", "programming"]));
+    }
+
+    #[test]
+    fn test_extract_tags_mixed_are_deduplicated() {
+        let manager = NoteManager::new(PathBuf::from("notes"));
+
+        let extracted = manager.extract_tags("---\ntags: [rust, programming]\n---\n# Note\n\n#rust #async\n");
+        assert_eq!(extracted, tags(&["rust", "programming", "async"]));
+    }
+
+    #[test]
+    fn test_sanitize_title_for_filename() {
+        assert_eq!(sanitize_title_for_filename("a/b\\c:d*e?f\"g<h>i|j"), "a-b-c-d-e-f-g-h-i-j");
+        assert_eq!(sanitize_title_for_filename(" .hidden. "), "hidden");
+        assert_eq!(sanitize_title_for_filename("Café 日本 42"), "Café 日本 42");
+        assert_eq!(sanitize_title_for_filename("..."), "Untitled");
+    }
+
+    #[test]
+    fn test_create_note_with_unsafe_title() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let title = "Meeting: 2024/01/01 - Q&A";
+
+        let note = manager
+            .create_note(title, &format!("# {}\n", title), NoteType::Markdown, None)
+            .unwrap();
+
+        assert_eq!(note.title, title);
+        assert!(temp_dir.path().join("Meeting- 2024-01-01 - Q&A.md").exists());
+    }
+
+    #[test]
+    fn test_uuid_ids_survive_rename_and_move() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_id_scheme(IdScheme::Uuid);
+
+        let note = manager.create_note("Idea", "# Idea\n\nBody #rust\n", NoteType::Markdown, None).unwrap();
+        assert!(uuid::Uuid::parse_str(&note.id).is_ok());
+        assert!(note.content.starts_with(&format!("---\nid: {}\n---\n", note.id)));
+        assert_eq!(note.title, "Idea");
+        assert_eq!(note.tags, tags(&["rust"]));
+
+        let renamed = manager.rename_note(&note.id, "Better Idea", false).unwrap();
+        assert_eq!(renamed.id, note.id);
+
+        let moved = manager.move_note(&note.id, "archive/Better Idea.md").unwrap();
+        assert_eq!(moved.id, note.id);
+
+        // A fresh manager finds the note by rebuilding the cache
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_id_scheme(IdScheme::Uuid);
+        manager.rebuild_id_cache();
+        assert_eq!(manager.get_note(&note.id).unwrap().path, moved.path);
+    }
+
+    #[test]
+    fn test_uuid_ids_only_for_markdown_notes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_id_scheme(IdScheme::Uuid);
+
+        let note = manager.create_note("Plain", "Body", NoteType::PlainText, None).unwrap();
+
+        assert_eq!(note.content, "Body");
+        assert!(uuid::Uuid::parse_str(&note.id).is_err());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("Plain.txt")).unwrap(), "Body");
+    }
+
+    #[test]
+    fn test_id_scheme_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let uuid_manager = NoteManager::new(temp_dir.path().to_path_buf()).with_id_scheme(IdScheme::Uuid);
+        let note = uuid_manager.create_note("Idea", "# Idea\n", NoteType::Markdown, None).unwrap();
+        uuid_manager.create_note("Plain", "Body", NoteType::PlainText, None).unwrap();
+        let path_manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let path_id = path_manager.find_note_by_title("Idea").unwrap().unwrap();
+
+        assert!(uuid_manager.id_scheme_changes(IdScheme::Uuid).is_empty());
+        assert_eq!(
+            uuid_manager.id_scheme_changes(IdScheme::PathBased),
+            HashMap::from([(note.id.clone(), path_id.clone())])
+        );
+        assert_eq!(
+            path_manager.id_scheme_changes(IdScheme::Uuid),
+            HashMap::from([(path_id, note.id)])
+        );
+    }
+
+    #[test]
+    fn test_with_frontmatter_id_keeps_existing_frontmatter() {
+        let content = with_frontmatter_id("---\ntags: [rust]\n---\n# Note\n", "abc");
+        assert_eq!(content, "---\nid: abc\ntags: [rust]\n---\n# Note\n");
+
+        let unchanged = with_frontmatter_id("---\nid: xyz\n---\n", "abc");
+        assert_eq!(unchanged, "---\nid: xyz\n---\n");
+    }
+
+    #[test]
+    fn test_search_titles_prefix_before_substring() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["Project 10", "project 2", "My Project", "Notes"] {
+            fs::write(temp_dir.path().join(format!("{}.txt", name)), name).unwrap();
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let titles: Vec<String> = manager
+            .search_titles("proj", 10)
+            .unwrap()
+            .into_iter()
+            .map(|note| note.title)
+            .collect();
+        assert_eq!(titles, vec!["project 2", "Project 10", "My Project"]);
+
+        assert_eq!(manager.search_titles("proj", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_find_notes_by_title_all_returns_duplicates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("work")).unwrap();
+        fs::write(temp_dir.path().join("Ideas.txt"), "home").unwrap();
+        fs::write(temp_dir.path().join("work").join("ideas.txt"), "work").unwrap();
+        fs::write(temp_dir.path().join("Other.txt"), "other").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        assert_eq!(manager.find_notes_by_title_all("IDEAS").unwrap().len(), 2);
+        assert!(manager.find_notes_by_title_all("Missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_notes_matching_regex() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "Due 2024-01-31\n").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "No date here\n").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "first\nsecond 1999-12-01\n").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let mut titles: Vec<String> = manager
+            .find_notes_matching_regex(r"\d{4}-\d{2}-\d{2}", 10)
+            .unwrap()
+            .into_iter()
+            .map(|note| note.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["a", "c"]);
+
+        assert_eq!(manager.find_notes_matching_regex(r"\d{4}", 1).unwrap().len(), 1);
+
+        let error = manager.find_notes_matching_regex("(unclosed", 10).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid regular expression"));
+    }
+
+    #[test]
+    fn test_get_folder_tree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("work").join("meetings")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("empty")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("private")).unwrap();
+        fs::write(temp_dir.path().join("root.md"), "# Root").unwrap();
+        fs::write(temp_dir.path().join("work").join("a.txt"), "a").unwrap();
+        fs::write(temp_dir.path().join("work").join("b.md"), "# B").unwrap();
+        fs::write(temp_dir.path().join("work").join("image.png"), "png").unwrap();
+        fs::write(temp_dir.path().join("work").join("meetings").join("c.md"), "# C").unwrap();
+        fs::write(temp_dir.path().join("private").join("secret.md"), "# Secret").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf())
+            .with_excluded_folders(vec!["private".to_string()]);
+        let tree = manager.get_folder_tree().unwrap();
+
+        assert_eq!(tree.path, "");
+        assert_eq!(tree.note_count, 1);
+
+        let names: Vec<&str> = tree.children.iter().map(|child| child.name.as_str()).collect();
+        assert_eq!(names, vec!["empty", "work"]);
+        assert_eq!(tree.children[0].note_count, 0);
+
+        let work = &tree.children[1];
+        assert_eq!(work.note_count, 2);
+        assert_eq!(work.children[0].path, Path::new("work").join("meetings").to_string_lossy());
+        assert_eq!(work.children[0].note_count, 1);
+    }
+
+    #[test]
+    fn test_get_stale_notes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(ARCHIVE_DIR)).unwrap();
+        let now = std::time::SystemTime::now();
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+        for (path, age_days) in [("older.txt", 200), ("old.txt", 100), ("fresh.txt", 1), (".archive/archived.txt", 300)] {
+            let path = temp_dir.path().join(path);
+            fs::write(&path, "note").unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - day * age_days)
+                .unwrap();
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let titles: Vec<String> = manager
+            .get_stale_notes(90)
+            .unwrap()
+            .into_iter()
+            .map(|note| note.title)
+            .collect();
+        assert_eq!(titles, vec!["older", "old"]);
+    }
+
+    #[test]
+    fn test_get_notes_modified_after() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let now = std::time::SystemTime::now();
+        for (name, age_secs) in [("old", 3600), ("new", 60)] {
+            let path = temp_dir.path().join(format!("{}.txt", name));
+            fs::write(&path, name).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - std::time::Duration::from_secs(age_secs))
+                .unwrap();
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let threshold = DateTime::<Utc>::from(now - std::time::Duration::from_secs(600));
+
+        let titles: Vec<String> = manager
+            .get_notes_modified_after(threshold)
+            .unwrap()
+            .into_iter()
+            .map(|note| note.title)
+            .collect();
+        assert_eq!(titles, vec!["new"]);
+    }
+
+    #[test]
+    fn test_get_tag_stats() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "#rust #async").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "#rust").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "#rust #cooking").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let stats = manager.get_tag_stats(0, usize::MAX).unwrap();
+        let tags: Vec<(&str, usize)> = stats.iter().map(|stat| (stat.tag.as_str(), stat.count)).collect();
+        assert_eq!(tags, vec![("rust", 3), ("async", 1), ("cooking", 1)]);
+        assert_eq!(stats[0].note_titles, vec!["a", "b", "c"]);
+        assert_eq!(stats[0].note_ids.len(), 3);
+
+        let common = manager.get_tag_stats(2, usize::MAX).unwrap();
+        assert_eq!(common.len(), 1);
+
+        let rare = manager.get_tag_stats(0, 1).unwrap();
+        assert_eq!(rare.len(), 2);
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("medium.txt"), "12345").unwrap();
+        fs::write(temp_dir.path().join("small.txt"), "1").unwrap();
+        fs::write(temp_dir.path().join("large.txt"), "1234567890").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let notes = manager.list_notes(Some(vec![SortOption::SizeDesc])).unwrap();
+        let sizes: Vec<u64> = notes.iter().map(|note| note.size_bytes).collect();
+        assert_eq!(sizes, vec![10, 5, 1]);
+        assert_eq!(manager.get_note_size(&notes[0].id).unwrap(), 10);
+
+        let notes = manager.list_notes(Some(vec![SortOption::SizeAsc])).unwrap();
+        assert_eq!(notes[0].title, "small");
+    }
+
+    #[test]
+    fn test_sort_by_multiple_columns() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "12").unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "34").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "1").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        // Notes of equal size are ordered by the second option
+        let notes = manager
+            .list_notes(Some(vec![SortOption::SizeDesc, SortOption::TitleAsc]))
+            .unwrap();
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, vec!["a", "b", "c"]);
+
+        let notes = manager
+            .list_notes(Some(vec![SortOption::SizeDesc, SortOption::TitleDesc]))
+            .unwrap();
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_note_preview() {
+        let lines = |text: &str| text.lines().map(String::from).collect::<Vec<_>>().into_iter();
+
+        let content = "---\ntags: [rust]\n---\n# Title\n\nFirst   paragraph here.\n## Section\nMore text #tag\n";
+        assert_eq!(note_preview(lines(content), 200), "First paragraph here. More text #tag");
+
+        // Cut at a word boundary
+        assert_eq!(note_preview(lines("one two three"), 9), "one two");
+        assert_eq!(note_preview(lines("abcdefghij"), 4), "abcd");
+        assert_eq!(note_preview(lines("# Only a heading"), 200), "");
+    }
+
+    #[test]
+    fn test_validate_notes_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("work")).unwrap();
+        fs::create_dir_all(temp_dir.path().join(".trash")).unwrap();
+        fs::write(temp_dir.path().join("a.md"), "12345").unwrap();
+        fs::write(temp_dir.path().join("work").join("b.txt"), "123").unwrap();
+        fs::write(temp_dir.path().join("image.png"), "png").unwrap();
+        fs::write(temp_dir.path().join(".trash").join("c.md"), "trashed").unwrap();
+
+        let validation = validate_notes_dir(temp_dir.path()).unwrap();
+        assert!(validation.is_dir && validation.can_read && validation.can_write);
+        assert_eq!(validation.estimated_note_count, 2);
+        assert_eq!(validation.estimated_size_bytes, 8);
+        assert!(!validation.is_cloud_synced);
+
+        // The write check leaves nothing behind
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 4);
+
+        let missing = validate_notes_dir(&temp_dir.path().join("missing")).unwrap();
+        assert!(!missing.is_dir && !missing.can_write);
+    }
+
+    #[test]
+    fn test_is_cloud_synced() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dropbox_notes = temp_dir.path().join("Dropbox").join("notes");
+        fs::create_dir_all(&dropbox_notes).unwrap();
+        fs::write(temp_dir.path().join("Dropbox").join(".dropbox"), "").unwrap();
+        assert!(is_cloud_synced(&dropbox_notes));
+
+        let onedrive_notes = temp_dir.path().join("OneDrive - Work").join("notes");
+        fs::create_dir_all(&onedrive_notes).unwrap();
+        assert!(is_cloud_synced(&onedrive_notes));
+
+        let icloud_notes = temp_dir.path().join("icloud");
+        fs::create_dir_all(&icloud_notes).unwrap();
+        fs::write(icloud_notes.join(".note.md.icloud"), "").unwrap();
+        assert!(is_cloud_synced(&icloud_notes));
+    }
+
+    #[test]
+    fn test_get_note_absolute_path_does_not_require_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let id = base64::engine::general_purpose::STANDARD.encode("work/missing.md");
+
+        let path = manager.get_note_absolute_path(&id).unwrap();
+
+        assert_eq!(path, temp_dir.path().join("work/missing.md"));
+        assert!(manager.get_note(&id).is_err());
+        assert!(manager.get_note_absolute_path("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_note_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let encode = |path: &str| base64::engine::general_purpose::STANDARD.encode(path);
+
+        let missing = encode("missing.md");
+        let error = manager.get_note(&missing).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<NoteError>(),
+            Some(NoteError::NotFound(id)) if *id == missing
+        ));
+
+        assert!(matches!(
+            manager.get_note_absolute_path("not base64!"),
+            Err(NoteError::InvalidId(_))
+        ));
+        assert!(matches!(
+            manager.get_note_absolute_path(&encode("../outside.md")),
+            Err(NoteError::PathEscape)
+        ));
+        assert!(matches!(
+            manager.get_note_absolute_path(&encode("/etc/passwd")),
+            Err(NoteError::PathEscape)
+        ));
+    }
+
+    #[test]
+    fn test_org_title() {
+        assert_eq!(org_title("#+title: Weekly Review\n* Tasks\n"), Some("Weekly Review".to_string()));
+        assert_eq!(org_title("#+AUTHOR: Me\n#+TITLE:  Plans \n"), Some("Plans".to_string()));
+        assert_eq!(org_title("* Heading only\n"), None);
+        assert_eq!(org_title("#+TITLE:\n"), None);
+    }
+
+    #[test]
+    fn test_org_tags() {
+        let content = "#+TITLE: Plans\n#+FILETAGS: :work:planning:\n\n\
+                       * Meeting notes    :work:meeting:\n\
+                       ** Follow up :urgent:\n\
+                       Body with :not_a_tag: text\n\
+                       *bold* text :ignored:\n";
+
+        assert_eq!(org_tags(content), tags(&["work", "planning", "meeting", "urgent"]));
+    }
+
+    #[test]
+    fn test_org_files_are_listed_only_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.md"), "# A").unwrap();
+        fs::write(
+            temp_dir.path().join("plans.org"),
+            "#+TITLE: Plans\n#+FILETAGS: :work:\n* Tasks :urgent:\n",
+        )
+        .unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        assert_eq!(manager.list_notes(None).unwrap().len(), 1);
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_org_files(true);
+        let notes = manager.list_notes(Some(vec![SortOption::TitleAsc])).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].title, "Plans");
+        assert_eq!(notes[1].file_type, NoteType::Org);
+        assert_eq!(notes[1].tags, tags(&["work", "urgent"]));
+
+        let note = manager.create_note("Ideas", "* Ideas\n", NoteType::Org, None).unwrap();
+        assert_eq!(note.path, "Ideas.org");
+        assert_eq!(note.title, "Ideas");
+    }
+
+    #[test]
+    fn test_custom_extensions_are_listed_as_plain_text() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.md"), "# A").unwrap();
+        fs::write(temp_dir.path().join("guide.rst"), "Guide\n=====\n").unwrap();
+        fs::write(temp_dir.path().join("page.WIKI"), "== Page ==").unwrap();
+        fs::write(temp_dir.path().join("image.png"), "png").unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf())
+            .with_custom_extensions(vec!["rst".to_string(), "wiki".to_string()]);
+        let notes = manager.list_notes(Some(vec![SortOption::TitleAsc])).unwrap();
+
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, vec!["A", "guide", "page"]);
+        assert_eq!(notes[1].file_type, NoteType::PlainText);
+    }
+
+    #[test]
+    fn test_filename_pattern_with_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+
+        let filename = manager
+            .generate_filename_from_pattern("my-note", "{date}-{title}.{extension}", &NoteType::Markdown)
+            .unwrap();
+
+        assert_eq!(filename, format!("{}-my-note.md", today));
+    }
+
+    #[test]
+    fn test_filename_pattern_with_datetime_without_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let filename = manager
+            .generate_filename_from_pattern("ignored", "{datetime}.{extension}", &NoteType::PlainText)
+            .unwrap();
+
+        let (stamp, extension) = filename.split_once('.').unwrap();
+        assert_eq!(extension, "txt");
+        assert!(chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M").is_ok());
+        assert!(!filename.contains("ignored"));
+    }
+
+    #[test]
+    fn test_replace_markdown_heading() {
+        assert_eq!(
+            replace_markdown_heading("---\ntags: [a]\n---\n# Old\r\nBody # not a title\n", "New").as_deref(),
+            Some("---\ntags: [a]\n---\n# New\r\nBody # not a title\n")
+        );
+        assert_eq!(replace_markdown_heading("# Old", "New").as_deref(), Some("# New"));
+        assert_eq!(replace_markdown_heading("## Section\n# Old\n", "New"), None);
+        assert_eq!(replace_markdown_heading("Intro\n", "New"), None);
+    }
+
+    #[test]
+    fn test_rename_note_updates_heading() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Draft", "# Draft\n\nBody\n", NoteType::Markdown, None).unwrap();
+
+        let renamed = manager.rename_note(&note.id, "Final", true).unwrap();
+
+        assert_eq!(renamed.path, "Final.md");
+        assert_eq!(renamed.title, "Final");
+        assert_eq!(renamed.content, "# Final\n\nBody\n");
+
+        let kept = manager.rename_note(&renamed.id, "Other", false).unwrap();
+        assert_eq!(kept.title, "Final");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_notes_with_errors_reports_unreadable_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let unreadable = temp_dir.path().join("b.md");
+        fs::write(temp_dir.path().join("a.md"), "# A").unwrap();
+        fs::write(&unreadable, "# B").unwrap();
+        fs::set_permissions(&unreadable, fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Permissions are not enforced for every user, e.g. root
+        if fs::File::open(&unreadable).is_ok() {
+            return;
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let (notes, errors) = manager.list_notes_with_errors(None);
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].title, "A");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, unreadable);
+        assert_eq!(manager.list_notes(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_check_note_exists() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Exists", "content", NoteType::Markdown, None).unwrap();
+        
+        assert!(manager.check_note_exists(&note.id));
+        assert!(!manager.check_note_exists("not base64!"));
+        
+        fs::remove_file(temp_dir.path().join("Exists.md")).unwrap();
+        assert!(!manager.check_note_exists(&note.id));
+    }
+    
+    #[test]
+    fn test_get_vault_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        
+        let empty = manager.get_vault_stats().unwrap();
+        assert_eq!(empty.total_notes, 0);
+        assert_eq!(empty.oldest_note, None);
+        assert_eq!(empty.average_note_size_bytes, 0.0);
+        
+        fs::write(temp_dir.path().join("a.md"), "# A\n\n#rust #notes").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "two words #rust").unwrap();
+        fs::write(temp_dir.path().join("c.txt"), "").unwrap();
+        
+        let stats = manager.get_vault_stats().unwrap();
+        let notes = manager.list_notes(None).unwrap();
+        
+        assert_eq!(stats.total_notes, 3);
+        assert_eq!(stats.total_words, 6);
+        assert_eq!(stats.total_chars, 15 + 15);
+        assert_eq!(stats.total_tags, 3);
+        assert_eq!(stats.unique_tags, 2);
+        assert_eq!(stats.notes_by_type.get("Markdown"), Some(&1));
+        assert_eq!(stats.notes_by_type.get("PlainText"), Some(&2));
+        assert_eq!(stats.oldest_note, notes.iter().map(|note| note.created).min());
+        assert_eq!(stats.newest_note, notes.iter().map(|note| note.created).max());
+        assert_eq!(stats.average_note_size_bytes, (17 + 15) as f64 / 3.0);
+    }
+    
+    #[test]
+    fn test_get_vault_stats_counts_full_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        
+        // The summary reads only the short words and extrapolates them over the long one
+        let content = format!("{}{}", "a ".repeat(SUMMARY_READ_LIMIT as usize / 2), "b".repeat(100_000));
+        fs::write(temp_dir.path().join("long.txt"), &content).unwrap();
+        
+        let stats = manager.get_vault_stats().unwrap();
+        assert_eq!(stats.total_words, SUMMARY_READ_LIMIT / 2 + 1);
+        assert_eq!(stats.total_chars, content.len() as u64);
+    }
+    
+    #[test]
+    fn test_find_notes_with_tag_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("None", "no tags", NoteType::PlainText, None).unwrap();
+        manager.create_note("One", "#a", NoteType::PlainText, None).unwrap();
+        manager.create_note("Three", "#a #b #c", NoteType::PlainText, None).unwrap();
+        
+        let titles = |notes: Vec<NoteSummary>| -> Vec<String> {
+            notes.into_iter().map(|note| note.title).collect()
+        };
+        
+        assert_eq!(titles(manager.find_notes_with_tag_count(0, 0).unwrap()), vec!["None"]);
+        assert_eq!(
+            titles(manager.find_notes_with_tag_count_sorted(1, 5, Some(vec![SortOption::TitleDesc])).unwrap()),
+            vec!["Three", "One"]
+        );
+        assert!(manager.find_notes_with_tag_count(4, 2).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_find_notes_without_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("private")).unwrap();
+        fs::write(temp_dir.path().join("private").join("Hidden.txt"), "no tags").unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf())
+            .with_excluded_folders(vec!["private".to_string()]);
+        manager.create_note("None", "no tags", NoteType::PlainText, None).unwrap();
+        manager.create_note("One", "#a", NoteType::PlainText, None).unwrap();
+        manager.create_note("Two", "#a #b", NoteType::PlainText, None).unwrap();
+        
+        let titles = |notes: Vec<NoteSummary>| -> Vec<String> {
+            notes.into_iter().map(|note| note.title).collect()
+        };
+        
+        assert_eq!(titles(manager.find_notes_without_tags().unwrap()), vec!["None"]);
+        assert_eq!(titles(manager.find_notes_with_exactly_n_tags(2).unwrap()), vec!["Two"]);
+        assert!(manager.find_notes_with_exactly_n_tags(3).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_link_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("A", "First", NoteType::PlainText, None).unwrap();
+        let b = manager
+            .create_note("B", "Second\nSee [[a|the first]]\n", NoteType::PlainText, None)
+            .unwrap();
+        
+        manager.link_notes(&a.id, &b.id, Some("next")).unwrap();
+        
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "First\n[[B|next]]\n");
+        // B already links to A
+        assert_eq!(manager.get_note(&b.id).unwrap().content, "Second\nSee [[a|the first]]\n");
+        
+        // Linking again does not duplicate the links
+        manager.link_notes(&b.id, &a.id, None).unwrap();
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "First\n[[B|next]]\n");
+        assert_eq!(manager.get_note(&b.id).unwrap().content, "Second\nSee [[a|the first]]\n");
+        
+        assert!(manager.link_notes(&a.id, &a.id, None).is_err());
+    }
+    
+    #[test]
+    fn test_unlink_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager
+            .create_note("A", "See [[B]] and [[C]]\n[[b|alias]]\n", NoteType::PlainText, None)
+            .unwrap();
+        let b = manager.create_note("B", "Back to [[A]]\n", NoteType::PlainText, None).unwrap();
+        
+        manager.unlink_notes(&a.id, &b.id, false).unwrap();
+        
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "See and [[C]]\n");
+        assert_eq!(manager.get_note(&b.id).unwrap().content, "Back to [[A]]\n");
+        
+        // Unlinking notes that are not linked does nothing
+        manager.unlink_notes(&a.id, &b.id, false).unwrap();
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "See and [[C]]\n");
+    }
+    
+    #[test]
+    fn test_unlink_notes_bidirectional() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("A", "First", NoteType::PlainText, None).unwrap();
+        let b = manager.create_note("B", "Second", NoteType::PlainText, None).unwrap();
+        manager.link_notes(&a.id, &b.id, Some("next")).unwrap();
+        
+        manager.unlink_notes(&a.id, &b.id, true).unwrap();
+        
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "First\n");
+        assert_eq!(manager.get_note(&b.id).unwrap().content, "Second\n");
+    }
+    
+    #[test]
+    fn test_validate_wikilinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager
+            .create_note(
+                "A",
+                "# A\nSee [[b]]\nand [[Missing]]\n[[B#Intro]] [[#Local]] [[work/D|D]] [[work/B]]",
+                NoteType::Markdown,
+                None,
+            )
+            .unwrap();
+        fs::create_dir(temp_dir.path().join("work")).unwrap();
+        fs::write(temp_dir.path().join("work").join("D.txt"), "d").unwrap();
+        let b = manager.create_note("B", "# B\nBack to [[A]]", NoteType::Markdown, None).unwrap();
+        manager.create_note("C", "# C\nNo links", NoteType::Markdown, None).unwrap();
+        
+        let expected = vec![
+            BrokenLink {
+                source_note_id: a.id.clone(),
+                source_note_title: "A".to_string(),
+                broken_target: "Missing".to_string(),
+                line_number: Some(3),
+            },
+            BrokenLink {
+                source_note_id: a.id.clone(),
+                source_note_title: "A".to_string(),
+                broken_target: "work/B".to_string(),
+                line_number: Some(4),
+            },
+        ];
+        
+        assert_eq!(manager.validate_wikilinks(None).unwrap(), expected);
+        assert_eq!(manager.validate_wikilinks(Some(&[a.id.as_str()][..])).unwrap(), expected);
+        assert!(manager.validate_wikilinks(Some(&[b.id.as_str()][..])).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_get_note_metadata_matches_get_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let note = manager.create_note("Meta", "# Meta\nSome words #tag", NoteType::Markdown, None).unwrap();
+        
+        let metadata = manager.get_note_metadata(&note.id).unwrap();
+        
+        assert_eq!(metadata.id, note.id);
+        assert_eq!(metadata.title, note.title);
+        assert_eq!(metadata.tags, note.tags);
+        assert_eq!(metadata.word_count, 4);
+        assert!(manager.get_note_metadata("missing").is_err());
+    }
+    
+    #[test]
+    fn test_summary_of_large_note_estimates_statistics() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let words = 30_000;
+        let note = manager
+            .create_note("Large", &"word ".repeat(words), NoteType::PlainText, None)
+            .unwrap();
+        
+        let metadata = manager.get_note_metadata(&note.id).unwrap();
+        
+        assert_eq!(metadata.size_bytes, words as u64 * 5);
+        assert!((metadata.word_count as f64 - words as f64).abs() / (words as f64) < 0.01);
+        assert!((metadata.char_count as f64 - (words * 5) as f64).abs() / ((words * 5) as f64) < 0.01);
+    }
+    
+    #[test]
+    fn test_create_note_suggests_free_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("Idea", "first", NoteType::PlainText, None).unwrap();
+        fs::write(temp_dir.path().join("Idea_2.txt"), "second").unwrap();
+        
+        let err = manager.create_note("Idea", "third", NoteType::PlainText, None).unwrap_err();
+        
+        assert_eq!(err.downcast_ref::<NoteExistsError>().unwrap().suggested_name, "Idea_3");
+        assert_eq!(manager.suggest_note_filename("Idea", &NoteType::PlainText), "Idea_3");
+        assert_eq!(manager.suggest_note_filename("Idea", &NoteType::Markdown), "Idea");
+        assert_eq!(manager.find_available_filename("Idea", "txt"), "Idea_3.txt");
+    }
+    
+    #[test]
+    fn test_create_note_suggests_name_for_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let pattern = "{date}-{title}.{extension}";
+        manager.create_note("Idea", "first", NoteType::PlainText, Some(pattern)).unwrap();
+        
+        let err = manager.create_note("Idea", "second", NoteType::PlainText, Some(pattern)).unwrap_err();
+        let suggested_name = err.downcast_ref::<NoteExistsError>().unwrap().suggested_name.clone();
+        assert_eq!(suggested_name, "Idea_2");
+        
+        let note = manager.create_note(&suggested_name, "second", NoteType::PlainText, Some(pattern)).unwrap();
+        assert!(note.path.ends_with("-Idea_2.txt"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_list_notes_terminates_with_symlink_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join("sub").join("a.md"), "# A").unwrap();
+        std::os::unix::fs::symlink(temp_dir.path(), temp_dir.path().join("sub").join("loop")).unwrap();
+        
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let notes = manager.list_notes(None).unwrap();
+        assert_eq!(notes.len(), 1);
+        
+        let manager = NoteManager::new(temp_dir.path().to_path_buf()).with_walk_options(false, 1);
+        assert!(manager.list_notes(None).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_find_notes_by_content_contains() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("A", "first line\nThe Quick fox", NoteType::PlainText, None).unwrap();
+        manager.create_note("B", "the quick fox", NoteType::PlainText, None).unwrap();
+        manager.create_note("C", "slow", NoteType::PlainText, None).unwrap();
+        
+        let titles = |notes: Vec<NoteSummary>| -> Vec<String> {
+            let mut titles: Vec<String> = notes.into_iter().map(|note| note.title).collect();
+            titles.sort();
+            titles
+        };
+        
+        let insensitive = manager.find_notes_by_content_contains("quick", false, 10).unwrap();
+        assert_eq!(titles(insensitive), vec!["A", "B"]);
+        let sensitive = manager.find_notes_by_content_contains("Quick", true, 10).unwrap();
+        assert_eq!(titles(sensitive), vec!["A"]);
+        assert_eq!(manager.find_notes_by_content_contains("quick", false, 1).unwrap().len(), 1);
+        assert!(manager.find_notes_by_content_contains("line\nThe", false, 10).unwrap().is_empty());
+        assert!(manager.find_notes_by_content_contains("", false, 10).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_batch_get_notes_preserves_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(temp_dir.path().join(format!("{}.txt", name)), name).unwrap();
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let mut summaries = manager.list_notes(Some(vec![SortOption::TitleDesc])).unwrap();
+        summaries.swap(0, 1);
+        let mut ids: Vec<&str> = summaries.iter().map(|summary| summary.id.as_str()).collect();
+        ids.insert(1, "bWlzc2luZy50eHQ=");
+
+        let notes = manager.batch_get_notes(&ids).unwrap();
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+
+        // The missing note is skipped and the requested order is kept
+        assert_eq!(titles, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_list_notes_paginated() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["a", "b", "c", "d", "e"] {
+            fs::write(temp_dir.path().join(format!("{}.txt", name)), name).unwrap();
+        }
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+
+        let (notes, page_info) = manager.list_notes_paginated(2, 2, Some(vec![SortOption::TitleAsc])).unwrap();
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, vec!["c", "d"]);
+        assert_eq!(page_info, PageInfo { page: 2, page_size: 2, total_count: 5, total_pages: 3 });
+
+        let (notes, _) = manager.list_notes_paginated(3, 2, Some(vec![SortOption::TitleAsc])).unwrap();
+        assert_eq!(notes.len(), 1);
+
+        let (notes, _) = manager.list_notes_paginated(4, 2, Some(vec![SortOption::TitleAsc])).unwrap();
+        assert!(notes.is_empty());
+
+        assert!(manager.list_notes_paginated(0, 2, None).is_err());
+        assert!(manager.list_notes_paginated(1, 0, None).is_err());
+    }
+
+    #[test]
+    fn test_is_listed_note_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let manager = NoteManager::new(root.to_path_buf())
+            .with_excluded_folders(vec!["private".to_string()]);
+
+        assert!(manager.is_listed_note_file(&root.join("work").join("a.md")));
+        assert!(manager.is_listed_note_file(&root.join("a.txt")));
+        assert!(!manager.is_listed_note_file(&root.join("tools").join("run.sh")));
+        assert!(!manager.is_listed_note_file(&root.join("setup.exe")));
+        assert!(!manager.is_listed_note_file(&root.join("private").join("a.md")));
+        assert!(!manager.is_listed_note_file(&root.join(".trash").join("a.md")));
+        assert!(!manager.is_listed_note_file(Path::new("/elsewhere/a.md")));
     }
 }
@@ -0,0 +1,286 @@
+use crate::notes::{Note, NoteManager, NoteType};
+use anyhow::{Context, Result};
+use std::fs;
+
+/// Separator placed between merged notes when none is given
+const DEFAULT_MERGE_SEPARATOR: &str = "\n\n---\n\n";
+
+impl NoteManager {
+    /// Splits a note into two notes at a line
+    ///
+    /// Lines before `split_at_line` stay in the original note, the remaining lines
+    /// become a new note in the same directory. For Markdown notes, a heading at
+    /// `split_at_line` becomes the title of the new note instead of `new_title`.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to split
+    /// * `split_at_line` - Zero-based index of the first line of the new note
+    /// * `new_title` - Title of the new note
+    ///
+    /// # Returns
+    /// The shortened original note and the new note
+    pub fn split_note(&self, id: &str, split_at_line: usize, new_title: &str) -> Result<(Note, Note)> {
+        let note = self.get_note(id)?;
+        let path = self.get_note_path(id)?;
+
+        let lines: Vec<&str> = note.content.lines().collect();
+        if split_at_line == 0 || split_at_line >= lines.len() {
+            anyhow::bail!(
+                "Split line {} is out of range for a note with {} lines",
+                split_at_line,
+                lines.len()
+            );
+        }
+
+        let (kept_lines, split_lines) = lines.split_at(split_at_line);
+        let split_content = split_lines.join("\n");
+
+        let (title, new_content) = match note.file_type {
+            NoteType::Markdown if split_lines[0].trim_start().starts_with('#') => {
+                let heading = split_lines[0].trim().trim_start_matches('#').trim().to_string();
+                (heading, format!("{}\n", split_content))
+            }
+            NoteType::Markdown => (
+                new_title.to_string(),
+                format!("# {}\n\n{}\n", new_title, split_content),
+            ),
+            NoteType::PlainText | NoteType::Org => (new_title.to_string(), format!("{}\n", split_content)),
+        };
+
+        if title.is_empty() || title.contains(['/', '\\']) {
+            anyhow::bail!("Invalid title for split note: {}", title);
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("txt");
+        let new_path = path.with_file_name(format!("{}.{}", title, extension));
+
+        if new_path.exists() {
+            anyhow::bail!("A note with this name already exists");
+        }
+
+        // Create the new note first so the original is untouched if this fails
+        fs::write(&new_path, new_content).context("Failed to write split note")?;
+        self.cache_note(None, &new_path);
+        let new_note = self.read_note(&new_path)?;
+
+        let updated_note = self.update_note_content(id, &format!("{}\n", kept_lines.join("\n")))?;
+
+        Ok((updated_note, new_note))
+    }
+
+    /// Merges one note into another
+    ///
+    /// The source note's content is appended to the target note and the source
+    /// note is moved to the trash. Links to the source note are updated to point
+    /// to the target note.
+    ///
+    /// # Parameters
+    /// * `target_id` - ID of the note to merge into
+    /// * `source_id` - ID of the note to append
+    /// * `separator` - Text placed between the two notes (defaults to a horizontal rule)
+    ///
+    /// # Returns
+    /// The merged target note
+    pub fn merge_notes(&self, target_id: &str, source_id: &str, separator: Option<&str>) -> Result<Note> {
+        if target_id == source_id {
+            anyhow::bail!("Cannot merge a note with itself");
+        }
+
+        let target = self.get_note(target_id)?;
+        let source = self.get_note(source_id)?;
+
+        let merged_content = format!(
+            "{}{}{}",
+            target.content.trim_end(),
+            separator.unwrap_or(DEFAULT_MERGE_SEPARATOR),
+            source.content
+        );
+
+        let merged_note = self.update_note_content(target_id, &merged_content)?;
+
+        self.trash_note(source_id)?;
+
+        if let Err(e) = self.update_backlinks(&source.title, &merged_note.title) {
+            // The merge itself succeeded, so backlink failures are not fatal
+            log::warn!("Failed to update links to {}: {}", source.title, e);
+        }
+
+        // Links in the merged content may now point to the merged note itself
+        self.get_note(target_id)
+    }
+
+    /// Converts a note between Markdown and plain text
+    ///
+    /// Markdown content is kept as-is when converting to plain text. Converting
+    /// plain text to Markdown prepends the title as a heading. Converting a note
+    /// that already has the target type returns it unchanged.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to convert
+    /// * `new_type` - Type to convert the note to
+    ///
+    /// # Returns
+    /// The converted note with its new ID
+    pub fn convert_note_type(&self, id: &str, new_type: NoteType) -> Result<Note> {
+        let note = self.get_note(id)?;
+        if note.file_type == new_type {
+            return Ok(note);
+        }
+
+        let path = self.get_note_path(id)?;
+        let new_path = path.with_extension(self.get_extension_for_type(&new_type));
+
+        if new_path.exists() {
+            anyhow::bail!("A note with this name already exists");
+        }
+
+        let new_content = match new_type {
+            NoteType::Markdown => format!("# {}\n\n{}", note.title, note.content),
+            NoteType::PlainText => note.content,
+            NoteType::Org => format!("#+TITLE: {}\n\n{}", note.title, note.content),
+        };
+
+        fs::write(&new_path, new_content).context("Failed to write converted note")?;
+        fs::remove_file(&path).context("Failed to remove original note")?;
+        self.cache_note(Some(id), &new_path);
+
+        self.read_note(&new_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manager_with_note(file_name: &str, content: &str) -> (TempDir, NoteManager, String) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(file_name), content).unwrap();
+
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let id = manager.list_notes(None).unwrap()[0].id.clone();
+
+        (temp_dir, manager, id)
+    }
+
+    #[test]
+    fn test_split_markdown_note_at_heading() {
+        let (temp_dir, manager, id) = manager_with_note(
+            "Project.md",
+            "# Project\nIntro with [[Plan]]\n## Tasks\n- write [[Spec]]\n- review\n",
+        );
+
+        let (original, new_note) = manager.split_note(&id, 2, "ignored").unwrap();
+
+        assert_eq!(original.content, "# Project\nIntro with [[Plan]]\n");
+        assert_eq!(new_note.title, "Tasks");
+        assert_eq!(new_note.content, "## Tasks\n- write [[Spec]]\n- review\n");
+        assert!(temp_dir.path().join("Tasks.md").exists());
+        assert_eq!(manager.extract_wikilinks(&original.id).unwrap(), vec!["Plan"]);
+        assert_eq!(manager.extract_wikilinks(&new_note.id).unwrap(), vec!["Spec"]);
+    }
+
+    #[test]
+    fn test_split_note_uses_new_title_without_heading() {
+        let (_temp_dir, manager, id) = manager_with_note("Log.md", "# Log\nmonday\ntuesday\n");
+
+        let (original, new_note) = manager.split_note(&id, 2, "Later").unwrap();
+
+        assert_eq!(original.content, "# Log\nmonday\n");
+        assert_eq!(new_note.title, "Later");
+        assert_eq!(new_note.content, "# Later\n\ntuesday\n");
+
+        let (_temp_dir, manager, id) = manager_with_note("Plain.txt", "one\ntwo\n");
+        let (original, new_note) = manager.split_note(&id, 1, "Second").unwrap();
+
+        assert_eq!(original.content, "one\n");
+        assert_eq!(new_note.title, "Second");
+        assert_eq!(new_note.content, "two\n");
+    }
+
+    #[test]
+    fn test_split_note_rejects_invalid_splits() {
+        let (temp_dir, manager, id) = manager_with_note("Note.txt", "one\ntwo\n");
+        fs::write(temp_dir.path().join("Taken.txt"), "taken").unwrap();
+
+        assert!(manager.split_note(&id, 0, "New").is_err());
+        assert!(manager.split_note(&id, 2, "New").is_err());
+        assert!(manager.split_note(&id, 1, "Taken").is_err());
+        assert!(manager.split_note(&id, 1, "a/b").is_err());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("Note.txt")).unwrap(), "one\ntwo\n");
+    }
+
+    #[test]
+    fn test_merge_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let target = manager.create_note("Target", "# Target\nfirst\n\n", NoteType::Markdown, None).unwrap();
+        let source = manager.create_note("Source", "# Source\nsecond\n", NoteType::Markdown, None).unwrap();
+        let index = manager
+            .create_note("Index", "See [[Source]] and [[Other]]", NoteType::PlainText, None)
+            .unwrap();
+
+        let merged = manager.merge_notes(&target.id, &source.id, None).unwrap();
+
+        assert_eq!(merged.id, target.id);
+        assert_eq!(merged.content, "# Target\nfirst\n\n---\n\n# Source\nsecond\n");
+        assert!(!temp_dir.path().join("Source.md").exists());
+        assert_eq!(manager.list_trashed_notes().unwrap().len(), 1);
+        assert_eq!(
+            manager.get_note(&index.id).unwrap().content,
+            "See [[Target]] and [[Other]]"
+        );
+    }
+
+    #[test]
+    fn test_merge_notes_with_separator() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager.create_note("A", "one", NoteType::PlainText, None).unwrap();
+        let b = manager.create_note("B", "two", NoteType::PlainText, None).unwrap();
+
+        assert!(manager.merge_notes(&a.id, &a.id, None).is_err());
+
+        let merged = manager.merge_notes(&a.id, &b.id, Some("\n")).unwrap();
+
+        assert_eq!(merged.content, "one\ntwo");
+        assert!(!temp_dir.path().join("B.txt").exists());
+    }
+
+    #[test]
+    fn test_convert_markdown_to_plain_text() {
+        let (temp_dir, manager, id) = manager_with_note("note.md", "# Note\n\nSome *text*\n");
+
+        let converted = manager.convert_note_type(&id, NoteType::PlainText).unwrap();
+
+        assert_eq!(converted.file_type, NoteType::PlainText);
+        assert_eq!(converted.content, "# Note\n\nSome *text*\n");
+        assert!(converted.path.ends_with("note.txt"));
+        assert!(!temp_dir.path().join("note.md").exists());
+    }
+
+    #[test]
+    fn test_convert_plain_text_to_markdown() {
+        let (temp_dir, manager, id) = manager_with_note("Shopping.txt", "milk\neggs\n");
+
+        let converted = manager.convert_note_type(&id, NoteType::Markdown).unwrap();
+
+        assert_eq!(converted.file_type, NoteType::Markdown);
+        assert_eq!(converted.title, "Shopping");
+        assert_eq!(converted.content, "# Shopping\n\nmilk\neggs\n");
+        assert!(!temp_dir.path().join("Shopping.txt").exists());
+    }
+
+    #[test]
+    fn test_convert_to_same_type_is_noop() {
+        let (_temp_dir, manager, id) = manager_with_note("note.md", "# Note\n");
+
+        let converted = manager.convert_note_type(&id, NoteType::Markdown).unwrap();
+
+        assert_eq!(converted.id, id);
+        assert_eq!(converted.content, "# Note\n");
+    }
+}
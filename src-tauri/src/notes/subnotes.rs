@@ -1,7 +1,8 @@
-use crate::notes::{NoteManager, NoteSummary};
+use crate::notes::{Note, NoteManager, NoteSummary, NoteType};
 use anyhow::Result;
 use serde::Serialize;
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 /// Information about a subnote
 #[derive(Debug, Clone, Serialize)]
@@ -12,6 +13,21 @@ pub struct SubnoteInfo {
     pub depth: u32,
 }
 
+/// A note in the Zettelkasten tree with its direct subnotes
+#[derive(Debug, Clone, Serialize)]
+pub struct ZettelNode {
+    /// ID of the note
+    pub id: String,
+    /// Title of the note
+    pub title: String,
+    /// Zettelkasten identifier of the note (e.g., "1a2")
+    pub prefix: String,
+    /// Depth in the tree, 0 for root notes
+    pub depth: u32,
+    /// Direct subnotes in Zettelkasten order
+    pub children: Vec<ZettelNode>,
+}
+
 impl NoteManager {
     /// Gets all subnotes for a parent note
     ///
@@ -47,6 +63,236 @@ impl NoteManager {
 
         Ok(subnotes)
     }
+
+    /// Gets the subnotes of a note level by level, up to a maximum depth
+    ///
+    /// Each subnote is directly followed by its own subnotes, and the notes on
+    /// every level are in Zettelkasten order (1a, 1a1, 1a1a, 1a2, 1b, ...).
+    ///
+    /// # Parameters
+    /// * `parent_id` - ID of the parent note
+    /// * `max_depth` - Deepest level to include, 1 for direct children only (unlimited if None)
+    ///
+    /// # Returns
+    /// List of subnotes with their depth below the parent
+    pub fn list_all_subnotes_flat(&self, parent_id: &str, max_depth: Option<u32>) -> Result<Vec<SubnoteInfo>> {
+        let parent_note = self.get_note(parent_id)?;
+
+        let Some(prefix) = extract_prefix(&parent_note.title).filter(|prefix| !prefix.is_empty()) else {
+            return Ok(Vec::new());
+        };
+
+        let notes = self.list_notes(None)?;
+        let children = children_by_parent_prefix(&notes);
+
+        let mut subnotes = Vec::new();
+        let mut visited = HashSet::from([parent_note.id]);
+        collect_subnotes(prefix, 1, max_depth.unwrap_or(u32::MAX), &children, &mut visited, &mut subnotes);
+
+        Ok(subnotes)
+    }
+
+    /// Creates a subnote with the next free Zettelkasten identifier
+    ///
+    /// A parent ending in a digit gets letter suffixes (1 -> 1a, 1b, ...), a parent
+    /// ending in a letter gets number suffixes (1a -> 1a1, 1a2, ...).
+    ///
+    /// # Parameters
+    /// * `parent_id` - ID of the parent note
+    /// * `title` - Title of the subnote (without identifier)
+    /// * `content` - Content of the subnote
+    ///
+    /// # Returns
+    /// The created subnote
+    pub fn create_subnote(&self, parent_id: &str, title: &str, content: &str) -> Result<Note> {
+        let parent_note = self.get_note(parent_id)?;
+
+        let Some(parent_prefix) = extract_prefix(&parent_note.title).filter(|prefix| !prefix.is_empty()) else {
+            anyhow::bail!("Parent note has no Zettelkasten identifier");
+        };
+
+        let existing_prefixes: Vec<String> = self
+            .get_subnotes(parent_id)?
+            .iter()
+            .filter_map(|subnote| extract_prefix(&subnote.note.title))
+            .map(String::from)
+            .collect();
+
+        let next_suffix = next_subnote_suffix(parent_prefix, &existing_prefixes);
+        let full_title = format!("{}{}-{}", parent_prefix, next_suffix, title);
+
+        self.create_note(&full_title, content, NoteType::Markdown, None)
+    }
+
+    /// Gets the parent of a note in the Zettelkasten hierarchy
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    ///
+    /// # Returns
+    /// The parent note, or None for root notes and notes without a parent on disk
+    pub fn get_parent_note(&self, id: &str) -> Result<Option<NoteSummary>> {
+        let note = self.get_note(id)?;
+
+        let Some(parent) = extract_prefix(&note.title).and_then(parent_prefix) else {
+            return Ok(None);
+        };
+
+        let notes = self.list_notes(None)?;
+
+        Ok(notes
+            .into_iter()
+            .find(|candidate| extract_prefix(&candidate.title) == Some(parent)))
+    }
+
+    /// Gets the root notes of the Zettelkasten hierarchy
+    ///
+    /// # Returns
+    /// Notes whose identifier is purely numeric (e.g., "1", "42"), in Zettelkasten order
+    pub fn get_root_notes(&self) -> Result<Vec<NoteSummary>> {
+        let mut roots: Vec<NoteSummary> = self
+            .list_notes(None)?
+            .into_iter()
+            .filter(|note| extract_prefix(&note.title).map_or(false, is_root_prefix))
+            .collect();
+
+        roots.sort_by(|a, b| {
+            let a_prefix = extract_prefix(&a.title).unwrap_or("");
+            let b_prefix = extract_prefix(&b.title).unwrap_or("");
+            zettelkasten_compare(a_prefix, b_prefix)
+        });
+
+        Ok(roots)
+    }
+
+    /// Builds the Zettelkasten tree of all notes
+    ///
+    /// Root notes have a purely numeric identifier; every other note is placed
+    /// under the note whose identifier is its parent prefix (1 -> 1a -> 1a1).
+    /// Notes whose parent is missing are not part of the tree.
+    ///
+    /// # Returns
+    /// The trees of all root notes, in Zettelkasten order
+    pub fn get_zettelkasten_index(&self) -> Result<Vec<ZettelNode>> {
+        let notes = self.list_notes(None)?;
+
+        let roots = notes
+            .iter()
+            .filter(|note| extract_prefix(&note.title).map_or(false, is_root_prefix))
+            .collect();
+        let children = children_by_parent_prefix(&notes);
+
+        let mut visited = HashSet::new();
+
+        Ok(zettel_nodes(roots, 0, &children, &mut visited))
+    }
+}
+
+/// Groups notes by the Zettelkasten prefix of their parent
+///
+/// # Parameters
+/// * `notes` - All notes
+///
+/// # Returns
+/// The direct subnotes of every prefix that has any
+fn children_by_parent_prefix(notes: &[NoteSummary]) -> HashMap<&str, Vec<&NoteSummary>> {
+    let mut children: HashMap<&str, Vec<&NoteSummary>> = HashMap::new();
+
+    for note in notes {
+        if let Some(parent) = extract_prefix(&note.title).and_then(parent_prefix) {
+            children.entry(parent).or_default().push(note);
+        }
+    }
+
+    children
+}
+
+/// Appends the subnotes of a prefix and, recursively, their subnotes in Zettelkasten order
+///
+/// # Parameters
+/// * `prefix` - Prefix of the note whose subnotes to collect
+/// * `depth` - Depth of the subnotes below the original parent
+/// * `max_depth` - Deepest level to collect
+/// * `children` - Notes grouped by the prefix of their parent
+/// * `visited` - IDs of notes already collected; a note is never added twice
+/// * `subnotes` - Collected subnotes
+fn collect_subnotes(
+    prefix: &str,
+    depth: u32,
+    max_depth: u32,
+    children: &HashMap<&str, Vec<&NoteSummary>>,
+    visited: &mut HashSet<String>,
+    subnotes: &mut Vec<SubnoteInfo>,
+) {
+    if depth > max_depth {
+        return;
+    }
+
+    let mut level = children.get(prefix).cloned().unwrap_or_default();
+    level.sort_by(|a, b| {
+        let a_prefix = extract_prefix(&a.title).unwrap_or("");
+        let b_prefix = extract_prefix(&b.title).unwrap_or("");
+        zettelkasten_compare(a_prefix, b_prefix)
+    });
+
+    for note in level {
+        if !visited.insert(note.id.clone()) {
+            continue;
+        }
+
+        subnotes.push(SubnoteInfo {
+            note: note.clone(),
+            depth,
+        });
+
+        let note_prefix = extract_prefix(&note.title).unwrap_or("");
+        collect_subnotes(note_prefix, depth + 1, max_depth, children, visited, subnotes);
+    }
+}
+
+/// Builds tree nodes for notes and, recursively, their subnotes
+///
+/// # Parameters
+/// * `notes` - Notes on the current level
+/// * `depth` - Depth of the current level
+/// * `children` - Notes grouped by the prefix of their parent
+/// * `visited` - IDs of notes already in the tree; a note is never added twice,
+///   so duplicate identifiers cannot make the tree recurse forever
+///
+/// # Returns
+/// The nodes in Zettelkasten order
+fn zettel_nodes(
+    mut notes: Vec<&NoteSummary>,
+    depth: u32,
+    children: &HashMap<&str, Vec<&NoteSummary>>,
+    visited: &mut HashSet<String>,
+) -> Vec<ZettelNode> {
+    notes.sort_by(|a, b| {
+        let a_prefix = extract_prefix(&a.title).unwrap_or("");
+        let b_prefix = extract_prefix(&b.title).unwrap_or("");
+        zettelkasten_compare(a_prefix, b_prefix)
+    });
+
+    let mut nodes = Vec::new();
+
+    for note in notes {
+        if !visited.insert(note.id.clone()) {
+            continue;
+        }
+
+        let prefix = extract_prefix(&note.title).unwrap_or("");
+        let subnotes = children.get(prefix).cloned().unwrap_or_default();
+
+        nodes.push(ZettelNode {
+            id: note.id.clone(),
+            title: note.title.clone(),
+            prefix: prefix.to_string(),
+            depth,
+            children: zettel_nodes(subnotes, depth + 1, children, visited),
+        });
+    }
+
+    nodes
 }
 
 /// Extracts the numeric prefix from a note title (e.g., "1" from "1-some-title")
@@ -107,6 +353,98 @@ fn is_subnote(title: &str, parent_prefix: Option<&str>) -> Option<u32> {
     }
 }
 
+/// Gets the prefix of the parent by removing the last letter or number segment
+///
+/// # Parameters
+/// * `prefix` - Prefix of the note (e.g., "1a2")
+///
+/// # Returns
+/// The parent prefix (e.g., "1a"), or None for root prefixes
+fn parent_prefix(prefix: &str) -> Option<&str> {
+    let last = prefix.chars().last()?;
+
+    let parent = if last.is_ascii_digit() {
+        prefix.trim_end_matches(|c: char| c.is_ascii_digit())
+    } else if last.is_alphabetic() {
+        prefix.trim_end_matches(|c: char| c.is_alphabetic())
+    } else {
+        return None;
+    };
+
+    if parent.is_empty() {
+        None
+    } else {
+        Some(parent)
+    }
+}
+
+/// Checks if a prefix identifies a root note (purely numeric, e.g., "1" or "42")
+fn is_root_prefix(prefix: &str) -> bool {
+    !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Determines the next free suffix for a direct child of a parent prefix
+///
+/// # Parameters
+/// * `parent_prefix` - Prefix of the parent note (e.g., "1" or "1a")
+/// * `existing_prefixes` - Prefixes of existing subnotes at any depth
+///
+/// # Returns
+/// The next suffix (e.g., "c" after "1a" and "1b", "aa" after "z", "2" after "1a1")
+fn next_subnote_suffix(parent_prefix: &str, existing_prefixes: &[String]) -> String {
+    let use_letters = parent_prefix.chars().last().map_or(false, |c| c.is_numeric());
+
+    // Only direct children have a suffix made of a single letter or number segment
+    let highest = existing_prefixes
+        .iter()
+        .filter_map(|prefix| prefix.strip_prefix(parent_prefix))
+        .filter_map(|suffix| {
+            if use_letters {
+                letters_to_index(suffix)
+            } else if !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()) {
+                suffix.parse::<u32>().ok()
+            } else {
+                None
+            }
+        })
+        .max()
+        .unwrap_or(0);
+
+    if use_letters {
+        index_to_letters(highest + 1)
+    } else {
+        (highest + 1).to_string()
+    }
+}
+
+/// Converts a letter suffix to its position in the sequence a, b, ..., z, aa, ab, ...
+fn letters_to_index(letters: &str) -> Option<u32> {
+    if letters.is_empty() {
+        return None;
+    }
+
+    letters.chars().try_fold(0u32, |index, c| {
+        let c = c.to_ascii_lowercase();
+        if !c.is_ascii_lowercase() {
+            return None;
+        }
+        index.checked_mul(26)?.checked_add(c as u32 - 'a' as u32 + 1)
+    })
+}
+
+/// Converts a position in the sequence a, b, ..., z, aa, ab, ... to its letter suffix
+fn index_to_letters(mut index: u32) -> String {
+    let mut letters = Vec::new();
+
+    while index > 0 {
+        index -= 1;
+        letters.push((b'a' + (index % 26) as u8) as char);
+        index /= 26;
+    }
+
+    letters.iter().rev().collect()
+}
+
 /// Compares two Zettelkasten prefixes for proper sorting
 /// Ensures order like: 1a, 1a1, 1a2, 1b, 1c, etc.
 ///
@@ -228,4 +566,95 @@ mod tests {
         prefixes.sort_by(|a, b| zettelkasten_compare(a, b));
         assert_eq!(prefixes, vec!["1a", "1a1", "1a2", "1a10", "1b", "1c"]);
     }
+
+    #[test]
+    fn test_next_subnote_suffix() {
+        let existing = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(next_subnote_suffix("1", &existing(&[])), "a");
+        assert_eq!(next_subnote_suffix("1", &existing(&["1a", "1b", "1a1"])), "c");
+        assert_eq!(next_subnote_suffix("1a", &existing(&[])), "1");
+        assert_eq!(next_subnote_suffix("1a", &existing(&["1a1", "1a1a"])), "2");
+        assert_eq!(next_subnote_suffix("1a", &existing(&["1a9", "1a10"])), "11");
+        assert_eq!(next_subnote_suffix("1", &existing(&["1y", "1z"])), "aa");
+        assert_eq!(next_subnote_suffix("1", &existing(&["1az"])), "ba");
+    }
+
+    #[test]
+    fn test_parent_prefix() {
+        assert_eq!(parent_prefix("1"), None);
+        assert_eq!(parent_prefix("42"), None);
+        assert_eq!(parent_prefix("1a"), Some("1"));
+        assert_eq!(parent_prefix("1a2"), Some("1a"));
+        assert_eq!(parent_prefix("1a12"), Some("1a"));
+        assert_eq!(parent_prefix("10b3c"), Some("10b3"));
+        assert_eq!(parent_prefix("title"), None);
+    }
+
+    #[test]
+    fn test_get_zettelkasten_index() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        for title in ["1-root", "1b-second", "1a-first", "1a1-nested", "2-other", "3a-orphan", "title"] {
+            manager.create_note(title, "", NoteType::PlainText, None).unwrap();
+        }
+
+        let index = manager.get_zettelkasten_index().unwrap();
+
+        let prefixes: Vec<&str> = index.iter().map(|node| node.prefix.as_str()).collect();
+        assert_eq!(prefixes, vec!["1", "2"]);
+
+        let children: Vec<&str> = index[0].children.iter().map(|node| node.prefix.as_str()).collect();
+        assert_eq!(children, vec!["1a", "1b"]);
+
+        let nested = &index[0].children[0].children;
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].title, "1a1-nested");
+        assert_eq!(nested[0].depth, 2);
+        assert!(index[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_list_all_subnotes_flat() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let mut root_id = String::new();
+        for title in ["1-root", "1b-second", "1a-first", "1a2-nested", "1a1-nested", "1a1a-deep", "10a-other"] {
+            let note = manager.create_note(title, "", NoteType::PlainText, None).unwrap();
+            if title == "1-root" {
+                root_id = note.id;
+            }
+        }
+
+        let flat = |max_depth| -> Vec<(String, u32)> {
+            manager
+                .list_all_subnotes_flat(&root_id, max_depth)
+                .unwrap()
+                .into_iter()
+                .map(|subnote| (subnote.note.title, subnote.depth))
+                .collect()
+        };
+
+        assert_eq!(
+            flat(None),
+            vec![
+                ("1a-first".to_string(), 1),
+                ("1a1-nested".to_string(), 2),
+                ("1a1a-deep".to_string(), 3),
+                ("1a2-nested".to_string(), 2),
+                ("1b-second".to_string(), 1),
+            ]
+        );
+        assert_eq!(flat(Some(1)), vec![("1a-first".to_string(), 1), ("1b-second".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_is_root_prefix() {
+        assert!(is_root_prefix("1"));
+        assert!(is_root_prefix("42"));
+        assert!(!is_root_prefix("1a"));
+        assert!(!is_root_prefix("1a1"));
+        assert!(!is_root_prefix("title"));
+        assert!(!is_root_prefix(""));
+    }
 }
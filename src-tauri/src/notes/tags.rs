@@ -0,0 +1,285 @@
+use crate::notes::{split_frontmatter, NoteManager, NoteType};
+use anyhow::Result;
+use serde::Serialize;
+
+/// Summary of a bulk tag update
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchUpdateResult {
+    /// Number of notes whose content changed
+    pub updated: usize,
+    /// IDs of notes that could not be updated
+    pub failed: Vec<String>,
+}
+
+impl NoteManager {
+    /// Adds and removes inline `#tag`s on multiple notes
+    ///
+    /// Missing tags are appended to the first paragraph of each note, after
+    /// the title line of Markdown notes. Removed tags are deleted wherever they
+    /// appear in the body; tags in frontmatter are left untouched. Org notes
+    /// keep their tags in `#+FILETAGS` and are reported as failed.
+    ///
+    /// # Parameters
+    /// * `ids` - IDs of the notes to update
+    /// * `tags_to_add` - Tags to add, with or without a leading `#`
+    /// * `tags_to_remove` - Tags to remove, with or without a leading `#`
+    ///
+    /// # Returns
+    /// The number of changed notes and the IDs of notes that failed
+    pub fn batch_update_tags(
+        &self,
+        ids: &[&str],
+        tags_to_add: &[&str],
+        tags_to_remove: &[&str],
+    ) -> Result<BatchUpdateResult> {
+        let tags_to_add = normalize_tags(tags_to_add)?;
+        let tags_to_remove = normalize_tags(tags_to_remove)?;
+
+        let mut result = BatchUpdateResult::default();
+
+        for id in ids {
+            match self.update_tags(id, &tags_to_add, &tags_to_remove) {
+                Ok(true) => result.updated += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    log::warn!("Failed to update tags of note {}: {}", id, e);
+                    result.failed.push(id.to_string());
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Adds and removes inline tags on a single note
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// * `tags_to_add` - Normalized tags to add
+    /// * `tags_to_remove` - Normalized tags to remove
+    ///
+    /// # Returns
+    /// Whether the note content changed
+    fn update_tags(&self, id: &str, tags_to_add: &[String], tags_to_remove: &[String]) -> Result<bool> {
+        let note = self.get_note(id)?;
+
+        if note.file_type == NoteType::Org {
+            anyhow::bail!("Tags of Org notes cannot be updated");
+        }
+
+        let (_, body) = split_frontmatter(&note.content);
+        let frontmatter = &note.content[..note.content.len() - body.len()];
+
+        let body = remove_inline_tags(body, tags_to_remove);
+
+        // Tags already present in frontmatter or the body are not added again
+        let existing = self.extract_tags(&format!("{}{}", frontmatter, body));
+        let missing: Vec<&str> = tags_to_add
+            .iter()
+            .filter(|tag| !existing.contains(*tag))
+            .map(String::as_str)
+            .collect();
+
+        // The first line of a Markdown note is its title
+        let skip_first_line = note.file_type == NoteType::Markdown;
+        let body = add_inline_tags(&body, &missing, skip_first_line);
+
+        let content = format!("{}{}", frontmatter, body);
+
+        if content == note.content {
+            return Ok(false);
+        }
+
+        self.update_note_content(id, &content)?;
+
+        Ok(true)
+    }
+}
+
+/// Strips leading `#`s from tags and rejects empty tags or tags with whitespace
+fn normalize_tags(tags: &[&str]) -> Result<Vec<String>> {
+    tags.iter()
+        .map(|tag| {
+            let tag = tag.trim().trim_start_matches('#');
+
+            if tag.is_empty() || tag.contains(char::is_whitespace) {
+                anyhow::bail!("Invalid tag: '{}'", tag);
+            }
+
+            Ok(tag.to_string())
+        })
+        .collect()
+}
+
+/// Extracts the inline `#tag`s of note content in order of appearance
+///
+/// # Parameters
+/// * `body` - Note content without frontmatter
+///
+/// # Returns
+/// The tags without `#`, possibly with duplicates
+pub(crate) fn inline_tags(body: &str) -> Vec<String> {
+    body.split_whitespace()
+        .filter(|word| word.starts_with('#') && word.len() > 1)
+        // Remove the # and any trailing punctuation
+        .map(|word| {
+            word.trim_start_matches('#')
+                .trim_end_matches(|c: char| !c.is_alphanumeric())
+                .to_string()
+        })
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Checks whether a word is an inline tag for one of the given tags
+fn is_tag_word(word: &str, tags: &[String]) -> bool {
+    let word = word.trim();
+
+    // Matches the way `extract_tags` reads inline tags
+    word.starts_with('#')
+        && tags.iter().any(|tag| {
+            word.trim_start_matches('#')
+                .trim_end_matches(|c: char| !c.is_alphanumeric())
+                == tag.as_str()
+        })
+}
+
+/// Removes inline tags from note content
+///
+/// Lines that only contained removed tags are dropped.
+///
+/// # Parameters
+/// * `body` - Note content without frontmatter
+/// * `tags` - Tags to remove, without `#`
+///
+/// # Returns
+/// The content without the tags
+pub(crate) fn remove_inline_tags(body: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        return body.to_string();
+    }
+
+    let mut lines = Vec::new();
+
+    for line in body.split('\n') {
+        let had_tags = line.split(' ').any(|word| is_tag_word(word, tags));
+        if !had_tags {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let kept: Vec<&str> = line
+            .split(' ')
+            .filter(|word| !is_tag_word(word, tags))
+            .collect();
+        let kept = kept.join(" ").trim_end().to_string();
+
+        if !kept.trim().is_empty() {
+            lines.push(kept);
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Appends inline tags to the end of the first paragraph of note content
+///
+/// Headings are not part of a paragraph. Content without a paragraph gets the
+/// tags on a new line at the end.
+fn add_inline_tags(body: &str, tags: &[&str], skip_first_line: bool) -> String {
+    if tags.is_empty() {
+        return body.to_string();
+    }
+
+    let tag_text = tags
+        .iter()
+        .map(|tag| format!("#{}", tag))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+    let trailing_newline = body.ends_with('\n');
+
+    let is_heading = |line: &str| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with('#') && trimmed.trim_start_matches('#').starts_with(' ')
+    };
+
+    let first_line = if skip_first_line { 1 } else { 0 };
+    let start = (first_line..lines.len())
+        .find(|&i| !lines[i].trim().is_empty() && !is_heading(&lines[i]));
+
+    match start {
+        Some(start) => {
+            let end = (start..lines.len())
+                .take_while(|&i| !lines[i].trim().is_empty() && !is_heading(&lines[i]))
+                .last()
+                .unwrap_or(start);
+            let line = lines[end].trim_end().to_string();
+            lines[end] = format!("{} {}", line, tag_text);
+        }
+        None => {
+            if lines.last().map_or(false, |line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(tag_text);
+        }
+    }
+
+    let mut content = lines.join("\n");
+    if trailing_newline {
+        content.push('\n');
+    }
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_inline_tags_to_first_paragraph() {
+        let body = "# Title\n\nFirst line\nsecond line\n\nOther paragraph\n";
+
+        assert_eq!(
+            add_inline_tags(body, &["a", "b"], true),
+            "# Title\n\nFirst line\nsecond line #a #b\n\nOther paragraph\n"
+        );
+        assert_eq!(add_inline_tags("# Title", &["a"], true), "# Title\n\n#a");
+    }
+
+    #[test]
+    fn test_remove_inline_tags() {
+        let tags = vec!["old".to_string()];
+
+        assert_eq!(
+            remove_inline_tags("Text #old, #older\n#old\nmore", &tags),
+            "Text #older\nmore"
+        );
+    }
+
+    #[test]
+    fn test_batch_update_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        let a = manager
+            .create_note("A", "# A\n\nBody #old\n", NoteType::Markdown, None)
+            .unwrap();
+        let b = manager
+            .create_note("B", "Plain #keep #new", NoteType::PlainText, None)
+            .unwrap();
+
+        let result = manager
+            .batch_update_tags(&[&a.id, &b.id, "missing"], &["#new"], &["old"])
+            .unwrap();
+
+        assert_eq!(result.updated, 1);
+        assert_eq!(result.failed, vec!["missing"]);
+        assert_eq!(manager.get_note(&a.id).unwrap().content, "# A\n\nBody #new\n");
+        assert_eq!(manager.get_note(&b.id).unwrap().tags, vec!["keep", "new"]);
+
+        assert!(manager.batch_update_tags(&[&a.id], &["two words"], &[]).is_err());
+    }
+}
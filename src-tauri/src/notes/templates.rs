@@ -0,0 +1,151 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Templates that are always available, even without user-defined templates
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "daily",
+        "# {{title}}\n\n## Tasks\n- [ ] \n\n## Notes\n\n",
+    ),
+    (
+        "meeting",
+        "# {{title}}\n\nDate: {{datetime}}\n\n## Attendees\n- \n\n## Agenda\n- \n\n## Action Items\n- [ ] \n",
+    ),
+    (
+        "project",
+        "# {{title}}\n\nCreated: {{date}}\n\n## Goals\n- \n\n## Tasks\n- [ ] \n\n## Resources\n- \n",
+    ),
+];
+
+/// A note template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteTemplate {
+    /// Name of the template
+    pub name: String,
+    /// Template content with `{{placeholder}}` variables
+    pub content: String,
+    /// Whether this is a built-in template
+    pub builtin: bool,
+}
+
+/// Gets the content of a built-in template
+///
+/// # Parameters
+/// * `name` - Name of the template
+///
+/// # Returns
+/// The template content if a built-in template with this name exists
+pub fn builtin_template(name: &str) -> Option<&'static str> {
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin_name, _)| *builtin_name == name)
+        .map(|(_, content)| *content)
+}
+
+/// Gets all built-in templates
+pub fn builtin_templates() -> Vec<NoteTemplate> {
+    BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, content)| NoteTemplate {
+            name: name.to_string(),
+            content: content.to_string(),
+            builtin: true,
+        })
+        .collect()
+}
+
+/// Renders a template by substituting its placeholders
+///
+/// User-provided variables take precedence over the built-in `{{title}}`,
+/// `{{date}}` and `{{datetime}}` placeholders. Placeholders are substituted in a
+/// single pass, so substituted values are never expanded again, and unknown
+/// placeholders are kept as they are.
+///
+/// # Parameters
+/// * `content` - Template content
+/// * `title` - Title of the note being created
+/// * `variables` - Additional placeholder values keyed by placeholder name
+///
+/// # Returns
+/// The rendered note content
+pub fn render_template(content: &str, title: &str, variables: &HashMap<String, String>) -> String {
+    let now = Local::now();
+    let value = |name: &str| -> Option<String> {
+        if let Some(value) = variables.get(name) {
+            return Some(value.clone());
+        }
+        match name {
+            "title" => Some(title.to_string()),
+            "date" => Some(now.format("%Y-%m-%d").to_string()),
+            "datetime" => Some(now.format("%Y-%m-%d %H:%M").to_string()),
+            _ => None,
+        }
+    };
+
+    let mut rendered = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+
+        let Some(end) = after_open.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        match value(&after_open[..end]) {
+            Some(value) => {
+                rendered.push_str(&value);
+                rest = &after_open[end + 2..];
+            }
+            None => {
+                // Keep the first brace, a placeholder may start at the next one
+                rendered.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_substitutes_placeholders() {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+
+        let rendered = render_template("# {{title}}\nCreated: {{date}}", "Plan", &HashMap::new());
+
+        assert_eq!(rendered, format!("# Plan\nCreated: {}", today));
+    }
+
+    #[test]
+    fn test_render_template_does_not_expand_substituted_values() {
+        let variables = HashMap::from([("project".to_string(), "{{title}}".to_string())]);
+
+        let rendered = render_template("{{title}} / {{project}}", "Notes on {{date}}", &variables);
+
+        assert_eq!(rendered, "Notes on {{date}} / {{title}}");
+    }
+
+    #[test]
+    fn test_render_template_variables_override_builtin_placeholders() {
+        let variables = HashMap::from([("date".to_string(), "someday".to_string())]);
+
+        assert_eq!(render_template("{{date}}", "Plan", &variables), "someday");
+    }
+
+    #[test]
+    fn test_render_template_keeps_unknown_and_unclosed_placeholders() {
+        assert_eq!(
+            render_template("{{unknown}} {{{title}} {{title", "Plan", &HashMap::new()),
+            "{{unknown}} {Plan {{title"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::notes::{Note, NoteManager};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the directory (inside the notes directory) holding trashed notes
+pub(crate) const TRASH_DIR: &str = ".trash";
+
+/// Extension of the sidecar file stored next to each trashed note
+const SIDECAR_EXTENSION: &str = "json";
+
+/// Metadata stored in the sidecar file of a trashed note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrashMetadata {
+    /// Original path of the note relative to the notes directory
+    original_path: String,
+    /// When the note was moved to the trash
+    deleted_at: DateTime<Utc>,
+}
+
+/// Information about a note in the trash
+#[derive(Debug, Clone, Serialize)]
+pub struct TrashedNote {
+    /// Identifier of the trashed note (its file name inside the trash)
+    pub trash_id: String,
+    /// Title of the note derived from its original file name
+    pub title: String,
+    /// Original path of the note relative to the notes directory
+    pub original_path: String,
+    /// When the note was moved to the trash
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl NoteManager {
+    /// Moves a note to the trash instead of deleting it permanently
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note to trash
+    ///
+    /// # Returns
+    /// Information about the trashed note
+    pub fn trash_note(&self, id: &str) -> Result<TrashedNote> {
+        let path = self.get_note_path(id)?;
+
+        let original_path = path
+            .strip_prefix(&self.notes_dir)
+            .context("Path is not in notes directory")?
+            .to_string_lossy()
+            .to_string();
+
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("Invalid note file name")?;
+
+        let trash_dir = self.trash_dir();
+        fs::create_dir_all(&trash_dir).context("Failed to create trash directory")?;
+
+        // Prefix with a timestamp so notes with the same name never collide in the trash
+        let deleted_at = Utc::now();
+        let trash_id = format!("{}_{}", deleted_at.timestamp_millis(), file_name);
+        let trash_path = trash_dir.join(&trash_id);
+
+        fs::rename(&path, &trash_path).context("Failed to move note to trash")?;
+        self.uncache_note(id);
+
+        let metadata = TrashMetadata {
+            original_path,
+            deleted_at,
+        };
+        let metadata_str =
+            serde_json::to_string_pretty(&metadata).context("Failed to serialize trash metadata")?;
+        fs::write(sidecar_path(&trash_path), metadata_str)
+            .context("Failed to write trash metadata")?;
+
+        Ok(to_trashed_note(trash_id, metadata))
+    }
+
+    /// Lists all notes currently in the trash
+    ///
+    /// # Returns
+    /// Trashed notes, most recently deleted first
+    pub fn list_trashed_notes(&self) -> Result<Vec<TrashedNote>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut trashed = Vec::new();
+
+        for entry in fs::read_dir(&trash_dir).context("Failed to read trash directory")? {
+            let path = entry.context("Failed to read trash entry")?.path();
+
+            if !path.is_file() || is_sidecar(&path) {
+                continue;
+            }
+
+            let Some(trash_id) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            let metadata = read_metadata(&path)?;
+            trashed.push(to_trashed_note(trash_id.to_string(), metadata));
+        }
+
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+
+        Ok(trashed)
+    }
+
+    /// Restores a trashed note to its original location
+    ///
+    /// # Parameters
+    /// * `trash_id` - Identifier of the trashed note
+    ///
+    /// # Returns
+    /// The restored note
+    pub fn restore_trashed_note(&self, trash_id: &str) -> Result<Note> {
+        let trash_path = self.get_trash_path(trash_id)?;
+        let metadata = read_metadata(&trash_path)?;
+
+        let target_path = self.notes_dir.join(&metadata.original_path);
+        if target_path.exists() {
+            anyhow::bail!("A note already exists at the original location");
+        }
+
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create parent directories")?;
+        }
+
+        fs::rename(&trash_path, &target_path).context("Failed to restore note from trash")?;
+        self.cache_note(None, &target_path);
+
+        let sidecar = sidecar_path(&trash_path);
+        if sidecar.exists() {
+            fs::remove_file(sidecar).context("Failed to remove trash metadata")?;
+        }
+
+        self.read_note(&target_path)
+    }
+
+    /// Permanently removes trashed notes older than the retention window
+    ///
+    /// # Parameters
+    /// * `retention_days` - Number of days a note is kept in the trash
+    ///
+    /// # Returns
+    /// The number of notes permanently removed
+    pub fn empty_trash(&self, retention_days: u32) -> Result<usize> {
+        let cutoff = Utc::now() - Duration::days(retention_days as i64);
+        let mut removed = 0;
+
+        for trashed in self.list_trashed_notes()? {
+            if trashed.deleted_at > cutoff {
+                continue;
+            }
+
+            let trash_path = self.trash_dir().join(&trashed.trash_id);
+            fs::remove_file(&trash_path).context("Failed to remove trashed note")?;
+
+            let sidecar = sidecar_path(&trash_path);
+            if sidecar.exists() {
+                fs::remove_file(sidecar).context("Failed to remove trash metadata")?;
+            }
+
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// Gets the trash directory for this notes directory
+    fn trash_dir(&self) -> PathBuf {
+        self.notes_dir.join(TRASH_DIR)
+    }
+
+    /// Resolves a trash ID to the path of the trashed note
+    ///
+    /// # Parameters
+    /// * `trash_id` - Identifier of the trashed note
+    ///
+    /// # Returns
+    /// Path to the trashed note file
+    fn get_trash_path(&self, trash_id: &str) -> Result<PathBuf> {
+        // Trash IDs are plain file names; reject anything that could escape the trash
+        if Path::new(trash_id).file_name().and_then(|name| name.to_str()) != Some(trash_id) {
+            anyhow::bail!("Invalid trash ID: {}", trash_id);
+        }
+
+        let path = self.trash_dir().join(trash_id);
+
+        if !path.is_file() || is_sidecar(&path) {
+            anyhow::bail!("Trashed note not found: {}", trash_id);
+        }
+
+        Ok(path)
+    }
+}
+
+/// Gets the sidecar metadata path for a trashed note
+fn sidecar_path(trash_path: &Path) -> PathBuf {
+    let mut sidecar = trash_path.as_os_str().to_owned();
+    sidecar.push(".");
+    sidecar.push(SIDECAR_EXTENSION);
+    PathBuf::from(sidecar)
+}
+
+/// Checks if a path in the trash is a sidecar metadata file
+fn is_sidecar(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == SIDECAR_EXTENSION)
+}
+
+/// Reads the metadata of a trashed note
+///
+/// Falls back to the file's modification time when the sidecar is missing,
+/// so orphaned files are still listed and eventually cleaned up.
+fn read_metadata(trash_path: &Path) -> Result<TrashMetadata> {
+    let sidecar = sidecar_path(trash_path);
+
+    if sidecar.exists() {
+        let metadata_str =
+            fs::read_to_string(&sidecar).context("Failed to read trash metadata")?;
+        return serde_json::from_str(&metadata_str).context("Failed to parse trash metadata");
+    }
+
+    let deleted_at = trash_path
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+
+    let original_path = trash_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("")
+        .to_string();
+
+    Ok(TrashMetadata {
+        original_path,
+        deleted_at,
+    })
+}
+
+/// Builds the public trash entry from its metadata
+fn to_trashed_note(trash_id: String, metadata: TrashMetadata) -> TrashedNote {
+    let title = Path::new(&metadata.original_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Untitled Note")
+        .to_string();
+
+    TrashedNote {
+        trash_id,
+        title,
+        original_path: metadata.original_path,
+        deleted_at: metadata.deleted_at,
+    }
+}
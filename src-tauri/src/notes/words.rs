@@ -0,0 +1,213 @@
+use crate::notes::{split_frontmatter, NoteManager};
+use anyhow::Result;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Common English words left out of word frequencies
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "could", "did", "do", "does", "doing", "down", "during", "each", "few", "for",
+    "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself",
+    "him", "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "me", "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once",
+    "only", "or", "other", "our", "ours", "ourselves", "out", "over", "own", "same", "she",
+    "should", "so", "some", "such", "than", "that", "the", "their", "theirs", "them",
+    "themselves", "then", "there", "these", "they", "this", "those", "through", "to", "too",
+    "under", "until", "up", "very", "was", "we", "were", "what", "when", "where", "which",
+    "while", "who", "whom", "why", "will", "with", "would", "you", "your", "yours", "yourself",
+    "yourselves",
+];
+
+/// Removes Markdown syntax so only the prose of a note is counted
+struct MarkdownStripper {
+    /// Patterns and their replacements, applied in order
+    patterns: Vec<(Regex, &'static str)>,
+}
+
+impl MarkdownStripper {
+    fn new() -> Self {
+        let patterns = [
+            // Fenced code blocks, then inline code
+            (r"(?s)```.*?```", " "),
+            (r"`[^`\n]*`", " "),
+            // Images and links keep their text, wikilinks their target or alias
+            (r"!?\[([^\]]*)\]\([^)]*\)", "$1"),
+            (r"\[\[(?:[^\]|]*\|)?([^\]]*)\]\]", "$1"),
+            (r"https?://\S+", " "),
+            // Headers, then bold and italic markers
+            (r"(?m)^\s{0,3}#{1,6}\s+", ""),
+            (r"[*_~]+", " "),
+        ];
+
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|(pattern, replacement)| {
+                    (Regex::new(pattern).expect("Invalid Markdown pattern"), replacement)
+                })
+                .collect(),
+        }
+    }
+
+    fn strip(&self, content: &str) -> String {
+        self.patterns
+            .iter()
+            .fold(content.to_string(), |text, (regex, replacement)| {
+                regex.replace_all(&text, *replacement).into_owned()
+            })
+    }
+}
+
+impl NoteManager {
+    /// Counts the most frequent words of a note
+    ///
+    /// Markdown syntax and frontmatter are removed and words are lowercased.
+    /// English stop words and the configured custom stop words are skipped.
+    ///
+    /// # Parameters
+    /// * `id` - ID of the note
+    /// * `top_n` - Maximum number of words to return
+    ///
+    /// # Returns
+    /// Words with their counts, most frequent first
+    pub fn get_word_frequency(&self, id: &str, top_n: usize) -> Result<Vec<(String, usize)>> {
+        let note = self.get_note(id)?;
+
+        let stripper = MarkdownStripper::new();
+        let stop_words = self.stop_words();
+        let mut counts = HashMap::new();
+        count_words(&note.content, &stripper, &stop_words, &mut counts);
+
+        Ok(top_words(counts, top_n))
+    }
+
+    /// Counts the most frequent words across all notes
+    ///
+    /// Words are counted like in `get_word_frequency`. Notes that cannot be
+    /// read are skipped.
+    ///
+    /// # Parameters
+    /// * `top_n` - Maximum number of words to return
+    ///
+    /// # Returns
+    /// Words with their counts, most frequent first
+    pub fn get_vault_word_frequency(&self, top_n: usize) -> Result<Vec<(String, usize)>> {
+        let stripper = MarkdownStripper::new();
+        let stop_words = self.stop_words();
+        let mut counts = HashMap::new();
+
+        for summary in self.list_notes(None)? {
+            match self.get_note(&summary.id) {
+                Ok(note) => count_words(&note.content, &stripper, &stop_words, &mut counts),
+                Err(e) => log::warn!("Failed to read note {}: {}", summary.id, e),
+            }
+        }
+
+        Ok(top_words(counts, top_n))
+    }
+
+    /// Gets the built-in and custom stop words
+    fn stop_words(&self) -> HashSet<String> {
+        STOP_WORDS
+            .iter()
+            .map(|word| word.to_string())
+            .chain(self.custom_stop_words.iter().map(|word| word.to_lowercase()))
+            .collect()
+    }
+}
+
+/// Adds the words of note content to word counts
+fn count_words(
+    content: &str,
+    stripper: &MarkdownStripper,
+    stop_words: &HashSet<String>,
+    counts: &mut HashMap<String, usize>,
+) {
+    let (_, body) = split_frontmatter(content);
+
+    for word in stripper.strip(body).split_whitespace() {
+        // Remove surrounding punctuation, including the # of inline tags
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+
+        if !word.chars().any(char::is_alphabetic) || stop_words.contains(&word) {
+            continue;
+        }
+
+        *counts.entry(word).or_insert(0) += 1;
+    }
+}
+
+/// Sorts word counts by count descending, then alphabetically, and keeps the first `top_n`
+fn top_words(counts: HashMap<String, usize>, top_n: usize) -> Vec<(String, usize)> {
+    let mut words: Vec<(String, usize)> = counts.into_iter().collect();
+    words.sort_by(|(word_a, count_a), (word_b, count_b)| {
+        count_b.cmp(count_a).then_with(|| word_a.cmp(word_b))
+    });
+    words.truncate(top_n);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_strip_markdown() {
+        let stripper = MarkdownStripper::new();
+        let content = "# Title\n\n**Bold** and _italic_ [link text](https://example.com) [[Target|alias]]\n\n```\nlet code = 1;\n```\nUse `inline`";
+
+        let words: Vec<String> = stripper
+            .strip(content)
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+
+        assert_eq!(
+            words,
+            vec!["Title", "Bold", "and", "italic", "link", "text", "alias", "Use"]
+        );
+    }
+
+    #[test]
+    fn test_get_word_frequency() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf())
+            .with_custom_stop_words(vec!["Rust".to_string()]);
+        let note = manager
+            .create_note(
+                "Notes",
+                "# Notes\n\nThe tantivy index. Tantivy, **index** and #rust 2024\nnotes rust",
+                NoteType::Markdown,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            manager.get_word_frequency(&note.id, 10).unwrap(),
+            vec![
+                ("index".to_string(), 2),
+                ("notes".to_string(), 2),
+                ("tantivy".to_string(), 2),
+            ]
+        );
+        assert_eq!(manager.get_word_frequency(&note.id, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_get_vault_word_frequency() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = NoteManager::new(temp_dir.path().to_path_buf());
+        manager.create_note("A", "apple banana", NoteType::PlainText, None).unwrap();
+        manager.create_note("B", "apple cherry", NoteType::PlainText, None).unwrap();
+
+        assert_eq!(
+            manager.get_vault_word_frequency(2).unwrap(),
+            vec![("apple".to_string(), 2), ("banana".to_string(), 1)]
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use crate::notes::Note;
+use crate::notes::{content_stats, normalized_link_targets, Note};
 use crate::search::index::IndexableDocument;
+use std::path::Path;
 
 /// Converts between Note objects and IndexableDocument objects
 pub struct DocumentConverter;
@@ -18,6 +19,8 @@ impl DocumentConverter {
     /// # Returns
     /// An IndexableDocument representing the note
     pub fn note_to_document(&self, note: &Note) -> IndexableDocument {
+        let (word_count, char_count) = content_stats(&note.content);
+        
         IndexableDocument {
             id: note.id.clone(),
             title: note.title.clone(),
@@ -26,8 +29,49 @@ impl DocumentConverter {
             created: note.created,
             modified: note.modified,
             file_type: format!("{:?}", note.file_type),
+            links: Self::extract_links(&note.content),
+            folder_path: Self::folder_path(&note.path),
+            word_count,
+            char_count,
+            size_bytes: note.content.len() as u64,
         }
     }
+
+    /// Gets the folder of a note path with `/` separators on every platform
+    ///
+    /// # Parameters
+    /// * `path` - Path of the note relative to the notes directory
+    ///
+    /// # Returns
+    /// The folder path, empty for notes in the root of the notes directory
+    fn folder_path(path: &str) -> String {
+        Path::new(path)
+            .parent()
+            .map(|parent| {
+                parent
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extracts the outgoing wikilink targets of a note
+    ///
+    /// Targets are lowercased and deduplicated so `search_by_field("links", ...)`
+    /// matches regardless of how a link was capitalized.
+    ///
+    /// # Parameters
+    /// * `content` - The note content
+    ///
+    /// # Returns
+    /// The sorted link targets
+    fn extract_links(content: &str) -> Vec<String> {
+        let mut links: Vec<String> = normalized_link_targets(content).into_iter().collect();
+        links.sort();
+        links
+    }
     
     /// Converts multiple Notes to IndexableDocuments
     /// 
@@ -42,3 +86,34 @@ impl DocumentConverter {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::NoteType;
+    use chrono::Utc;
+
+    #[test]
+    fn test_note_to_document_extracts_links() {
+        let note = Note {
+            id: "id".to_string(),
+            title: "Source".to_string(),
+            content: "See [[Beta]], [[alpha]] and [[beta]] again.".to_string(),
+            created: Utc::now(),
+            modified: Utc::now(),
+            tags: Vec::new(),
+            file_type: NoteType::Markdown,
+            path: "source.md".to_string(),
+        };
+
+        let document = DocumentConverter::new().note_to_document(&note);
+
+        assert_eq!(document.links, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_folder_path() {
+        assert_eq!(DocumentConverter::folder_path("note.md"), "");
+        assert_eq!(DocumentConverter::folder_path("projects/rust/note.md"), "projects/rust");
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Serialize, Serializer};
+use std::sync::PoisonError;
 use thiserror::Error;
 
 /// Errors that can occur in the search module
@@ -38,6 +40,58 @@ pub enum SearchError {
     /// Tantivy error
     #[error("Tantivy error: {0}")]
     TantivyError(String),
+
+    /// The search service lock was poisoned
+    #[error("Search service unavailable: {0}")]
+    LockError(String),
+}
+
+/// Structured error sent to the frontend by search commands
+#[derive(Debug, Clone, Serialize)]
+pub struct TauriError {
+    /// Stable identifier of the error kind
+    pub code: &'static str,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl SearchError {
+    /// Gets a stable identifier for the kind of error
+    ///
+    /// # Returns
+    /// A snake_case error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            SearchError::IndexCreationError(_) => "index_creation",
+            SearchError::IndexOpenError(_) => "index_open",
+            SearchError::DocumentAddError(_) => "document_add",
+            SearchError::DocumentRemoveError(_) => "document_remove",
+            SearchError::QueryParseError(_) => "query_parse",
+            SearchError::SearchExecutionError(_) => "search_execution",
+            SearchError::SnippetGenerationError(_) => "snippet_generation",
+            SearchError::IoError(_) => "io",
+            SearchError::TantivyError(_) => "tantivy",
+            SearchError::LockError(_) => "lock",
+        }
+    }
+
+    /// Converts the error into the structure returned by Tauri commands
+    ///
+    /// # Returns
+    /// The error code and message
+    pub fn to_tauri_error(&self) -> TauriError {
+        TauriError {
+            code: self.code(),
+            message: self.to_string(),
+        }
+    }
+}
+
+/// Serialize as `{ code, message }` so Tauri commands can return SearchError directly
+impl Serialize for SearchError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_tauri_error().serialize(serializer)
+    }
 }
 
 /// Convert Tantivy errors to SearchError
@@ -46,3 +100,30 @@ impl From<tantivy::TantivyError> for SearchError {
         SearchError::TantivyError(err.to_string())
     }
 }
+
+/// Convert a poisoned lock to SearchError
+impl<T> From<PoisonError<T>> for SearchError {
+    fn from(err: PoisonError<T>) -> Self {
+        SearchError::LockError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_error_serializes_code_and_message() {
+        let err = SearchError::QueryParseError("unbalanced quote".to_string());
+
+        let value = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "code": "query_parse",
+                "message": "Failed to parse search query: unbalanced quote",
+            })
+        );
+    }
+}
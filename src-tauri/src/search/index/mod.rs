@@ -17,6 +17,16 @@ pub struct IndexableDocument {
     pub modified: chrono::DateTime<chrono::Utc>,
     /// Type of the document
     pub file_type: String,
+    /// Lowercased targets of the wikilinks in the document
+    pub links: Vec<String>,
+    /// Folder of the document relative to the notes directory, `/`-separated and empty for the root
+    pub folder_path: String,
+    /// Number of words in the document
+    pub word_count: u32,
+    /// Number of characters in the document, excluding line breaks
+    pub char_count: u32,
+    /// Size of the document in bytes
+    pub size_bytes: u64,
 }
 
 /// Interface for search index operations
@@ -25,24 +35,37 @@ pub trait SearchIndex {
     #[allow(dead_code)]
     fn add_document(&self, document: &IndexableDocument) -> Result<(), SearchError>;
     
+    /// Add or replace multiple documents with a single commit
+    fn add_documents(&self, documents: &[IndexableDocument]) -> Result<(), SearchError>;
+    
     /// Remove a document from the index
     #[allow(dead_code)]
     fn remove_document(&self, id: &str) -> Result<(), SearchError>;
     
+    /// Remove multiple documents with a single commit
+    fn remove_documents(&self, ids: &[String]) -> Result<(), SearchError>;
+    
     /// Clear the entire index
     #[allow(dead_code)]
     fn clear(&self) -> Result<(), SearchError>;
     
-    /// Optimize the index for better performance
-    #[allow(dead_code)]
+    /// Optimize the index by merging its segments
     fn optimize(&self) -> Result<(), SearchError>;
     
     /// Get the number of documents in the index
     #[allow(dead_code)]
     fn document_count(&self) -> Result<usize, SearchError>;
     
+    /// Get the number of searchable segments of the index
+    fn segment_count(&self) -> Result<usize, SearchError>;
+    
+    /// Get the IDs of all documents in the index
+    fn all_document_ids(&self) -> Result<Vec<String>, SearchError>;
+    
     /// Rebuild the index with the given documents
-    fn rebuild_index(&self, documents: &[IndexableDocument]) -> Result<(), SearchError>;
+    /// 
+    /// `on_progress` is called periodically with the number of documents indexed so far.
+    fn rebuild_index(&self, documents: &[IndexableDocument], on_progress: &dyn Fn(usize)) -> Result<(), SearchError>;
 }
 
 pub mod tantivy_index;
@@ -1,7 +1,13 @@
 use std::path::{Path, PathBuf};
-use log::info;
+use log::{error, info, warn};
 use tantivy::{
-    schema::{Field, Schema, STORED, TEXT},
+    collector::DocSetCollector,
+    query::AllQuery,
+    schema::{
+        Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, INDEXED, STORED, STRING,
+        TEXT,
+    },
+    tokenizer::{Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer},
     Index, IndexReader, ReloadPolicy, Term,
 };
 use tempfile::TempDir;
@@ -9,6 +15,15 @@ use tempfile::TempDir;
 use crate::search::error::SearchError;
 use super::{IndexableDocument, SearchIndex};
 
+/// Number of documents indexed between progress reports during a rebuild
+const PROGRESS_INTERVAL: usize = 100;
+
+/// Tokens longer than this many bytes are dropped, like tantivy's default tokenizer
+const MAX_TOKEN_LENGTH: usize = 40;
+
+/// Name of tantivy's built-in tokenizer (no stemming)
+const DEFAULT_TOKENIZER: &str = "default";
+
 /// Tantivy implementation of the SearchIndex trait
 #[derive(Clone)]
 pub struct TantivyIndex {
@@ -18,6 +33,12 @@ pub struct TantivyIndex {
     index: Index,
     /// Index reader for searching
     reader: IndexReader,
+    /// Heap size of index writers in bytes
+    writer_memory_bytes: usize,
+    /// Language code used to tokenize titles and content
+    language: String,
+    /// Whether an index with an outdated schema was discarded and has to be rebuilt
+    recreated: bool,
     /// Schema fields
     id_field: Field,
     title_field: Field,
@@ -26,6 +47,11 @@ pub struct TantivyIndex {
     created_field: Field,
     modified_field: Field,
     file_type_field: Field,
+    links_field: Field,
+    folder_path_field: Field,
+    word_count_field: Field,
+    char_count_field: Field,
+    size_bytes_field: Field,
 }
 
 impl TantivyIndex {
@@ -33,16 +59,18 @@ impl TantivyIndex {
     /// 
     /// # Parameters
     /// * `index_path` - Path to the search index directory
+    /// * `writer_memory_mb` - Heap size of index writers in megabytes
+    /// * `language` - Language code (e.g. `de`) used to tokenize titles and content
     /// 
     /// # Returns
     /// A new TantivyIndex instance
-    pub fn new(index_path: &Path) -> Result<Self, SearchError> {
+    pub fn new(index_path: &Path, writer_memory_mb: u32, language: &str) -> Result<Self, SearchError> {
         // Create search index directory if it doesn't exist
         std::fs::create_dir_all(index_path)
             .map_err(|e| SearchError::IoError(e))?;
         
         // Define schema
-        let schema = Self::create_schema()?;
+        let schema = Self::create_schema(language)?;
         
         // Get field references
         let id_field = schema.get_field("id")
@@ -59,17 +87,49 @@ impl TantivyIndex {
             .map_err(|_| SearchError::IndexCreationError("Failed to get modified field".into()))?;
         let file_type_field = schema.get_field("file_type")
             .map_err(|_| SearchError::IndexCreationError("Failed to get file_type field".into()))?;
+        let links_field = schema.get_field("links")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get links field".into()))?;
+        let folder_path_field = schema.get_field("folder_path")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get folder_path field".into()))?;
+        let word_count_field = schema.get_field("word_count")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get word_count field".into()))?;
+        let char_count_field = schema.get_field("char_count")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get char_count field".into()))?;
+        let size_bytes_field = schema.get_field("size_bytes")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get size_bytes field".into()))?;
         
         // Create or open index
-        let index = if index_path.join("meta.json").exists() {
+        let mut recreated = false;
+        let existing_index = if index_path.join("meta.json").exists() {
             info!("Opening existing search index at {:?}", index_path);
-            Index::open_in_dir(index_path)
-                .map_err(|e| SearchError::IndexOpenError(e.to_string()))?
+            let existing_index = Index::open_in_dir(index_path)
+                .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+            
+            // An index created with an older schema is discarded and reported through `was_recreated`
+            if existing_index.schema() == schema {
+                Some(existing_index)
+            } else {
+                warn!("Search index schema changed, recreating empty index at {:?}", index_path);
+                drop(existing_index);
+                std::fs::remove_dir_all(index_path)
+                    .map_err(|e| SearchError::IoError(e))?;
+                std::fs::create_dir_all(index_path)
+                    .map_err(|e| SearchError::IoError(e))?;
+                recreated = true;
+                None
+            }
+        } else {
+            None
+        };
+        
+        let index = if let Some(existing_index) = existing_index {
+            existing_index
         } else {
             info!("Creating new search index at {:?}", index_path);
             Index::create_in_dir(index_path, schema)
                 .map_err(|e| SearchError::IndexCreationError(e.to_string()))?
         };
+        register_tokenizer(&index, language);
         
         // Create reader
         let reader = index
@@ -82,6 +142,9 @@ impl TantivyIndex {
             index_path: index_path.to_path_buf(),
             index,
             reader,
+            writer_memory_bytes: writer_memory_mb as usize * 1_000_000,
+            language: language.to_string(),
+            recreated,
             id_field,
             title_field,
             content_field,
@@ -89,6 +152,11 @@ impl TantivyIndex {
             created_field,
             modified_field,
             file_type_field,
+            links_field,
+            folder_path_field,
+            word_count_field,
+            char_count_field,
+            size_bytes_field,
         })
     }
     
@@ -102,21 +170,51 @@ impl TantivyIndex {
         self.reader.clone()
     }
     
+    /// Checks whether an existing index with an outdated schema was discarded
+    /// 
+    /// The recreated index is empty, so the notes have to be indexed again.
+    pub fn was_recreated(&self) -> bool {
+        self.recreated
+    }
+    
     /// Creates the search schema
     /// 
+    /// The tokenizer name is part of the schema, so changing the language
+    /// makes an existing index incompatible and it is recreated.
+    /// 
+    /// # Parameters
+    /// * `language` - Language code used to tokenize titles and content
+    /// 
     /// # Returns
     /// The Tantivy schema for indexing documents
-    fn create_schema() -> Result<Schema, SearchError> {
+    fn create_schema(language: &str) -> Result<Schema, SearchError> {
         let mut builder = Schema::builder();
         
+        let text_options = TextOptions::default()
+            .set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(&tokenizer_name(language))
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            )
+            .set_stored();
+        
         // Add fields to schema
-        builder.add_text_field("id", TEXT | STORED);
-        builder.add_text_field("title", TEXT | STORED);
-        builder.add_text_field("content", TEXT | STORED);
+        // IDs and file types are matched exactly, so they are not tokenized
+        builder.add_text_field("id", STRING | STORED);
+        builder.add_text_field("title", text_options.clone());
+        builder.add_text_field("content", text_options);
         builder.add_text_field("tags", TEXT | STORED);
-        builder.add_date_field("created", STORED);
-        builder.add_date_field("modified", STORED);
-        builder.add_text_field("file_type", STORED);
+        builder.add_date_field("created", INDEXED | STORED);
+        builder.add_date_field("modified", INDEXED | STORED);
+        builder.add_text_field("file_type", STRING | STORED);
+        // One untokenized value per wikilink target, so backlinks are exact term lookups
+        builder.add_text_field("links", STRING);
+        // Untokenized so folders and their subfolders can be matched with term and range queries
+        builder.add_text_field("folder_path", STRING | STORED);
+        // Statistics are only stored, so search results can show them without reading the notes
+        builder.add_u64_field("word_count", STORED);
+        builder.add_u64_field("char_count", STORED);
+        builder.add_u64_field("size_bytes", STORED);
         
         Ok(builder.build())
     }
@@ -139,6 +237,13 @@ impl TantivyIndex {
         doc.add_date(self.created_field, tantivy::DateTime::from_timestamp_secs(document.created.timestamp()));
         doc.add_date(self.modified_field, tantivy::DateTime::from_timestamp_secs(document.modified.timestamp()));
         doc.add_text(self.file_type_field, &document.file_type);
+        for link in &document.links {
+            doc.add_text(self.links_field, link);
+        }
+        doc.add_text(self.folder_path_field, &document.folder_path);
+        doc.add_u64(self.word_count_field, document.word_count as u64);
+        doc.add_u64(self.char_count_field, document.char_count as u64);
+        doc.add_u64(self.size_bytes_field, document.size_bytes);
         
         doc
     }
@@ -146,7 +251,7 @@ impl TantivyIndex {
 
 impl SearchIndex for TantivyIndex {
     fn add_document(&self, document: &IndexableDocument) -> Result<(), SearchError> {
-        let mut writer = self.index.writer(50_000_000)
+        let mut writer = self.index.writer(self.writer_memory_bytes)
             .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
         
         // Remove existing document with same ID if it exists
@@ -160,11 +265,43 @@ impl SearchIndex for TantivyIndex {
         writer.commit()
             .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
         
+        // Make the document searchable right away
+        self.reader.reload()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+        
+        Ok(())
+    }
+    
+    fn add_documents(&self, documents: &[IndexableDocument]) -> Result<(), SearchError> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+        
+        let mut writer = self.index.writer(self.writer_memory_bytes)
+            .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
+        
+        // A delete only affects documents added before it, so if an ID is
+        // listed twice the last version wins
+        for document in documents {
+            writer.delete_term(Term::from_field_text(self.id_field, &document.id));
+            
+            let doc = self.convert_to_tantivy_doc(document);
+            writer.add_document(doc)
+                .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
+        }
+        
+        writer.commit()
+            .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
+        
+        // Make the whole batch searchable right away
+        self.reader.reload()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+        
         Ok(())
     }
     
     fn remove_document(&self, id: &str) -> Result<(), SearchError> {
-        let mut writer = self.index.writer(50_000_000)
+        let mut writer = self.index.writer(self.writer_memory_bytes)
             .map_err(|e| SearchError::DocumentRemoveError(e.to_string()))?;
         
         writer.delete_term(Term::from_field_text(self.id_field, id));
@@ -174,8 +311,29 @@ impl SearchIndex for TantivyIndex {
         Ok(())
     }
     
+    fn remove_documents(&self, ids: &[String]) -> Result<(), SearchError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        
+        let mut writer = self.index.writer(self.writer_memory_bytes)
+            .map_err(|e| SearchError::DocumentRemoveError(e.to_string()))?;
+        
+        for id in ids {
+            writer.delete_term(Term::from_field_text(self.id_field, id));
+        }
+        
+        writer.commit()
+            .map_err(|e| SearchError::DocumentRemoveError(e.to_string()))?;
+        
+        self.reader.reload()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+        
+        Ok(())
+    }
+    
     fn clear(&self) -> Result<(), SearchError> {
-        let mut writer = self.index.writer(50_000_000)
+        let mut writer = self.index.writer(self.writer_memory_bytes)
             .map_err(|e| SearchError::DocumentRemoveError(e.to_string()))?;
         
         writer.delete_all_documents()
@@ -187,14 +345,29 @@ impl SearchIndex for TantivyIndex {
     }
     
     fn optimize(&self) -> Result<(), SearchError> {
-        let mut writer = self.index.writer(50_000_000)
+        let segment_ids = self.index.searchable_segment_ids()
             .map_err(|e| SearchError::IndexCreationError(e.to_string()))?;
         
-        // Note: merge_segments doesn't exist in current Tantivy version
-        // Instead, we'll just commit which should trigger merges based on policy
-        writer.commit()
+        if segment_ids.len() <= 1 {
+            return Ok(());
+        }
+        
+        info!("Merging {} index segments", segment_ids.len());
+        
+        let mut writer = self.index.writer(self.writer_memory_bytes)
             .map_err(|e| SearchError::IndexCreationError(e.to_string()))?;
         
+        // Merge all searchable segments into one, which also purges deleted documents
+        writer.merge(&segment_ids)
+            .wait()
+            .map_err(|e| SearchError::IndexCreationError(format!("Failed to merge segments: {}", e)))?;
+        
+        writer.wait_merging_threads()
+            .map_err(|e| SearchError::IndexCreationError(format!("Failed to finish index merges: {}", e)))?;
+        
+        self.reader.reload()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+        
         Ok(())
     }
     
@@ -204,7 +377,30 @@ impl SearchIndex for TantivyIndex {
         Ok(searcher.num_docs() as usize)
     }
     
-    fn rebuild_index(&self, documents: &[IndexableDocument]) -> Result<(), SearchError> {
+    fn segment_count(&self) -> Result<usize, SearchError> {
+        let segment_metas = self.index.searchable_segment_metas()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
+        
+        Ok(segment_metas.len())
+    }
+    
+    fn all_document_ids(&self) -> Result<Vec<String>, SearchError> {
+        let searcher = self.reader.searcher();
+        
+        let doc_addresses = searcher.search(&AllQuery, &DocSetCollector)?;
+        
+        let mut ids = Vec::with_capacity(doc_addresses.len());
+        for doc_address in doc_addresses {
+            let doc = searcher.doc(doc_address)?;
+            if let Some(id) = doc.get_first(self.id_field).and_then(|f| f.as_text()) {
+                ids.push(id.to_string());
+            }
+        }
+        
+        Ok(ids)
+    }
+    
+    fn rebuild_index(&self, documents: &[IndexableDocument], on_progress: &dyn Fn(usize)) -> Result<(), SearchError> {
         info!("Rebuilding search index with {} documents", documents.len());
         
         // Create a temporary directory for the new index
@@ -212,11 +408,12 @@ impl SearchIndex for TantivyIndex {
             .map_err(|e| SearchError::IndexCreationError(format!("Failed to create temp directory: {}", e)))?;
         
         // Create a new schema
-        let schema = Self::create_schema()?;
+        let schema = Self::create_schema(&self.language)?;
         
         // Create a new index in the temporary directory
         let temp_index = Index::create_in_dir(temp_dir.path(), schema)
             .map_err(|e| SearchError::IndexCreationError(format!("Failed to create temp index: {}", e)))?;
+        register_tokenizer(&temp_index, &self.language);
         
         // Get field references for the new index
         let id_field = temp_index.schema().get_field("id")
@@ -233,13 +430,23 @@ impl SearchIndex for TantivyIndex {
             .map_err(|_| SearchError::IndexCreationError("Failed to get modified field".into()))?;
         let file_type_field = temp_index.schema().get_field("file_type")
             .map_err(|_| SearchError::IndexCreationError("Failed to get file_type field".into()))?;
+        let links_field = temp_index.schema().get_field("links")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get links field".into()))?;
+        let folder_path_field = temp_index.schema().get_field("folder_path")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get folder_path field".into()))?;
+        let word_count_field = temp_index.schema().get_field("word_count")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get word_count field".into()))?;
+        let char_count_field = temp_index.schema().get_field("char_count")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get char_count field".into()))?;
+        let size_bytes_field = temp_index.schema().get_field("size_bytes")
+            .map_err(|_| SearchError::IndexCreationError("Failed to get size_bytes field".into()))?;
         
         // Create a writer with the new index
-        let mut writer = temp_index.writer(50_000_000)
+        let mut writer = temp_index.writer(self.writer_memory_bytes)
             .map_err(|e| SearchError::IndexCreationError(format!("Failed to create index writer: {}", e)))?;
         
         // Add all documents to the index
-        for document in documents {
+        for (indexed, document) in documents.iter().enumerate() {
             let tags_str = document.tags.join(" ");
             
             let mut doc = tantivy::Document::new();
@@ -250,9 +457,20 @@ impl SearchIndex for TantivyIndex {
             doc.add_date(created_field, tantivy::DateTime::from_timestamp_secs(document.created.timestamp()));
             doc.add_date(modified_field, tantivy::DateTime::from_timestamp_secs(document.modified.timestamp()));
             doc.add_text(file_type_field, &document.file_type);
+            for link in &document.links {
+                doc.add_text(links_field, link);
+            }
+            doc.add_text(folder_path_field, &document.folder_path);
+            doc.add_u64(word_count_field, document.word_count as u64);
+            doc.add_u64(char_count_field, document.char_count as u64);
+            doc.add_u64(size_bytes_field, document.size_bytes);
             
             writer.add_document(doc)
                 .map_err(|e| SearchError::DocumentAddError(e.to_string()))?;
+            
+            if (indexed + 1) % PROGRESS_INTERVAL == 0 {
+                on_progress(indexed + 1);
+            }
         }
         
         // Commit the changes
@@ -260,50 +478,287 @@ impl SearchIndex for TantivyIndex {
         writer.commit()
             .map_err(|e| SearchError::IndexCreationError(format!("Failed to commit changes: {}", e)))?;
         
-        // Create a backup of the current index
-        let backup_path = self.index_path.with_extension("bak");
-        if self.index_path.exists() {
-            info!("Creating backup of existing index at {:?}", backup_path);
-            if backup_path.exists() {
-                std::fs::remove_dir_all(&backup_path)
-                    .map_err(|e| SearchError::IoError(e))?;
-            }
-            std::fs::rename(&self.index_path, &backup_path)
-                .map_err(|e| SearchError::IoError(e))?;
-        }
+        // Let background merges finish so no segment files change during the copy
+        writer.wait_merging_threads()
+            .map_err(|e| SearchError::IndexCreationError(format!("Failed to finish index merges: {}", e)))?;
         
-        // Create the target directory if it doesn't exist
-        std::fs::create_dir_all(&self.index_path)
-            .map_err(|e| SearchError::IoError(e))?;
+        swap_in_index(temp_dir.path(), &self.index_path, copy_dir_all)?;
         
-        // Copy the temporary index to the target location
-        info!("Moving temporary index to target location");
-        copy_dir_all(temp_dir.path(), &self.index_path)
-            .map_err(|e| SearchError::IoError(e))?;
+        // Make the rebuilt index searchable right away
+        self.reader.reload()
+            .map_err(|e| SearchError::IndexOpenError(e.to_string()))?;
         
-        // Remove the backup if everything succeeded
+        info!("Search index rebuilt successfully");
+        Ok(())
+    }
+}
+
+/// Replaces the index at `index_path` with a copy of the index at `new_index`
+/// 
+/// The current index is moved aside to a `.bak` directory first. If the copy
+/// fails partway through, the partial copy is removed and the backup restored,
+/// so the previous index stays usable.
+/// 
+/// # Parameters
+/// * `new_index` - Directory containing the new index
+/// * `index_path` - Location of the live index
+/// * `copy` - Function copying one directory to another
+/// 
+/// # Returns
+/// Result indicating success or failure
+fn swap_in_index<F>(new_index: &Path, index_path: &Path, copy: F) -> Result<(), SearchError>
+where
+    F: Fn(&Path, &Path) -> std::io::Result<()>,
+{
+    // Create a backup of the current index
+    let backup_path = index_path.with_extension("bak");
+    if index_path.exists() {
+        info!("Creating backup of existing index at {:?}", backup_path);
         if backup_path.exists() {
-            info!("Removing backup index");
-            std::fs::remove_dir_all(backup_path)
+            std::fs::remove_dir_all(&backup_path)
                 .map_err(|e| SearchError::IoError(e))?;
         }
-        
-        info!("Search index rebuilt successfully");
-        Ok(())
+        std::fs::rename(index_path, &backup_path)
+            .map_err(|e| SearchError::IoError(e))?;
+    }
+    
+    // Create the target directory if it doesn't exist
+    std::fs::create_dir_all(index_path)
+        .map_err(|e| SearchError::IoError(e))?;
+    
+    // Copy the new index to the target location
+    info!("Moving temporary index to target location");
+    if let Err(e) = copy(new_index, index_path) {
+        error!("Failed to copy rebuilt index, restoring backup: {}", e);
+        restore_backup(index_path, &backup_path);
+        return Err(SearchError::IoError(e));
+    }
+    
+    // Remove the backup if everything succeeded
+    if backup_path.exists() {
+        info!("Removing backup index");
+        std::fs::remove_dir_all(backup_path)
+            .map_err(|e| SearchError::IoError(e))?;
+    }
+    
+    Ok(())
+}
+
+/// Replaces a partially written index with its backup
+/// 
+/// Failures are logged rather than returned, since this already runs while
+/// handling an error.
+fn restore_backup(index_path: &Path, backup_path: &Path) {
+    if !backup_path.exists() {
+        return;
+    }
+    
+    if let Err(e) = std::fs::remove_dir_all(index_path) {
+        error!("Failed to remove partial index at {:?}: {}", index_path, e);
+        return;
+    }
+    
+    if let Err(e) = std::fs::rename(backup_path, index_path) {
+        error!("Failed to restore index backup from {:?}: {}", backup_path, e);
     }
 }
 
 /// Recursively copy a directory
-fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> std::io::Result<()> {
-    std::fs::create_dir_all(&dst)?;
+fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
         let ty = entry.file_type()?;
         if ty.is_dir() {
-            copy_dir_all(entry.path(), dst.as_ref().join(entry.file_name()))?;
+            copy_dir_all(&entry.path(), &dst.join(entry.file_name()))?;
         } else {
-            std::fs::copy(entry.path(), dst.as_ref().join(entry.file_name()))?;
+            let target = dst.join(entry.file_name());
+            std::fs::copy(entry.path(), &target)?;
+            // Make sure the copy is on disk before the backup is removed
+            std::fs::File::open(&target)?.sync_all()?;
         }
     }
     Ok(())
 }
+
+/// Gets the stemmer language for a language code
+/// 
+/// English is left out so that English notes keep tantivy's default tokenizer.
+/// 
+/// # Parameters
+/// * `language` - ISO 639-1 language code
+/// 
+/// # Returns
+/// The stemmer language, or None if words are not stemmed
+fn stemmer_language(language: &str) -> Option<Language> {
+    match language {
+        "ar" => Some(Language::Arabic),
+        "da" => Some(Language::Danish),
+        "nl" => Some(Language::Dutch),
+        "fi" => Some(Language::Finnish),
+        "fr" => Some(Language::French),
+        "de" => Some(Language::German),
+        "el" => Some(Language::Greek),
+        "hu" => Some(Language::Hungarian),
+        "it" => Some(Language::Italian),
+        "no" | "nb" => Some(Language::Norwegian),
+        "pt" => Some(Language::Portuguese),
+        "ro" => Some(Language::Romanian),
+        "ru" => Some(Language::Russian),
+        "es" => Some(Language::Spanish),
+        "sv" => Some(Language::Swedish),
+        "ta" => Some(Language::Tamil),
+        "tr" => Some(Language::Turkish),
+        _ => None,
+    }
+}
+
+/// Gets the name of the tokenizer used for a language
+/// 
+/// # Parameters
+/// * `language` - ISO 639-1 language code
+/// 
+/// # Returns
+/// The name of a stemming tokenizer, or of the default tokenizer
+fn tokenizer_name(language: &str) -> String {
+    match stemmer_language(language) {
+        Some(_) => format!("stem_{}", language),
+        None => DEFAULT_TOKENIZER.to_string(),
+    }
+}
+
+/// Registers the stemming tokenizer for a language on an index
+/// 
+/// Nothing is registered for languages without a stemmer, since the default
+/// tokenizer is always available.
+/// 
+/// # Parameters
+/// * `index` - The index to register the tokenizer on
+/// * `language` - ISO 639-1 language code
+fn register_tokenizer(index: &Index, language: &str) {
+    let Some(stemmer_language) = stemmer_language(language) else {
+        return;
+    };
+    
+    let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(MAX_TOKEN_LENGTH))
+        .filter(LowerCaser)
+        .filter(Stemmer::new(stemmer_language))
+        .build();
+    
+    index.tokenizers().register(&tokenizer_name(language), analyzer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DEFAULT_SEARCH_LANGUAGE, MIN_SEARCH_WRITER_MEMORY_MB};
+
+    fn write_index(dir: &Path, contents: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("meta.json"), contents).unwrap();
+    }
+
+    fn document(id: &str) -> IndexableDocument {
+        let content = format!("Content of {}", id);
+        
+        IndexableDocument {
+            id: id.to_string(),
+            title: id.to_string(),
+            char_count: content.chars().count() as u32,
+            size_bytes: content.len() as u64,
+            content,
+            tags: Vec::new(),
+            created: chrono::Utc::now(),
+            modified: chrono::Utc::now(),
+            file_type: "Markdown".to_string(),
+            links: Vec::new(),
+            folder_path: String::new(),
+            word_count: 3,
+        }
+    }
+
+    #[test]
+    fn test_optimize_merges_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, DEFAULT_SEARCH_LANGUAGE).unwrap();
+
+        // Every add commits, creating one segment per document
+        for id in ["a", "b", "c"] {
+            index.add_document(&document(id)).unwrap();
+        }
+        let segment_count = || index.index().searchable_segment_ids().unwrap().len();
+        assert_eq!(segment_count(), 3);
+
+        index.optimize().unwrap();
+
+        assert_eq!(segment_count(), 1);
+        assert_eq!(index.document_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_remove_documents_deletes_all_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, DEFAULT_SEARCH_LANGUAGE).unwrap();
+        index.add_documents(&[document("a"), document("b"), document("c")]).unwrap();
+
+        index.remove_documents(&["a".to_string(), "c".to_string()]).unwrap();
+
+        assert_eq!(index.document_count().unwrap(), 1);
+        assert_eq!(index.all_document_ids().unwrap(), vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_swap_in_index_replaces_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("index");
+        let new_index = temp_dir.path().join("new");
+        write_index(&index_path, "old");
+        write_index(&new_index, "new");
+
+        swap_in_index(&new_index, &index_path, copy_dir_all).unwrap();
+
+        assert_eq!(std::fs::read_to_string(index_path.join("meta.json")).unwrap(), "new");
+        assert!(!index_path.with_extension("bak").exists());
+    }
+
+    #[test]
+    fn test_schema_change_reports_recreated_index() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, DEFAULT_SEARCH_LANGUAGE).unwrap();
+        assert!(!index.was_recreated());
+        index.add_document(&document("a")).unwrap();
+        drop(index);
+
+        // The tokenizer is part of the schema, so another language changes it
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, "de").unwrap();
+        assert!(index.was_recreated());
+        assert_eq!(index.document_count().unwrap(), 0);
+        drop(index);
+
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, "de").unwrap();
+        assert!(!index.was_recreated());
+    }
+
+    #[test]
+    fn test_swap_in_index_restores_backup_on_failed_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("index");
+        let new_index = temp_dir.path().join("new");
+        write_index(&index_path, "old");
+        write_index(&new_index, "new");
+
+        // Write part of the index, then fail as if the disk were full
+        let failing_copy = |_: &Path, dst: &Path| -> std::io::Result<()> {
+            std::fs::write(dst.join("meta.json"), "ne")?;
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))
+        };
+
+        let result = swap_in_index(&new_index, &index_path, failing_copy);
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(index_path.join("meta.json")).unwrap(), "old");
+        assert!(!index_path.with_extension("bak").exists());
+    }
+}
@@ -4,4 +4,5 @@ mod query;
 mod document;
 mod service;
 
-pub use service::{SearchService, SearchResult};
+pub use query::{DateField, SearchOptions};
+pub use service::{IndexHealth, IndexProgress, PaginatedSearch, SearchService, SearchResult};
@@ -1,7 +1,36 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
 use crate::search::error::SearchError;
 
+/// Date field used for date range filtering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateField {
+    /// When the document was created
+    Created,
+    /// When the document was last modified
+    #[default]
+    Modified,
+}
+
+impl FromStr for DateField {
+    type Err = SearchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(DateField::Created),
+            "modified" => Ok(DateField::Modified),
+            _ => Err(SearchError::QueryParseError(format!("Invalid date field: {}", s))),
+        }
+    }
+}
+
 /// Search options for configuring search behavior
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct SearchOptions {
     /// Maximum number of results to return
     pub limit: usize,
@@ -14,6 +43,31 @@ pub struct SearchOptions {
     /// HTML tag to use for highlighting matches
     #[allow(dead_code)]
     pub highlight_tag: String,
+    /// Only match documents whose date field falls within this inclusive range
+    pub date_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Date field the date range applies to
+    pub date_field: DateField,
+    /// Whether to match terms approximately
+    pub fuzzy: bool,
+    /// Maximum Levenshtein distance for fuzzy matches
+    pub fuzzy_distance: u8,
+    /// Whether an empty query matches every document instead of none
+    pub match_all_on_empty: bool,
+    /// Drop results scoring below this value
+    ///
+    /// Tantivy's BM25 scores are not normalized to [0, 1] and depend on the
+    /// query and index contents, so useful thresholds have to be found by
+    /// experimenting.
+    pub min_score: Option<f32>,
+    /// Drop results with any of these tags (with or without a leading `#`)
+    pub exclude_tags: Vec<String>,
+    /// How many times `limit` candidates are fetched when tags are excluded
+    ///
+    /// Excluded results are removed after the search, so without fetching
+    /// extra candidates fewer than `limit` results could remain.
+    pub over_fetch_factor: f32,
+    /// Only match documents inside this folder or its subfolders, relative to the notes directory
+    pub path_prefix: Option<String>,
 }
 
 impl Default for SearchOptions {
@@ -24,6 +78,15 @@ impl Default for SearchOptions {
             tags_boost: 1.5,
             snippet_length: 150,
             highlight_tag: "em".to_string(),
+            date_range: None,
+            date_field: DateField::default(),
+            fuzzy: false,
+            fuzzy_distance: 1,
+            match_all_on_empty: false,
+            min_score: None,
+            exclude_tags: Vec::new(),
+            over_fetch_factor: 2.0,
+            path_prefix: None,
         }
     }
 }
@@ -37,14 +100,22 @@ pub struct SearchHit {
     pub title: String,
     /// Highlighted snippets from the content
     pub snippets: Vec<String>,
+    /// Byte ranges of matched terms in the content
+    pub match_ranges: Vec<(usize, usize)>,
     /// Tags associated with the document
     pub tags: Vec<String>,
     /// When the document was created
-    pub created: chrono::DateTime<chrono::Utc>,
+    pub created: DateTime<Utc>,
     /// When the document was last modified
-    pub modified: chrono::DateTime<chrono::Utc>,
+    pub modified: DateTime<Utc>,
     /// Type of the document
     pub file_type: String,
+    /// Number of words in the document
+    pub word_count: u32,
+    /// Number of characters in the document, excluding line breaks
+    pub char_count: u32,
+    /// Size of the document in bytes
+    pub size_bytes: u64,
     /// Search relevance score
     pub score: f32,
 }
@@ -54,9 +125,14 @@ pub trait QueryEngine {
     /// Search for documents matching a query
     fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError>;
     
+    /// Search for documents matching a query, also counting all matching documents
+    fn search_with_count(&self, query: &str, options: &SearchOptions) -> Result<(Vec<SearchHit>, usize), SearchError>;
+    
     /// Search for documents with a specific field value
-    #[allow(dead_code)]
     fn search_by_field(&self, field: &str, value: &str, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError>;
+    
+    /// Search for documents with all or any of the given tags
+    fn search_by_tags(&self, tags: &[&str], match_all: bool, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError>;
 }
 
 pub mod tantivy_query;
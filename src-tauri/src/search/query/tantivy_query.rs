@@ -1,15 +1,28 @@
+use std::collections::HashSet;
+use std::ops::Bound;
+
 use log::{debug, info};
 use tantivy::{
-    collector::TopDocs,
-    query::{QueryParser, TermQuery},
+    collector::{Count, TopDocs},
+    query::{
+        AllQuery, BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, PhraseQuery, Query, QueryParser,
+        RangeQuery, TermQuery,
+    },
     schema::{Field, IndexRecordOption},
+    tokenizer::TokenStream,
     Term,
     Index, IndexReader, SnippetGenerator,
 };
 
 use crate::search::error::SearchError;
 use crate::search::index::TantivyIndex;
-use super::{QueryEngine, SearchOptions, SearchHit};
+use super::{DateField, QueryEngine, SearchOptions, SearchHit};
+
+/// Terms shorter than this are matched exactly even in fuzzy mode
+const MIN_FUZZY_TERM_LENGTH: usize = 3;
+
+/// Largest edit distance supported by tantivy's fuzzy queries
+const MAX_FUZZY_DISTANCE: u8 = 2;
 
 /// Tantivy implementation of the QueryEngine trait
 pub struct TantivyQueryEngine {
@@ -25,6 +38,11 @@ pub struct TantivyQueryEngine {
     created_field: Field,
     modified_field: Field,
     file_type_field: Field,
+    links_field: Field,
+    folder_path_field: Field,
+    word_count_field: Field,
+    char_count_field: Field,
+    size_bytes_field: Field,
 }
 
 impl TantivyQueryEngine {
@@ -58,6 +76,16 @@ impl TantivyQueryEngine {
             .map_err(|_| SearchError::QueryParseError("Failed to get modified field".into()))?;
         let file_type_field = schema.get_field("file_type")
             .map_err(|_| SearchError::QueryParseError("Failed to get file_type field".into()))?;
+        let links_field = schema.get_field("links")
+            .map_err(|_| SearchError::QueryParseError("Failed to get links field".into()))?;
+        let folder_path_field = schema.get_field("folder_path")
+            .map_err(|_| SearchError::QueryParseError("Failed to get folder_path field".into()))?;
+        let word_count_field = schema.get_field("word_count")
+            .map_err(|_| SearchError::QueryParseError("Failed to get word_count field".into()))?;
+        let char_count_field = schema.get_field("char_count")
+            .map_err(|_| SearchError::QueryParseError("Failed to get char_count field".into()))?;
+        let size_bytes_field = schema.get_field("size_bytes")
+            .map_err(|_| SearchError::QueryParseError("Failed to get size_bytes field".into()))?;
         
         Ok(Self {
             index,
@@ -69,15 +97,254 @@ impl TantivyQueryEngine {
             created_field,
             modified_field,
             file_type_field,
+            links_field,
+            folder_path_field,
+            word_count_field,
+            char_count_field,
+            size_bytes_field,
         })
     }
     
+    /// Builds a query that matches each term approximately
+    /// 
+    /// Every term is looked up in the title, content and tags fields, and a
+    /// document matches if any term matches, like the default query parser.
+    /// 
+    /// # Parameters
+    /// * `query_str` - The search query
+    /// * `options` - Search options holding boosts and the fuzzy distance
+    /// 
+    /// # Returns
+    /// The fuzzy query
+    fn build_fuzzy_query(&self, query_str: &str, options: &SearchOptions) -> Box<dyn Query> {
+        let fields = [
+            (self.title_field, options.title_boost),
+            (self.content_field, 1.0),
+            (self.tags_field, options.tags_boost),
+        ];
+        let distance = options.fuzzy_distance.min(MAX_FUZZY_DISTANCE);
+        
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        
+        for word in query_str.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+            for (field, boost) in fields {
+                // Terms are normalized like the indexed text (lowercased, and stemmed for some languages)
+                for token in self.analyze(field, word) {
+                    let term = Term::from_field_text(field, &token);
+                    
+                    // Short terms would match too many unrelated words approximately
+                    let term_query: Box<dyn Query> = if token.chars().count() >= MIN_FUZZY_TERM_LENGTH {
+                        Box::new(FuzzyTermQuery::new(term, distance, true))
+                    } else {
+                        Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs))
+                    };
+                    
+                    clauses.push((Occur::Should, Box::new(BoostQuery::new(term_query, boost))));
+                }
+            }
+        }
+        
+        Box::new(BooleanQuery::new(clauses))
+    }
+    
+    /// Runs text through the tokenizer of a field
+    /// 
+    /// # Parameters
+    /// * `field` - The field whose tokenizer to use
+    /// * `text` - The text to tokenize
+    /// 
+    /// # Returns
+    /// The tokens as they are stored in the index
+    fn analyze(&self, field: Field, text: &str) -> Vec<String> {
+        let Ok(mut analyzer) = self.index.tokenizer_for_field(field) else {
+            return vec![text.to_lowercase()];
+        };
+        
+        let mut tokens = Vec::new();
+        let mut stream = analyzer.token_stream(text);
+        while stream.advance() {
+            tokens.push(stream.token().text.clone());
+        }
+        
+        tokens
+    }
+    
+    /// Normalizes a single-word value like the indexed text of a field
+    /// 
+    /// Values with several words are only lowercased, as before.
+    /// 
+    /// # Parameters
+    /// * `field` - The field whose tokenizer to use
+    /// * `value` - The value to normalize
+    /// 
+    /// # Returns
+    /// The term to look up
+    fn analyzed_term(&self, field: Field, value: &str) -> String {
+        match self.analyze(field, value).as_slice() {
+            [token] => token.clone(),
+            _ => value.to_lowercase(),
+        }
+    }
+    
+    /// Collects the content terms a query looks for
+    /// 
+    /// Only exact terms are reported; approximate (fuzzy) terms are not.
+    /// 
+    /// # Parameters
+    /// * `query` - The query to inspect
+    /// 
+    /// # Returns
+    /// The analyzed content terms
+    fn content_terms(&self, query: &dyn Query) -> HashSet<String> {
+        let mut terms = HashSet::new();
+        
+        query.query_terms(&mut |term, _| {
+            if term.field() == self.content_field {
+                if let Ok(text) = std::str::from_utf8(term.serialized_value_bytes()) {
+                    terms.insert(text.to_string());
+                }
+            }
+        });
+        
+        terms
+    }
+    
+    /// Finds the byte ranges of the tokens in content that match any of the terms
+    /// 
+    /// # Parameters
+    /// * `terms` - Analyzed content terms
+    /// * `content` - The stored content of a document
+    /// 
+    /// # Returns
+    /// Start and end byte offsets of every match in content order
+    fn match_ranges(&self, terms: &HashSet<String>, content: &str) -> Vec<(usize, usize)> {
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        
+        let Ok(mut analyzer) = self.index.tokenizer_for_field(self.content_field) else {
+            return Vec::new();
+        };
+        
+        let mut ranges = Vec::new();
+        let mut stream = analyzer.token_stream(content);
+        while stream.advance() {
+            let token = stream.token();
+            if terms.contains(&token.text) {
+                ranges.push((token.offset_from, token.offset_to));
+            }
+        }
+        
+        ranges
+    }
+    
+    /// Restricts a query to documents within the date range of the search options
+    /// 
+    /// # Parameters
+    /// * `query` - The query to restrict, or `None` to match all documents in the range
+    /// * `options` - Search options holding the date range
+    /// 
+    /// # Returns
+    /// The restricted query
+    fn apply_date_range(&self, query: Option<Box<dyn Query>>, options: &SearchOptions) -> Box<dyn Query> {
+        let Some((from, to)) = options.date_range else {
+            return query.unwrap_or_else(|| Box::new(AllQuery));
+        };
+        
+        let field_name = match options.date_field {
+            DateField::Created => "created",
+            DateField::Modified => "modified",
+        };
+        
+        let range_query: Box<dyn Query> = Box::new(RangeQuery::new_date_bounds(
+            field_name.to_string(),
+            Bound::Included(tantivy::DateTime::from_timestamp_secs(from.timestamp())),
+            Bound::Included(tantivy::DateTime::from_timestamp_secs(to.timestamp())),
+        ));
+        
+        match query {
+            Some(query) => Box::new(BooleanQuery::new(vec![
+                (Occur::Must, query),
+                (Occur::Must, range_query),
+            ])),
+            None => range_query,
+        }
+    }
+    
+    /// Restricts a query to documents inside the folder of the search options
+    /// 
+    /// The folder matches itself and its subfolders, but not sibling folders
+    /// sharing its name as a prefix.
+    /// 
+    /// # Parameters
+    /// * `query` - The query to restrict
+    /// * `options` - Search options holding the path prefix
+    /// 
+    /// # Returns
+    /// The restricted query
+    fn apply_path_prefix(&self, query: Box<dyn Query>, options: &SearchOptions) -> Box<dyn Query> {
+        let prefix = options
+            .path_prefix
+            .as_deref()
+            .map(|prefix| prefix.trim().replace('\\', "/"))
+            .unwrap_or_default();
+        let prefix = prefix.trim_matches('/');
+        if prefix.is_empty() {
+            return query;
+        }
+        
+        // Subfolder paths sort between "prefix/" and "prefix0", as '0' follows '/'
+        let subfolders_start = format!("{}/", prefix);
+        let subfolders_end = format!("{}0", prefix);
+        let folder_query = BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(self.folder_path_field, prefix),
+                    IndexRecordOption::Basic,
+                )) as Box<dyn Query>,
+            ),
+            (
+                Occur::Should,
+                Box::new(RangeQuery::new_str_bounds(
+                    "folder_path".to_string(),
+                    Bound::Included(subfolders_start.as_str()),
+                    Bound::Excluded(subfolders_end.as_str()),
+                )),
+            ),
+        ]);
+        
+        Box::new(BooleanQuery::new(vec![
+            (Occur::Must, query),
+            (Occur::Must, Box::new(folder_query)),
+        ]))
+    }
+    
+    /// Checks whether a document has any of the given tags
+    /// 
+    /// # Parameters
+    /// * `doc` - The document to check
+    /// * `tags` - Lowercase tags without `#`
+    /// 
+    /// # Returns
+    /// True if one of the document's tags is in `tags`
+    fn has_any_tag(&self, doc: &tantivy::Document, tags: &HashSet<String>) -> bool {
+        doc.get_first(self.tags_field)
+            .and_then(|f| f.as_text())
+            .map_or(false, |tags_str| {
+                tags_str
+                    .split_whitespace()
+                    .any(|tag| tags.contains(&tag.to_lowercase()))
+            })
+    }
+    
     /// Process a search result document into a SearchHit
     /// 
     /// # Parameters
     /// * `doc` - The document to process
     /// * `score` - The relevance score
     /// * `snippets` - Highlighted snippets
+    /// * `match_ranges` - Byte ranges of matches in the content
     /// 
     /// # Returns
     /// A SearchHit representing the document
@@ -86,6 +353,7 @@ impl TantivyQueryEngine {
         doc: &tantivy::Document,
         score: f32,
         snippets: Vec<String>,
+        match_ranges: Vec<(usize, usize)>,
     ) -> Result<SearchHit, SearchError> {
         // Extract stored fields
         let id = doc
@@ -130,14 +398,20 @@ impl TantivyQueryEngine {
             .unwrap_or("PlainText")
             .to_string();
         
+        let stored_u64 = |field: Field| doc.get_first(field).and_then(|f| f.as_u64()).unwrap_or(0);
+        
         Ok(SearchHit {
             id,
             title,
             snippets,
+            match_ranges,
             tags,
             created,
             modified,
             file_type,
+            word_count: u32::try_from(stored_u64(self.word_count_field)).unwrap_or(u32::MAX),
+            char_count: u32::try_from(stored_u64(self.char_count_field)).unwrap_or(u32::MAX),
+            size_bytes: stored_u64(self.size_bytes_field),
             score,
         })
     }
@@ -145,6 +419,10 @@ impl TantivyQueryEngine {
 
 impl QueryEngine for TantivyQueryEngine {
     fn search(&self, query_str: &str, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError> {
+        self.search_with_count(query_str, options).map(|(hits, _)| hits)
+    }
+    
+    fn search_with_count(&self, query_str: &str, options: &SearchOptions) -> Result<(Vec<SearchHit>, usize), SearchError> {
         info!("Executing search query: {}", query_str);
         
         let searcher = self.reader.searcher();
@@ -161,19 +439,47 @@ impl QueryEngine for TantivyQueryEngine {
         query_parser.set_field_boost(self.title_field, options.title_boost);
         query_parser.set_field_boost(self.tags_field, options.tags_boost);
         
-        // Parse query
-        let query = query_parser.parse_query(query_str)
-            .map_err(|e| SearchError::QueryParseError(e.to_string()))?;
+        // Parse query; an empty query matches every document in the date range
+        let parsed_query = if query_str.trim().is_empty() {
+            None
+        } else if options.fuzzy {
+            Some(self.build_fuzzy_query(query_str, options))
+        } else {
+            Some(query_parser.parse_query(query_str)
+                .map_err(|e| SearchError::QueryParseError(e.to_string()))?)
+        };
+        
+        let query = self.apply_path_prefix(self.apply_date_range(parsed_query, options), options);
         
         debug!("Parsed query: {:?}", query);
         
-        // Execute search
-        let top_docs = searcher.search(
+        let excluded_tags: HashSet<String> = options
+            .exclude_tags
+            .iter()
+            .map(|tag| tag.trim().trim_start_matches('#').to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        
+        // Fetch extra candidates to make up for the results with excluded tags
+        let fetch_limit = if excluded_tags.is_empty() {
+            options.limit
+        } else {
+            (options.limit as f32 * options.over_fetch_factor.max(1.0)).ceil() as usize
+        };
+        
+        // Execute search, counting all matches alongside the top results
+        let (top_docs, total_hits) = searcher.search(
             &query,
-            &TopDocs::with_limit(options.limit),
+            &(TopDocs::with_limit(fetch_limit), Count),
         ).map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
         
-        info!("Search returned {} results", top_docs.len());
+        info!("Search returned {} of {} results", top_docs.len(), total_hits);
+        
+        // Drop low-scoring results before generating their snippets
+        let top_docs: Vec<_> = match options.min_score {
+            Some(min_score) => top_docs.into_iter().filter(|(score, _)| *score >= min_score).collect(),
+            None => top_docs,
+        };
         
         // Create snippet generator for highlighting
         let mut snippet_generator = SnippetGenerator::create(
@@ -184,29 +490,39 @@ impl QueryEngine for TantivyQueryEngine {
         
         snippet_generator.set_max_num_chars(options.snippet_length);
         
+        let content_terms = self.content_terms(query.as_ref());
+        
         // Process results
         let mut results = Vec::new();
         for (score, doc_address) in top_docs {
+            if results.len() == options.limit {
+                break;
+            }
+            
             let retrieved_doc = searcher.doc(doc_address)
                 .map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
             
-            // Generate snippets for highlighting
-            let snippets = if let Some(content) = retrieved_doc.get_first(self.content_field) {
-                if let Some(_content_str) = content.as_text() {
+            if !excluded_tags.is_empty() && self.has_any_tag(&retrieved_doc, &excluded_tags) {
+                continue;
+            }
+            
+            // Generate snippets and match ranges for highlighting
+            let (snippets, match_ranges) = if let Some(content) = retrieved_doc.get_first(self.content_field) {
+                if let Some(content_str) = content.as_text() {
                     let snippet = snippet_generator.snippet_from_doc(&retrieved_doc);
-                    vec![snippet.to_html()]
+                    (vec![snippet.to_html()], self.match_ranges(&content_terms, content_str))
                 } else {
-                    Vec::new()
+                    (Vec::new(), Vec::new())
                 }
             } else {
-                Vec::new()
+                (Vec::new(), Vec::new())
             };
             
-            let hit = self.process_hit(&retrieved_doc, score, snippets)?;
+            let hit = self.process_hit(&retrieved_doc, score, snippets, match_ranges)?;
             results.push(hit);
         }
         
-        Ok(results)
+        Ok((results, total_hits))
     }
     
     fn search_by_field(&self, field: &str, value: &str, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError> {
@@ -214,18 +530,19 @@ impl QueryEngine for TantivyQueryEngine {
         
         let searcher = self.reader.searcher();
         
-        // Get the field
-        let field = match field {
-            "title" => self.title_field,
-            "content" => self.content_field,
-            "tags" => self.tags_field,
-            "id" => self.id_field,
-            "file_type" => self.file_type_field,
+        // Get the field; tokenized text fields are stored lowercase in the index
+        let (field, value) = match field {
+            "title" => (self.title_field, self.analyzed_term(self.title_field, value)),
+            "content" => (self.content_field, self.analyzed_term(self.content_field, value)),
+            "tags" => (self.tags_field, value.to_lowercase()),
+            "id" => (self.id_field, value.to_string()),
+            "file_type" => (self.file_type_field, value.to_string()),
+            "links" => (self.links_field, value.trim().to_lowercase()),
             _ => return Err(SearchError::QueryParseError(format!("Invalid field: {}", field))),
         };
         
         // Create term query
-        let term = Term::from_field_text(field, value);
+        let term = Term::from_field_text(field, &value);
         let query = TermQuery::new(term, IndexRecordOption::WithFreqsAndPositions);
         
         // Execute search
@@ -242,10 +559,348 @@ impl QueryEngine for TantivyQueryEngine {
             let retrieved_doc = searcher.doc(doc_address)
                 .map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
             
-            let hit = self.process_hit(&retrieved_doc, score, Vec::new())?;
+            let hit = self.process_hit(&retrieved_doc, score, Vec::new(), Vec::new())?;
             results.push(hit);
         }
         
         Ok(results)
     }
+    
+    fn search_by_tags(&self, tags: &[&str], match_all: bool, options: &SearchOptions) -> Result<Vec<SearchHit>, SearchError> {
+        info!("Executing tag search: {:?} (match all: {})", tags, match_all);
+        
+        let searcher = self.reader.searcher();
+        let occur = if match_all { Occur::Must } else { Occur::Should };
+        
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for tag in tags {
+            // The tags field is tokenized, so tags with several words are looked up as phrases
+            let mut terms: Vec<Term> = self
+                .analyze(self.tags_field, tag)
+                .iter()
+                .map(|token| Term::from_field_text(self.tags_field, token))
+                .collect();
+            
+            let query: Box<dyn Query> = match terms.len() {
+                0 => continue,
+                1 => Box::new(TermQuery::new(terms.remove(0), IndexRecordOption::Basic)),
+                _ => Box::new(PhraseQuery::new(terms)),
+            };
+            
+            clauses.push((occur, query));
+        }
+        
+        if clauses.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let query = BooleanQuery::new(clauses);
+        
+        let top_docs = searcher.search(
+            &query,
+            &TopDocs::with_limit(options.limit),
+        ).map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
+        
+        info!("Tag search returned {} results", top_docs.len());
+        
+        let mut results = Vec::new();
+        for (score, doc_address) in top_docs {
+            let retrieved_doc = searcher.doc(doc_address)
+                .map_err(|e| SearchError::SearchExecutionError(e.to_string()))?;
+            
+            let hit = self.process_hit(&retrieved_doc, score, Vec::new(), Vec::new())?;
+            results.push(hit);
+        }
+        
+        Ok(results)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DEFAULT_SEARCH_LANGUAGE, MIN_SEARCH_WRITER_MEMORY_MB};
+    use crate::notes::normalized_link_targets;
+    use crate::search::index::{IndexableDocument, SearchIndex};
+    use tempfile::TempDir;
+
+    fn document(id: &str, content: &str, tags: &[&str], file_type: &str) -> IndexableDocument {
+        IndexableDocument {
+            id: id.to_string(),
+            title: id.to_string(),
+            content: content.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            created: chrono::Utc::now(),
+            modified: chrono::Utc::now(),
+            file_type: file_type.to_string(),
+            links: normalized_link_targets(content).into_iter().collect(),
+            folder_path: String::new(),
+            word_count: 0,
+            char_count: 0,
+            size_bytes: 0,
+        }
+    }
+
+    fn engine_with_documents(documents: &[IndexableDocument]) -> (TempDir, TantivyQueryEngine) {
+        engine_with_language(documents, DEFAULT_SEARCH_LANGUAGE)
+    }
+
+    fn engine_with_language(documents: &[IndexableDocument], language: &str) -> (TempDir, TantivyQueryEngine) {
+        let temp_dir = TempDir::new().unwrap();
+        let index = TantivyIndex::new(temp_dir.path(), MIN_SEARCH_WRITER_MEMORY_MB, language).unwrap();
+
+        for document in documents {
+            index.add_document(document).unwrap();
+        }
+        index.reader().reload().unwrap();
+
+        let engine = TantivyQueryEngine::new(&index).unwrap();
+        (temp_dir, engine)
+    }
+
+    fn engine_with_document(content: &str) -> (TempDir, TantivyQueryEngine) {
+        engine_with_documents(&[document("note", content, &[], "Markdown")])
+    }
+
+    fn hit_ids(hits: &[SearchHit]) -> Vec<&str> {
+        let mut ids: Vec<&str> = hits.iter().map(|hit| hit.id.as_str()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_fuzzy_search_matches_typo() {
+        let (_temp_dir, engine) = engine_with_document("Notes about programming in Rust");
+        let options = SearchOptions {
+            fuzzy: true,
+            ..Default::default()
+        };
+
+        let hits = engine.search("programing", &options).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "note");
+    }
+
+    #[test]
+    fn test_exact_search_misses_typo() {
+        let (_temp_dir, engine) = engine_with_document("Notes about programming in Rust");
+
+        let hits = engine.search("programing", &SearchOptions::default()).unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_search_ignores_short_terms() {
+        let (_temp_dir, engine) = engine_with_document("Notes about Rust");
+        let options = SearchOptions {
+            fuzzy: true,
+            fuzzy_distance: 2,
+            ..Default::default()
+        };
+
+        // "ru" is within distance 2 of "rust", but short terms are matched exactly
+        let hits = engine.search("ru", &options).unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_search_by_tags_field() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("a", "Rust tips", &["rust", "programming"], "Markdown"),
+            document("b", "More rust", &["Rust"], "PlainText"),
+            document("c", "Programming for cooks", &["food"], "Markdown"),
+        ]);
+
+        let hits = engine.search_by_field("tags", "Rust", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a", "b"]);
+
+        // Content mentioning the tag does not count as a tag match
+        let hits = engine.search_by_field("tags", "programming", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a"]);
+    }
+
+    #[test]
+    fn test_search_by_tags() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("a", "", &["rust", "project-x"], "Markdown"),
+            document("b", "", &["rust"], "Markdown"),
+            document("c", "", &["python"], "Markdown"),
+        ]);
+        let options = SearchOptions::default();
+
+        let hits = engine.search_by_tags(&["Rust", "python"], false, &options).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a", "b", "c"]);
+
+        let hits = engine.search_by_tags(&["rust", "project-x"], true, &options).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a"]);
+
+        assert!(engine.search_by_tags(&[], true, &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_by_file_type_field() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("a", "First", &[], "Markdown"),
+            document("b", "Second", &[], "PlainText"),
+            document("c", "Third", &[], "Markdown"),
+        ]);
+
+        let hits = engine.search_by_field("file_type", "Markdown", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_search_by_id_field() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("Tm90ZXMvQS5tZA==", "First", &[], "Markdown"),
+            document("Tm90ZXMvQi5tZA==", "Second", &[], "Markdown"),
+        ]);
+
+        let hits = engine.search_by_field("id", "Tm90ZXMvQS5tZA==", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["Tm90ZXMvQS5tZA=="]);
+    }
+
+    #[test]
+    fn test_search_by_links_field() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("a", "See [[Rust Notes]] and [[Other|alias]]", &[], "Markdown"),
+            document("b", "Mentions Rust Notes without a link", &[], "Markdown"),
+            document("c", "Also [[rust notes]]", &[], "Markdown"),
+        ]);
+
+        let hits = engine.search_by_field("links", "Rust Notes", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a", "c"]);
+
+        let hits = engine.search_by_field("links", "Other", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a"]);
+    }
+
+    #[test]
+    fn test_german_stemming() {
+        let documents = [document("note", "Wir programmieren jeden Tag", &[], "Markdown")];
+
+        let (_temp_dir, engine) = engine_with_language(&documents, "de");
+        let hits = engine.search("programmiere", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["note"]);
+
+        let hits = engine.search_by_field("content", "Programmiere", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["note"]);
+
+        // Without stemming only the exact word matches
+        let (_temp_dir, engine) = engine_with_language(&documents, DEFAULT_SEARCH_LANGUAGE);
+        assert!(engine.search("programmiere", &SearchOptions::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_language_uses_default_tokenizer() {
+        let (_temp_dir, engine) = engine_with_language(
+            &[document("note", "Notes about programming", &[], "Markdown")],
+            "ja",
+        );
+
+        let hits = engine.search("Programming", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["note"]);
+    }
+
+    #[test]
+    fn test_search_by_invalid_field() {
+        let (_temp_dir, engine) = engine_with_document("Content");
+
+        assert!(engine.search_by_field("author", "me", &SearchOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_min_score_filters_results() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            document("a", "apple apple apple", &[], "Markdown"),
+            document("b", "apple banana cherry date elderberry fig grape", &[], "Markdown"),
+        ]);
+
+        let hits = engine.search("apple", &SearchOptions::default()).unwrap();
+        assert_eq!(hit_ids(&hits), vec!["a", "b"]);
+
+        let options = SearchOptions {
+            min_score: Some(hits[0].score),
+            ..Default::default()
+        };
+        assert_eq!(hit_ids(&engine.search("apple", &options).unwrap()), vec!["a"]);
+    }
+
+    #[test]
+    fn test_exclude_tags_filters_results() {
+        let (_temp_dir, engine) = engine_with_documents(&[
+            // The drafts score highest
+            document("a", "apple apple apple", &["draft"], "Markdown"),
+            document("b", "apple apple apple", &["Draft", "fruit"], "Markdown"),
+            document("c", "apple", &["fruit"], "Markdown"),
+            document("d", "apple", &[], "Markdown"),
+        ]);
+
+        let options = SearchOptions {
+            exclude_tags: vec!["#draft".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(hit_ids(&engine.search("apple", &options).unwrap()), vec!["c", "d"]);
+
+        // Without over-fetching, excluded results take up places within the limit
+        let options = SearchOptions {
+            limit: 2,
+            exclude_tags: vec!["draft".to_string()],
+            over_fetch_factor: 1.0,
+            ..Default::default()
+        };
+        assert!(engine.search("apple", &options).unwrap().is_empty());
+
+        let options = SearchOptions {
+            limit: 2,
+            exclude_tags: vec!["draft".to_string()],
+            over_fetch_factor: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(hit_ids(&engine.search("apple", &options).unwrap()), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn test_path_prefix_filters_results() {
+        let in_folder = |id: &str, folder_path: &str| IndexableDocument {
+            folder_path: folder_path.to_string(),
+            ..document(id, "apple", &[], "Markdown")
+        };
+        let (_temp_dir, engine) = engine_with_documents(&[
+            in_folder("a", ""),
+            in_folder("b", "projects"),
+            in_folder("c", "projects/rust"),
+            in_folder("d", "projects-old"),
+        ]);
+
+        let search = |path_prefix: Option<&str>| {
+            let options = SearchOptions {
+                path_prefix: path_prefix.map(str::to_string),
+                ..Default::default()
+            };
+            let hits = engine.search("apple", &options).unwrap();
+            hit_ids(&hits).into_iter().map(str::to_string).collect::<Vec<_>>()
+        };
+
+        assert_eq!(search(Some("projects")), vec!["b", "c"]);
+        assert_eq!(search(Some("/projects/rust/")), vec!["c"]);
+        assert_eq!(search(Some("project")), Vec::<String>::new());
+        assert_eq!(search(Some("")), vec!["a", "b", "c", "d"]);
+        assert_eq!(search(None), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_search_returns_match_ranges() {
+        let content = "Rust is fast. Rusty tools and RUST crates.";
+        let (_temp_dir, engine) = engine_with_document(content);
+
+        let hits = engine.search("rust", &SearchOptions::default()).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].match_ranges, vec![(0, 4), (30, 34)]);
+        for &(start, end) in &hits[0].match_ranges {
+            assert_eq!(content[start..end].to_lowercase(), "rust");
+        }
+    }
 }
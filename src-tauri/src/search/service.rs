@@ -1,11 +1,15 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use log::info;
+use walkdir::WalkDir;
 
-use crate::notes::{Note, NoteSummary, NoteType};
+use crate::config::{AppConfig, MAX_SEARCH_WRITER_MEMORY_MB, MIN_SEARCH_WRITER_MEMORY_MB};
+use crate::notes::{reading_time_secs, Note, NoteSummary, NoteType};
 use crate::search::error::SearchError;
 use crate::search::index::{SearchIndex, TantivyIndex};
-use crate::search::query::{QueryEngine, SearchOptions, TantivyQueryEngine};
+use crate::search::query::{QueryEngine, SearchHit, SearchOptions, TantivyQueryEngine};
 use crate::search::document::DocumentConverter;
 
 /// Search result with highlighting information
@@ -15,10 +19,75 @@ pub struct SearchResult {
     pub note: NoteSummary,
     /// Highlighted snippets from the content
     pub snippets: Vec<String>,
+    /// Start and end byte offsets of matched terms in the note content
+    #[serde(default)]
+    pub match_ranges: Vec<(usize, usize)>,
     /// Search relevance score
     pub score: f32,
 }
 
+/// A page of search results
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PaginatedSearch {
+    /// Results on the current page
+    pub results: Vec<SearchResult>,
+    /// Total number of matching notes across all pages
+    pub total_hits: usize,
+    /// Current page (1-indexed)
+    pub page: usize,
+    /// Maximum number of results per page
+    pub page_size: usize,
+}
+
+/// Progress of a search index rebuild
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct IndexProgress {
+    /// Number of notes indexed so far
+    pub current: usize,
+    /// Total number of notes to index
+    pub total: usize,
+}
+
+/// Segment count above which optimizing the index is recommended
+const MAX_HEALTHY_SEGMENTS: usize = 50;
+
+/// Overall state of the search index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum HealthStatus {
+    /// No maintenance is needed
+    Healthy,
+    /// The index has many segments; merging them with `optimize` speeds up searching
+    NeedsOptimization,
+}
+
+impl HealthStatus {
+    /// Determines the status of an index from its number of segments
+    fn from_segment_count(segment_count: usize) -> Self {
+        if segment_count > MAX_HEALTHY_SEGMENTS {
+            HealthStatus::NeedsOptimization
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+/// Health information about the search index
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IndexHealth {
+    /// Number of documents in the index
+    pub document_count: usize,
+    /// Total size of the index files in bytes
+    pub index_size_bytes: u64,
+    /// When the index was last rebuilt
+    pub last_rebuilt: DateTime<Utc>,
+    /// Path to the index directory
+    pub index_path: String,
+    /// Number of searchable segments, which grows with every incremental update
+    pub segment_count: usize,
+    /// Whether the index needs maintenance
+    pub health_status: HealthStatus,
+}
+
 /// High-level search service that coordinates index and query operations
 pub struct SearchService {
     /// The search index implementation
@@ -27,6 +96,14 @@ pub struct SearchService {
     query_engine: Arc<dyn QueryEngine + Send + Sync>,
     /// Document converter for converting between Note and IndexableDocument
     document_converter: DocumentConverter,
+    /// Path to the search index directory
+    index_path: PathBuf,
+    /// Maximum number of results fetched for a paginated search
+    max_paginated_results: usize,
+    /// Whether to merge index segments after a rebuild
+    optimize_on_rebuild: bool,
+    /// Whether the index was recreated empty because its schema changed
+    index_recreated: bool,
 }
 
 impl SearchService {
@@ -34,14 +111,18 @@ impl SearchService {
     /// 
     /// # Parameters
     /// * `app_data_dir` - Path to the application data directory
+    /// * `config` - Application configuration
     /// 
     /// # Returns
     /// A new SearchService instance
-    pub fn new(app_data_dir: &Path) -> Result<Self, SearchError> {
+    pub fn new(app_data_dir: &Path, config: &AppConfig) -> Result<Self, SearchError> {
         let index_path = app_data_dir.join("search_index");
         
-        // Create the index
-        let tantivy_index = TantivyIndex::new(&index_path)?;
+        // Create the index; a hand-edited config may be outside the supported range
+        let writer_memory_mb = config
+            .search_writer_memory_mb
+            .clamp(MIN_SEARCH_WRITER_MEMORY_MB, MAX_SEARCH_WRITER_MEMORY_MB);
+        let tantivy_index = TantivyIndex::new(&index_path, writer_memory_mb, &config.search_language)?;
         let index = Arc::new(tantivy_index.clone()) as Arc<dyn SearchIndex + Send + Sync>;
         
         // Create the query engine
@@ -56,9 +137,30 @@ impl SearchService {
             index,
             query_engine,
             document_converter,
+            index_path,
+            max_paginated_results: config.max_paginated_results,
+            optimize_on_rebuild: config.optimize_on_rebuild,
+            index_recreated: tantivy_index.was_recreated(),
         })
     }
     
+    /// Checks whether the index has to be rebuilt before it can be searched
+    /// 
+    /// # Parameters
+    /// * `note_count` - Number of notes in the notes directory
+    /// 
+    /// # Returns
+    /// True if the index was recreated after a schema change, or is empty while notes exist
+    pub fn needs_rebuild(&self, note_count: usize) -> bool {
+        self.index_recreated
+            || (note_count > 0 && self.index.document_count().map_or(true, |count| count == 0))
+    }
+    
+    /// Gets the directory containing the search index
+    pub fn index_path(&self) -> &Path {
+        &self.index_path
+    }
+    
     /// Indexes a note
     /// 
     /// # Parameters
@@ -72,6 +174,22 @@ impl SearchService {
         self.index.add_document(&document)
     }
     
+    /// Indexes multiple notes with a single commit
+    /// 
+    /// Much faster than calling `index_note` for each note of a large batch.
+    /// Existing entries of the notes are replaced; other entries are kept,
+    /// unlike with `rebuild_index`.
+    /// 
+    /// # Parameters
+    /// * `notes` - The notes to index
+    /// 
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn batch_index_notes(&self, notes: &[Note]) -> Result<(), SearchError> {
+        let documents = self.document_converter.notes_to_documents(notes);
+        self.index.add_documents(&documents)
+    }
+    
     /// Removes a note from the index
     /// 
     /// # Parameters
@@ -93,42 +211,85 @@ impl SearchService {
     /// # Returns
     /// List of search results
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, SearchError> {
+        // Tantivy would match every document
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        
         let options = SearchOptions {
             limit,
             ..Default::default()
         };
         
         let hits = self.query_engine.search(query, &options)?;
+        let unique_results = hits_to_results(hits);
         
-        // Deduplicate results by note ID
-        let mut unique_results = Vec::new();
-        let mut seen_ids = std::collections::HashSet::new();
-        
-        for hit in hits {
-            if !seen_ids.contains(&hit.id) {
-                seen_ids.insert(hit.id.clone());
-                
-                let result = SearchResult {
-                    note: NoteSummary {
-                        id: hit.id,
-                        title: hit.title,
-                        created: hit.created,
-                        modified: hit.modified,
-                        tags: hit.tags,
-                        file_type: if hit.file_type.contains("Markdown") {
-                            NoteType::Markdown
-                        } else {
-                            NoteType::PlainText
-                        },
-                    },
-                    snippets: hit.snippets,
-                    score: hit.score,
-                };
-                
-                unique_results.push(result);
-            }
+        info!("Search for '{}' returned {} unique results (after deduplication)", query, unique_results.len());
+        Ok(unique_results)
+    }
+    
+    /// Searches for notes and returns one page of results
+    /// 
+    /// Tantivy has no efficient offset-based pagination: every page fetches the
+    /// top `page * page_size` hits and drops the ones on earlier pages, so deep
+    /// pages get progressively more expensive. The number of fetched hits is
+    /// capped at the configured maximum; pages beyond it come back empty.
+    /// 
+    /// # Parameters
+    /// * `query` - The search query
+    /// * `page` - Page to return (1-indexed)
+    /// * `page_size` - Maximum number of results per page
+    /// 
+    /// # Returns
+    /// The results on the page and the total number of matches
+    pub fn search_paginated(&self, query: &str, page: usize, page_size: usize) -> Result<PaginatedSearch, SearchError> {
+        if page == 0 || page_size == 0 {
+            return Err(SearchError::QueryParseError("Page and page size must be greater than 0".into()));
         }
         
+        let offset = (page - 1).saturating_mul(page_size);
+        let limit = page.saturating_mul(page_size).min(self.max_paginated_results);
+        
+        let options = SearchOptions {
+            limit,
+            ..Default::default()
+        };
+        
+        let (hits, total_hits) = self.query_engine.search_with_count(query, &options)?;
+        let page_hits = hits.into_iter().skip(offset).collect();
+        
+        Ok(PaginatedSearch {
+            results: hits_to_results(page_hits),
+            total_hits,
+            page,
+            page_size,
+        })
+    }
+    
+    /// Sets the maximum number of results fetched for a paginated search
+    /// 
+    /// # Parameters
+    /// * `max_results` - Maximum of `page * page_size` before results are cut off
+    pub fn set_max_paginated_results(&mut self, max_results: usize) {
+        self.max_paginated_results = max_results;
+    }
+    
+    /// Searches for notes using custom search options
+    /// 
+    /// # Parameters
+    /// * `query` - The search query
+    /// * `options` - Options controlling limits, boosts and filters
+    /// 
+    /// # Returns
+    /// List of search results
+    pub fn search_with_options(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchResult>, SearchError> {
+        if query.trim().is_empty() && !options.match_all_on_empty {
+            return Ok(Vec::new());
+        }
+        
+        let hits = self.query_engine.search(query, options)?;
+        let unique_results = hits_to_results(hits);
+        
         info!("Search for '{}' returned {} unique results (after deduplication)", query, unique_results.len());
         Ok(unique_results)
     }
@@ -142,7 +303,6 @@ impl SearchService {
     /// 
     /// # Returns
     /// List of search results
-    #[allow(dead_code)]
     pub fn search_by_field(&self, field: &str, value: &str, limit: usize) -> Result<Vec<SearchResult>, SearchError> {
         let options = SearchOptions {
             limit,
@@ -150,55 +310,141 @@ impl SearchService {
         };
         
         let hits = self.query_engine.search_by_field(field, value, &options)?;
-        
-        // Deduplicate results by note ID
-        let mut unique_results = Vec::new();
-        let mut seen_ids = std::collections::HashSet::new();
-        
-        for hit in hits {
-            if !seen_ids.contains(&hit.id) {
-                seen_ids.insert(hit.id.clone());
-                
-                let result = SearchResult {
-                    note: NoteSummary {
-                        id: hit.id,
-                        title: hit.title,
-                        created: hit.created,
-                        modified: hit.modified,
-                        tags: hit.tags,
-                        file_type: if hit.file_type.contains("Markdown") {
-                            NoteType::Markdown
-                        } else {
-                            NoteType::PlainText
-                        },
-                    },
-                    snippets: hit.snippets,
-                    score: hit.score,
-                };
-                
-                unique_results.push(result);
-            }
-        }
+        let unique_results = hits_to_results(hits);
         
         info!("Field search for '{}={}' returned {} unique results (after deduplication)", field, value, unique_results.len());
         Ok(unique_results)
     }
     
+    /// Finds notes with all or any of the given tags using the indexed tags
+    /// 
+    /// Unlike filtering the note list, this does not read every note. Tags are
+    /// compared case-insensitively and a leading `#` is ignored; results are
+    /// only as fresh as the index.
+    /// 
+    /// # Parameters
+    /// * `tags` - Tags to filter by
+    /// * `match_all` - Whether notes must have all tags instead of any of them
+    /// * `limit` - Maximum number of results to return
+    /// 
+    /// # Returns
+    /// List of search results for the matching notes
+    pub fn search_by_tags(&self, tags: &[&str], match_all: bool, limit: usize) -> Result<Vec<SearchResult>, SearchError> {
+        let tags: Vec<&str> = tags
+            .iter()
+            .map(|tag| tag.trim().trim_start_matches('#'))
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        // Tags are split into words in the index, so "a-b" also matches a note tagged "x-a-b"
+        let has_tag = |result: &SearchResult, tag: &str| {
+            result.note.tags.iter().any(|note_tag| note_tag.eq_ignore_ascii_case(tag))
+        };
+        
+        // Fetch more hits until enough of them survive the exact tag filter
+        let mut fetch_limit = limit;
+        let results = loop {
+            let options = SearchOptions {
+                limit: fetch_limit,
+                ..Default::default()
+            };
+            
+            let hits = self.query_engine.search_by_tags(&tags, match_all, &options)?;
+            let exhausted = hits.len() < fetch_limit;
+            
+            let mut results: Vec<SearchResult> = hits_to_results(hits)
+                .into_iter()
+                .filter(|result| {
+                    if match_all {
+                        tags.iter().all(|tag| has_tag(result, tag))
+                    } else {
+                        tags.iter().any(|tag| has_tag(result, tag))
+                    }
+                })
+                .collect();
+            
+            if results.len() >= limit || exhausted {
+                results.truncate(limit);
+                break results;
+            }
+            
+            fetch_limit = fetch_limit.saturating_mul(4);
+        };
+        
+        info!("Tag search for {:?} returned {} results", tags, results.len());
+        Ok(results)
+    }
+    
+    /// Finds notes that link to a note using the indexed wikilink targets
+    /// 
+    /// Matching is case-insensitive on the link target, so the results are
+    /// only as fresh as the index.
+    /// 
+    /// # Parameters
+    /// * `target_title` - Title of the linked note
+    /// * `limit` - Maximum number of results to return
+    /// 
+    /// # Returns
+    /// List of search results for the linking notes
+    pub fn find_backlinks_indexed(&self, target_title: &str, limit: usize) -> Result<Vec<SearchResult>, SearchError> {
+        self.search_by_field("links", target_title, limit)
+    }
+    
+    /// Removes index entries for notes that no longer exist
+    /// 
+    /// # Parameters
+    /// * `valid_ids` - IDs of all notes currently on disk
+    /// 
+    /// # Returns
+    /// Number of stale entries removed
+    pub fn remove_stale_documents(&self, valid_ids: &[&str]) -> Result<usize, SearchError> {
+        let valid_ids: HashSet<&str> = valid_ids.iter().copied().collect();
+        
+        let stale_ids: Vec<String> = self.index.all_document_ids()?
+            .into_iter()
+            .filter(|id| !valid_ids.contains(id.as_str()))
+            .collect();
+        
+        self.index.remove_documents(&stale_ids)?;
+        
+        info!("Removed {} stale documents from search index", stale_ids.len());
+        Ok(stale_ids.len())
+    }
+    
     /// Rebuilds the search index with all notes
     /// 
     /// # Parameters
     /// * `notes` - All notes to index
+    /// * `on_progress` - Called before indexing starts, periodically while
+    ///   indexing, and with `current == total` once the rebuild completes
     /// 
     /// # Returns
     /// Result indicating success or failure
-    pub fn rebuild_index(&self, notes: &[Note]) -> Result<(), SearchError> {
+    pub fn rebuild_index(&self, notes: &[Note], on_progress: impl Fn(IndexProgress)) -> Result<(), SearchError> {
         info!("Rebuilding search index with {} notes", notes.len());
         
+        let total = notes.len();
+        on_progress(IndexProgress { current: 0, total });
+        
         // Convert notes to documents
         let documents = self.document_converter.notes_to_documents(notes);
         
         // Rebuild the index
-        self.index.rebuild_index(&documents)?;
+        self.index.rebuild_index(&documents, &|current| {
+            if current < total {
+                on_progress(IndexProgress { current, total });
+            }
+        })?;
+        
+        if self.optimize_on_rebuild {
+            self.optimize()?;
+        }
+        
+        on_progress(IndexProgress { current: total, total });
         
         info!("Search index rebuilt successfully");
         Ok(())
@@ -208,17 +454,269 @@ impl SearchService {
     /// 
     /// # Returns
     /// Number of documents in the index
-    #[allow(dead_code)]
     pub fn document_count(&self) -> Result<usize, SearchError> {
         self.index.document_count()
     }
     
-    /// Optimizes the index for better performance
+    /// Gets health information about the search index
+    /// 
+    /// # Parameters
+    /// * `last_rebuilt` - When the index was last rebuilt
+    /// 
+    /// # Returns
+    /// Document count, size, location and segment count of the index
+    pub fn get_health(&self, last_rebuilt: DateTime<Utc>) -> Result<IndexHealth, SearchError> {
+        let document_count = self.index.document_count()?;
+        
+        let mut index_size_bytes = 0;
+        for entry in WalkDir::new(self.index_path()) {
+            let entry = entry.map_err(|e| SearchError::IoError(e.into()))?;
+            if entry.file_type().is_file() {
+                index_size_bytes += entry.metadata().map_err(|e| SearchError::IoError(e.into()))?.len();
+            }
+        }
+        
+        let segment_count = self.index.segment_count()?;
+        
+        Ok(IndexHealth {
+            document_count,
+            index_size_bytes,
+            last_rebuilt,
+            index_path: self.index_path().to_string_lossy().to_string(),
+            segment_count,
+            health_status: HealthStatus::from_segment_count(segment_count),
+        })
+    }
+    
+    /// Checks that the index contains one document per note
+    /// 
+    /// # Parameters
+    /// * `note_count` - Number of notes on disk
+    /// 
+    /// # Returns
+    /// True if the document count equals the number of notes
+    pub fn verify_index(&self, note_count: usize) -> Result<bool, SearchError> {
+        let document_count = self.index.document_count()?;
+        
+        if document_count != note_count {
+            info!("Search index has {} documents but there are {} notes", document_count, note_count);
+        }
+        
+        Ok(document_count == note_count)
+    }
+    
+    /// Optimizes the index by merging its segments into one
     /// 
     /// # Returns
     /// Result indicating success or failure
-    #[allow(dead_code)]
     pub fn optimize(&self) -> Result<(), SearchError> {
         self.index.optimize()
     }
 }
+
+/// Converts search hits to search results, deduplicating by note ID
+/// 
+/// # Parameters
+/// * `hits` - Search hits in relevance order
+/// 
+/// # Returns
+/// Unique search results in relevance order
+fn hits_to_results(hits: Vec<SearchHit>) -> Vec<SearchResult> {
+    let mut unique_results = Vec::new();
+    let mut seen_ids = HashSet::new();
+    
+    for hit in hits {
+        if !seen_ids.contains(&hit.id) {
+            seen_ids.insert(hit.id.clone());
+            
+            let result = SearchResult {
+                note: NoteSummary {
+                    id: hit.id,
+                    title: hit.title,
+                    created: hit.created,
+                    modified: hit.modified,
+                    tags: hit.tags,
+                    file_type: match hit.file_type.as_str() {
+                        "Markdown" => NoteType::Markdown,
+                        "Org" => NoteType::Org,
+                        _ => NoteType::PlainText,
+                    },
+                    word_count: hit.word_count,
+                    char_count: hit.char_count,
+                    reading_time_secs: reading_time_secs(hit.word_count),
+                    size_bytes: hit.size_bytes,
+                    preview: None,
+                },
+                snippets: hit.snippets,
+                match_ranges: hit.match_ranges,
+                score: hit.score,
+            };
+            
+            unique_results.push(result);
+        }
+    }
+    
+    unique_results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Creates a Markdown note whose title is its ID
+    fn test_note(id: &str, content: &str, tags: &[&str]) -> Note {
+        Note {
+            id: id.to_string(),
+            title: id.to_string(),
+            content: content.to_string(),
+            created: Utc::now(),
+            modified: Utc::now(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            file_type: NoteType::Markdown,
+            path: format!("{}.md", id),
+        }
+    }
+
+    #[test]
+    fn test_empty_query_returns_no_results() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        service.rebuild_index(&[test_note("id", "content", &[])], |_| {}).unwrap();
+
+        assert!(service.search("  ", 10).unwrap().is_empty());
+        assert!(service.search_with_options("", &SearchOptions::default()).unwrap().is_empty());
+
+        let options = SearchOptions {
+            match_all_on_empty: true,
+            ..Default::default()
+        };
+        assert_eq!(service.search_with_options("", &options).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_paginated_respects_configured_maximum() {
+        let app_dir = TempDir::new().unwrap();
+        let config = AppConfig {
+            max_paginated_results: 3,
+            ..Default::default()
+        };
+        let mut service = SearchService::new(app_dir.path(), &config).unwrap();
+        let notes: Vec<Note> = (0..5).map(|i| test_note(&format!("n{}", i), "apple", &[])).collect();
+        service.rebuild_index(&notes, |_| {}).unwrap();
+
+        let first_page = service.search_paginated("apple", 1, 2).unwrap();
+        assert_eq!((first_page.results.len(), first_page.total_hits), (2, 5));
+        assert_eq!(service.search_paginated("apple", 2, 2).unwrap().results.len(), 1);
+        assert!(service.search_paginated("apple", 3, 2).unwrap().results.is_empty());
+
+        service.set_max_paginated_results(10);
+        assert_eq!(service.search_paginated("apple", 3, 2).unwrap().results.len(), 1);
+    }
+
+    #[test]
+    fn test_batch_index_notes_replaces_existing_entries() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        service
+            .rebuild_index(&[test_note("a", "apple", &[]), test_note("b", "banana", &[])], |_| {})
+            .unwrap();
+
+        service
+            .batch_index_notes(&[
+                test_note("a", "cherry", &[]),
+                test_note("c", "cherry", &[]),
+                test_note("c", "cherry pie", &[]),
+            ])
+            .unwrap();
+
+        assert_eq!(service.document_count().unwrap(), 3);
+        assert!(service.search("apple", 10).unwrap().is_empty());
+        assert_eq!(service.search("banana", 10).unwrap().len(), 1);
+        assert_eq!(service.search("cherry", 10).unwrap().len(), 2);
+        assert_eq!(service.search("pie", 10).unwrap()[0].note.id, "c");
+    }
+
+    #[test]
+    fn test_search_results_include_note_statistics() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        service.index_note(&test_note("a", "one two three", &[])).unwrap();
+
+        let results = service.search("two", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].note.word_count, 3);
+        assert_eq!(results[0].note.char_count, 13);
+        assert_eq!(results[0].note.size_bytes, 13);
+    }
+
+    #[test]
+    fn test_remove_stale_documents() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        let notes: Vec<Note> = ["a", "b", "c"].iter().map(|id| test_note(id, "apple", &[])).collect();
+        service.rebuild_index(&notes, |_| {}).unwrap();
+
+        assert_eq!(service.remove_stale_documents(&["b"]).unwrap(), 2);
+
+        assert_eq!(service.document_count().unwrap(), 1);
+        assert_eq!(service.search("apple", 10).unwrap()[0].note.id, "b");
+        assert_eq!(service.remove_stale_documents(&["b"]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_health_reports_segment_count() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        service.rebuild_index(&[test_note("a", "", &[]), test_note("b", "", &[])], |_| {}).unwrap();
+        service.index_note(&test_note("c", "", &[])).unwrap();
+
+        let health = service.get_health(Utc::now()).unwrap();
+        assert_eq!(health.segment_count, 2);
+        assert_eq!(health.health_status, HealthStatus::Healthy);
+
+        service.optimize().unwrap();
+        assert_eq!(service.get_health(Utc::now()).unwrap().segment_count, 1);
+
+        assert_eq!(HealthStatus::from_segment_count(MAX_HEALTHY_SEGMENTS), HealthStatus::Healthy);
+        assert_eq!(
+            HealthStatus::from_segment_count(MAX_HEALTHY_SEGMENTS + 1),
+            HealthStatus::NeedsOptimization
+        );
+    }
+
+    #[test]
+    fn test_search_by_tags_matches_exact_tags() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        let notes = [
+            test_note("a", "", &["project-x", "rust"]),
+            test_note("b", "", &["old-project-x"]),
+        ];
+        service.rebuild_index(&notes, |_| {}).unwrap();
+
+        let ids = |results: Vec<SearchResult>| -> Vec<String> {
+            results.into_iter().map(|result| result.note.id).collect()
+        };
+
+        assert_eq!(ids(service.search_by_tags(&["#project-x"], false, 10).unwrap()), vec!["a"]);
+        assert_eq!(ids(service.search_by_tags(&["rust", "project-x"], true, 10).unwrap()), vec!["a"]);
+        assert!(service.search_by_tags(&["rust", "old-project-x"], true, 10).unwrap().is_empty());
+        assert!(service.search_by_tags(&["#"], false, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_by_tags_fills_limit_after_filtering() {
+        let app_dir = TempDir::new().unwrap();
+        let service = SearchService::new(app_dir.path(), &AppConfig::default()).unwrap();
+        // Notes tagged "old-project-x" also match "project-x" in the index
+        let mut notes: Vec<Note> = (0..20)
+            .map(|i| test_note(&format!("old-{}", i), "", &["old-project-x"]))
+            .collect();
+        notes.extend((0..3).map(|i| test_note(&format!("new-{}", i), "", &["project-x"])));
+        service.rebuild_index(&notes, |_| {}).unwrap();
+
+        assert_eq!(service.search_by_tags(&["project-x"], false, 2).unwrap().len(), 2);
+        assert_eq!(service.search_by_tags(&["project-x"], false, 10).unwrap().len(), 3);
+    }
+}
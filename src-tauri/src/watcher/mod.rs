@@ -0,0 +1,243 @@
+use anyhow::{Context, Result};
+use log::{info, warn};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// Name of the event emitted when a note changes on disk
+pub const NOTE_CHANGED_EVENT: &str = "note-changed";
+
+/// Kind of change detected for a note file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteChangeType {
+    /// A note file was created
+    Created,
+    /// A note file was modified
+    Modified,
+    /// A note file was deleted
+    Deleted,
+}
+
+/// Payload of the `note-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteChangedEvent {
+    /// Kind of change
+    pub event_type: NoteChangeType,
+    /// Path of the changed note relative to the notes directory
+    pub path: String,
+}
+
+/// Watches the notes directory and emits events when note files change
+///
+/// Watching stops when the service is dropped.
+pub struct WatcherService {
+    /// Underlying file system watcher
+    _watcher: RecommendedWatcher,
+    /// Directory being watched
+    notes_dir: PathBuf,
+}
+
+impl WatcherService {
+    /// Starts watching a notes directory recursively
+    ///
+    /// # Parameters
+    /// * `app_handle` - Tauri app handle used to emit events
+    /// * `notes_dir` - Path to the notes directory
+    /// * `is_external_note_change` - Decides whether a changed path is a note file changed outside the app
+    /// * `on_change` - Called before events are emitted for a change to note files
+    ///
+    /// # Returns
+    /// A running WatcherService
+    pub fn start(
+        app_handle: AppHandle,
+        notes_dir: &Path,
+        is_external_note_change: fn(&AppHandle, &Path) -> bool,
+        on_change: fn(&AppHandle),
+    ) -> Result<Self> {
+        let watched_dir = notes_dir.to_path_buf();
+
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            match result {
+                Ok(event) => emit_note_changes(&app_handle, &watched_dir, &event, is_external_note_change, on_change),
+                Err(e) => warn!("File watcher error: {}", e),
+            }
+        })
+        .context("Failed to create file watcher")?;
+
+        watcher
+            .watch(notes_dir, RecursiveMode::Recursive)
+            .context("Failed to watch notes directory")?;
+
+        info!("Watching {:?} for external changes", notes_dir);
+
+        Ok(Self {
+            _watcher: watcher,
+            notes_dir: notes_dir.to_path_buf(),
+        })
+    }
+
+    /// Gets the directory being watched
+    pub fn notes_dir(&self) -> &Path {
+        &self.notes_dir
+    }
+}
+
+/// Emits a `note-changed` event for every note file affected by a file system event
+///
+/// # Parameters
+/// * `app_handle` - Tauri app handle used to emit events
+/// * `notes_dir` - Path to the notes directory
+/// * `event` - The file system event
+/// * `is_external_note_change` - Decides whether a changed path is a note file changed outside the app
+/// * `on_change` - Called once before the first event is emitted
+fn emit_note_changes(
+    app_handle: &AppHandle,
+    notes_dir: &Path,
+    event: &Event,
+    is_external_note_change: fn(&AppHandle, &Path) -> bool,
+    on_change: fn(&AppHandle),
+) {
+    let mut notified = false;
+
+    for (event_type, path) in note_changes(event) {
+        let Ok(relative_path) = path.strip_prefix(notes_dir) else {
+            continue;
+        };
+
+        if !is_external_note_change(app_handle, path) {
+            continue;
+        }
+
+        if !notified {
+            on_change(app_handle);
+            notified = true;
+        }
+
+        let payload = NoteChangedEvent {
+            event_type,
+            path: relative_path.to_string_lossy().to_string(),
+        };
+
+        if let Err(e) = app_handle.emit(NOTE_CHANGED_EVENT, payload) {
+            warn!("Failed to emit {} event: {}", NOTE_CHANGED_EVENT, e);
+        }
+    }
+}
+
+/// Maps a file system event to the changes of the paths it affects
+///
+/// Renames are reported as the old path being deleted and the new path being
+/// created, so listeners never keep a note under a path that no longer exists.
+///
+/// # Parameters
+/// * `event` - The file system event
+///
+/// # Returns
+/// The kind of change for each affected path
+fn note_changes(event: &Event) -> Vec<(NoteChangeType, &Path)> {
+    let paths = event.paths.iter().map(PathBuf::as_path);
+
+    match event.kind {
+        EventKind::Create(_) => paths.map(|path| (NoteChangeType::Created, path)).collect(),
+        EventKind::Remove(_) => paths.map(|path| (NoteChangeType::Deleted, path)).collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            paths.map(|path| (NoteChangeType::Deleted, path)).collect()
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            paths.map(|path| (NoteChangeType::Created, path)).collect()
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            // The first path is the source of the rename, the second its target
+            paths
+                .enumerate()
+                .map(|(i, path)| {
+                    let event_type = if i == 0 { NoteChangeType::Deleted } else { NoteChangeType::Created };
+                    (event_type, path)
+                })
+                .collect()
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => {
+            // Some platforms do not tell which side of the rename a path is on
+            paths
+                .map(|path| {
+                    let event_type = if path.exists() { NoteChangeType::Created } else { NoteChangeType::Deleted };
+                    (event_type, path)
+                })
+                .collect()
+        }
+        EventKind::Modify(_) => paths.map(|path| (NoteChangeType::Modified, path)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange, RemoveKind};
+    use tempfile::TempDir;
+
+    fn kinds(event: &Event) -> Vec<(String, PathBuf)> {
+        note_changes(event)
+            .into_iter()
+            .map(|(event_type, path)| (format!("{:?}", event_type), path.to_path_buf()))
+            .collect()
+    }
+
+    #[test]
+    fn test_note_changes_create_modify_remove() {
+        let event = Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from("/notes/a.md"));
+        assert_eq!(kinds(&event), vec![("Created".to_string(), PathBuf::from("/notes/a.md"))]);
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+            .add_path(PathBuf::from("/notes/a.md"));
+        assert_eq!(kinds(&event), vec![("Modified".to_string(), PathBuf::from("/notes/a.md"))]);
+
+        let event = Event::new(EventKind::Remove(RemoveKind::File)).add_path(PathBuf::from("/notes/a.md"));
+        assert_eq!(kinds(&event), vec![("Deleted".to_string(), PathBuf::from("/notes/a.md"))]);
+
+        let event = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/notes/a.md"));
+        assert!(kinds(&event).is_empty());
+    }
+
+    #[test]
+    fn test_note_changes_maps_renames_to_delete_and_create() {
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/notes/a.md"))
+            .add_path(PathBuf::from("/notes/b.md"));
+        assert_eq!(
+            kinds(&event),
+            vec![
+                ("Deleted".to_string(), PathBuf::from("/notes/a.md")),
+                ("Created".to_string(), PathBuf::from("/notes/b.md")),
+            ]
+        );
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From)))
+            .add_path(PathBuf::from("/notes/a.md"));
+        assert_eq!(kinds(&event), vec![("Deleted".to_string(), PathBuf::from("/notes/a.md"))]);
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To)))
+            .add_path(PathBuf::from("/notes/b.md"));
+        assert_eq!(kinds(&event), vec![("Created".to_string(), PathBuf::from("/notes/b.md"))]);
+    }
+
+    #[test]
+    fn test_note_changes_resolves_ambiguous_renames() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("b.md");
+        std::fs::write(&existing, "b").unwrap();
+        let missing = temp_dir.path().join("a.md");
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Any)))
+            .add_path(missing.clone())
+            .add_path(existing.clone());
+        assert_eq!(
+            kinds(&event),
+            vec![("Deleted".to_string(), missing), ("Created".to_string(), existing)]
+        );
+    }
+}
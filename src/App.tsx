@@ -94,7 +94,7 @@ function App() {
   const loadNotes = useCallback(async () => {
     try {
       setNotesLoading(true);
-      const notes = await invoke<NoteSummary[]>('list_notes', { sort: sortOption });
+      const notes = await invoke<NoteSummary[]>('list_notes', { sort: [sortOption] });
       setNotes(notes);
       setAllTags(collectAllTags(notes));
       setNotesLoading(false);
@@ -154,7 +154,7 @@ function App() {
       const filtered = await invoke<NoteSummary[]>('filter_notes_by_tags', {
         tags: selectedTags,
         matchAll: matchAllTags,
-        sort: sortOption
+        sort: [sortOption]
       });
       
       setFilteredNotes(filtered);
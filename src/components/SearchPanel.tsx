@@ -1,6 +1,6 @@
 import React, { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { SearchResult } from '../types';
+import { SearchError, SearchResult } from '../types';
 import { debounce } from 'lodash';
 import { Icon, IconName } from '../components/common';
 
@@ -66,7 +66,7 @@ export const SearchPanel: React.FC<SearchPanelProps> = ({
         setError(null);
       } catch (err) {
         console.error('Search failed:', err);
-        setError(`Search failed: ${err}`);
+        setError(`Search failed: ${(err as SearchError).message ?? err}`);
         setResults([]);
       } finally {
         setSearching(false);
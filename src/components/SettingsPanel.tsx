@@ -1,7 +1,7 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
-import { AppConfig, NoteType, AutoUpdateMode } from '../types';
+import { AppConfig, NoteType, AutoUpdateMode, PatternValidationError } from '../types';
 import { defaultProviderRegistry } from '../providers/llm/ProviderRegistry';
 import './SettingsPanel.css';
 
@@ -169,11 +169,6 @@ export const SettingsPanel: React.FC<SettingsPanelProps> = ({
    * Updates the configuration with the new pattern
    */
   const handleSavePattern = async () => {
-    if (!namingPattern.includes('{title}')) {
-      setPatternError('Pattern must include {title} placeholder');
-      return;
-    }
-    
     try {
       setSavingPattern(true);
       setPatternError(null);
@@ -194,7 +189,7 @@ export const SettingsPanel: React.FC<SettingsPanelProps> = ({
       }, 3000);
     } catch (error) {
       console.error('Failed to save naming pattern:', error);
-      setPatternError(`Failed to save naming pattern: ${error}`);
+      setPatternError(`Failed to save naming pattern: ${(error as PatternValidationError).message ?? error}`);
       setSavingPattern(false);
     }
   };
@@ -405,7 +400,7 @@ export const SettingsPanel: React.FC<SettingsPanelProps> = ({
             </button>
           </div>
           <div className="pattern-help">
-            Available placeholders: {'{number}'}, {'{title}'}, {'{extension}'}
+            Available placeholders: {'{number}'}, {'{title}'}, {'{date}'}, {'{datetime}'}, {'{extension}'}
           </div>
           {patternError && <div className="error-message">{patternError}</div>}
           {patternSuccess && <div className="success-message">Naming pattern saved successfully!</div>}
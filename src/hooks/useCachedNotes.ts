@@ -104,6 +104,11 @@ export const useCachedNotes = () => {
       setNoteLoading(true);
       setError(null);
       
+      // Remember the note as recently viewed; failing to do so does not fail loading it
+      invoke('set_note_viewed', { id }).catch(err => {
+        console.error('Failed to record note view:', err);
+      });
+      
       // Check if the note is in the cache
       const cachedNote = getFromCache(id);
       
@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import { Note, NoteType, SearchResult } from '../types';
+import { Note, NoteType, SearchError, SearchResult } from '../types';
 import { Tool } from './types';
 
 /**
@@ -100,7 +100,7 @@ export const searchNotesTool: Tool = {
       return results;
     } catch (error) {
       console.error('Error searching notes:', error);
-      throw new Error(`Failed to search notes: ${error}`);
+      throw new Error(`Failed to search notes: ${(error as SearchError).message ?? error}`);
     }
   }
 };
@@ -101,7 +101,12 @@ export enum NoteType {
   /**
    * Plain text note
    */
-  PlainText = "PlainText"
+  PlainText = "PlainText",
+  
+  /**
+   * Emacs Org-mode note
+   */
+  Org = "Org"
 }
 
 /**
@@ -198,12 +203,47 @@ export interface SearchResult {
    */
   snippets: string[];
   
+  /**
+   * Start and end byte offsets of matched terms in the note content
+   */
+  match_ranges: [number, number][];
+  
   /**
    * Search relevance score
    */
   score: number;
 }
 
+/**
+ * Error returned by search commands
+ */
+export interface SearchError {
+  /**
+   * Stable identifier of the error kind (e.g. "query_parse")
+   */
+  code: string;
+  
+  /**
+   * Human-readable error message
+   */
+  message: string;
+}
+
+/**
+ * Error returned when a note naming pattern is invalid
+ */
+export interface PatternValidationError {
+  /**
+   * Human-readable error message
+   */
+  message: string;
+  
+  /**
+   * Unknown placeholders in the pattern, including their braces (e.g. "{titl}")
+   */
+  invalid_placeholders: string[];
+}
+
 /**
  * Information about a subnote with hierarchy depth
  */